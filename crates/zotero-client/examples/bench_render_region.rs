@@ -0,0 +1,140 @@
+//! Benchmark region rendering against rendering the full page and cropping it.
+//! Run: cargo run --release --package zotero-client --features image --example bench_render_region -- <pdf> [page] [dpi]
+//!
+//! `page` is zero-based (default 0) and `dpi` defaults to 300. The region is
+//! the top-left quarter of the page, about the size of a figure on a poster.
+//! Besides the time per render, it prints the size of the pixmap each approach
+//! allocates, which dominates peak memory.
+
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::ImageEncoder;
+use mupdf::{Colorspace, Document, Matrix};
+use zotero_client::image::{render_region, ImageFormat, RenderLimits};
+
+const RUNS: u32 = 5;
+
+/// The previous implementation: render the whole page, then copy the rows of the region.
+/// Returns the encoded region and the size in bytes of the full-page pixmap.
+fn render_full_and_crop(
+    pdf_path: &str,
+    page_num: usize,
+    rect: [f64; 4],
+    dpi: u32,
+) -> (String, usize) {
+    let doc = Document::open(pdf_path).expect("open PDF");
+    let page = doc.load_page(page_num as i32).expect("load page");
+    let bounds = page.bounds().expect("page bounds");
+    let page_height = bounds.y1 - bounds.y0;
+
+    let clip_x1 = (rect[0] as f32).max(bounds.x0);
+    let clip_y1 = (page_height - rect[3] as f32).max(bounds.y0);
+    let clip_x2 = (rect[2] as f32).min(bounds.x1);
+    let clip_y2 = (page_height - rect[1] as f32).min(bounds.y1);
+
+    let scale = dpi as f32 / 72.0;
+    let full = page
+        .to_pixmap(
+            &Matrix::new_scale(scale, scale),
+            &Colorspace::device_rgb(),
+            true,
+            true,
+        )
+        .expect("render page");
+
+    let px_x1 = ((clip_x1 - bounds.x0) * scale) as u32;
+    let px_y1 = ((clip_y1 - bounds.y0) * scale) as u32;
+    let px_x2 = ((clip_x2 - bounds.x0) * scale) as u32;
+    let px_y2 = ((clip_y2 - bounds.y0) * scale) as u32;
+    let width = px_x2 - px_x1;
+    let height = px_y2 - px_y1;
+
+    let samples = full.samples();
+    let n = full.n() as u32;
+    let mut region = Vec::with_capacity((width * height * n) as usize);
+    for y in px_y1..px_y2 {
+        let start = ((y * full.width() + px_x1) * n) as usize;
+        region.extend_from_slice(&samples[start..start + (width * n) as usize]);
+    }
+
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(&region, width, height, image::ExtendedColorType::Rgba8)
+        .expect("encode PNG");
+    (STANDARD.encode(&png), samples.len())
+}
+
+fn time<T>(mut f: impl FnMut() -> T) -> (Duration, T) {
+    let mut result = f();
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        result = f();
+    }
+    (start.elapsed() / RUNS, result)
+}
+
+fn mib(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(pdf_path) = args.next() else {
+        eprintln!("Usage: bench_render_region <pdf> [page] [dpi]");
+        std::process::exit(2);
+    };
+    let page_num: usize = args.next().map_or(0, |a| a.parse().expect("page number"));
+    let dpi: u32 = args.next().map_or(300, |a| a.parse().expect("DPI"));
+
+    let bounds = Document::open(&pdf_path)
+        .and_then(|doc| doc.load_page(page_num as i32))
+        .and_then(|page| page.bounds())
+        .expect("page bounds");
+    let (width, height) = (
+        (bounds.x1 - bounds.x0) as f64,
+        (bounds.y1 - bounds.y0) as f64,
+    );
+    let rect = [0.0, height / 2.0, width / 2.0, height];
+
+    println!(
+        "Page {} ({:.0}x{:.0} pt) at {} DPI, region {:?}, {} runs\n",
+        page_num, width, height, dpi, rect, RUNS
+    );
+
+    let (full_time, (full_data, full_bytes)) =
+        time(|| render_full_and_crop(&pdf_path, page_num, rect, dpi));
+    let (region_time, region) = time(|| {
+        render_region(
+            &pdf_path,
+            page_num,
+            rect,
+            dpi,
+            ImageFormat::Png,
+            RenderLimits::default(),
+        )
+        .expect("render region")
+    });
+
+    // The clipped pixmap is RGBA, like the full page.
+    let region_bytes = region.width as usize * region.height as usize * 4;
+    println!(
+        "{:<22} {:>10.1} ms {:>10.1} MiB pixmap",
+        "full page + crop",
+        full_time.as_secs_f64() * 1000.0,
+        mib(full_bytes)
+    );
+    println!(
+        "{:<22} {:>10.1} ms {:>10.1} MiB pixmap",
+        "render_region",
+        region_time.as_secs_f64() * 1000.0,
+        mib(region_bytes)
+    );
+    println!(
+        "\nSpeedup: {:.1}x, output {}x{} px, identical: {}",
+        full_time.as_secs_f64() / region_time.as_secs_f64(),
+        region.width,
+        region.height,
+        full_data == region.data
+    );
+}
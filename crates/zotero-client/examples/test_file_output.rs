@@ -1,7 +1,7 @@
 //! Test file-based image rendering.
 //! Run: cargo run --package zotero-client --features image --example test_file_output
 
-use zotero_client::image::{render_page_to_file, ImageFormat};
+use zotero_client::image::{render_page_to_file, ImageFormat, RenderLimits};
 
 fn main() {
     let pdf_path =
@@ -21,13 +21,20 @@ fn main() {
         };
         let output_path = format!("/tmp/test-page-{dpi}dpi.{ext}");
 
-        match render_page_to_file(pdf_path, 0, dpi, format, &output_path) {
-            Ok(saved_path) => {
-                let metadata = std::fs::metadata(&saved_path).unwrap();
+        match render_page_to_file(
+            pdf_path,
+            0,
+            dpi,
+            format,
+            RenderLimits::default(),
+            &output_path,
+        ) {
+            Ok(saved) => {
+                let metadata = std::fs::metadata(&saved.path).unwrap();
                 println!(
                     "✓ {dpi} DPI {:?}: {} ({:.1} KB)",
                     format,
-                    saved_path,
+                    saved.path,
                     metadata.len() / 1024
                 );
             }
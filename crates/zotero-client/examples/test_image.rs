@@ -1,7 +1,7 @@
 //! Quick test for image extraction.
 //! Run: cargo run --package zotero-client --features image --example test_image
 
-use zotero_client::image::{detect_figures, render_page, render_region, ImageFormat};
+use zotero_client::image::{detect_figures, render_page, render_region, ImageFormat, RenderLimits};
 
 fn main() {
     // Use a known PDF from Zotero storage
@@ -12,7 +12,7 @@ fn main() {
 
     // Test 1: Render page 1
     println!("=== Test 1: Render Page 1 (PNG, 150 DPI) ===");
-    match render_page(pdf_path, 0, 150, ImageFormat::Png) {
+    match render_page(pdf_path, 0, 150, ImageFormat::Png, RenderLimits::default()) {
         Ok(output) => {
             println!("  SUCCESS!");
             println!("  Dimensions: {}x{} pixels", output.width, output.height);
@@ -33,7 +33,7 @@ fn main() {
 
     // Test 2: Render as JPEG
     println!("\n=== Test 2: Render Page 1 (JPEG, 100 DPI) ===");
-    match render_page(pdf_path, 0, 100, ImageFormat::Jpeg) {
+    match render_page(pdf_path, 0, 100, ImageFormat::Jpeg, RenderLimits::default()) {
         Ok(output) => {
             println!("  SUCCESS!");
             println!("  Dimensions: {}x{} pixels", output.width, output.height);
@@ -68,7 +68,14 @@ fn main() {
             // Test 4: Render first detected figure
             if let Some(fig) = figures.first() {
                 println!("\n=== Test 4: Render First Detected Figure ===");
-                match render_region(pdf_path, 0, fig.rect, 150, ImageFormat::Png) {
+                match render_region(
+                    pdf_path,
+                    0,
+                    fig.rect,
+                    150,
+                    ImageFormat::Png,
+                    RenderLimits::default(),
+                ) {
                     Ok(output) => {
                         println!("  SUCCESS!");
                         println!(
@@ -95,7 +102,14 @@ fn main() {
     // Test 5: Render a specific region
     println!("\n=== Test 5: Render Specific Region ===");
     let test_rect = [100.0, 100.0, 400.0, 300.0]; // A test region
-    match render_region(pdf_path, 0, test_rect, 150, ImageFormat::Png) {
+    match render_region(
+        pdf_path,
+        0,
+        test_rect,
+        150,
+        ImageFormat::Png,
+        RenderLimits::default(),
+    ) {
         Ok(output) => {
             println!("  SUCCESS!");
             println!(
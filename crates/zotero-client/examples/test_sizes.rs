@@ -1,7 +1,7 @@
 //! Test to check base64 output sizes at different DPIs.
 //! Run: cargo run --package zotero-client --features image --example test_sizes
 
-use zotero_client::image::{render_page, ImageFormat};
+use zotero_client::image::{render_page, ImageFormat, RenderLimits};
 
 fn main() {
     let pdf_path =
@@ -16,8 +16,8 @@ fn main() {
 
     for dpi in [50, 72, 100, 150] {
         // Test PNG
-        let png_result = render_page(pdf_path, 0, dpi, ImageFormat::Png);
-        let jpeg_result = render_page(pdf_path, 0, dpi, ImageFormat::Jpeg);
+        let png_result = render_page(pdf_path, 0, dpi, ImageFormat::Png, RenderLimits::default());
+        let jpeg_result = render_page(pdf_path, 0, dpi, ImageFormat::Jpeg, RenderLimits::default());
 
         match (png_result, jpeg_result) {
             (Ok(png), Ok(jpeg)) => {
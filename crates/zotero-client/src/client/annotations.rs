@@ -0,0 +1,295 @@
+//! Annotation operations on PDF attachments.
+
+use super::ZoteroClient;
+use crate::error::{Result, ZoteroClientError};
+use crate::types::{
+    AnnotationFilter, CreateAnnotationRequest, CreateAnnotationResponse,
+    CreateAreaAnnotationRequest, DeleteAnnotationResponse, UpdateAnnotationRequest,
+    ZoteroAnnotation,
+};
+
+impl ZoteroClient {
+    /// List the annotations on a PDF attachment, in reading order.
+    ///
+    /// Annotations are returned sorted by their Zotero sort index, which
+    /// orders them by page and then by position on the page.
+    ///
+    /// # Arguments
+    ///
+    /// * `attachment_key` - The PDF attachment key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::ZoteroClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// for annotation in client.list_annotations("PDF_KEY").await? {
+    ///     println!("{:?}: {:?}", annotation.page_label, annotation.text);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_annotations(&self, attachment_key: &str) -> Result<Vec<ZoteroAnnotation>> {
+        let children = self.get_children(attachment_key).await?;
+
+        let mut annotations: Vec<ZoteroAnnotation> = children
+            .children
+            .into_iter()
+            .filter(|child| child.get("itemType").and_then(|t| t.as_str()) == Some("annotation"))
+            .map(serde_json::from_value::<ZoteroAnnotation>)
+            .collect::<std::result::Result<_, _>>()?;
+
+        for annotation in &mut annotations {
+            if annotation.parent_item_key.is_none() {
+                annotation.parent_item_key = Some(attachment_key.to_string());
+            }
+        }
+
+        annotations.sort_by(|a, b| a.sort_index.cmp(&b.sort_index));
+        Ok(annotations)
+    }
+
+    /// List the annotations on a PDF attachment that match a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `attachment_key` - The PDF attachment key
+    /// * `filter` - Criteria the annotations must satisfy
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::{ZoteroClient, types::{AnnotationFilter, HighlightColor}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// let filter = AnnotationFilter::new().with_semantic_color(HighlightColor::Question);
+    /// let questions = client.list_annotations_matching("PDF_KEY", &filter).await?;
+    /// println!("{} open questions", questions.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_annotations_matching(
+        &self,
+        attachment_key: &str,
+        filter: &AnnotationFilter,
+    ) -> Result<Vec<ZoteroAnnotation>> {
+        let annotations = self.list_annotations(attachment_key).await?;
+        Ok(annotations
+            .into_iter()
+            .filter(|annotation| filter.matches(annotation))
+            .collect())
+    }
+
+    /// Create an annotation on a PDF attachment.
+    ///
+    /// Handles every type built by [`CreateAnnotationRequest`]: highlight,
+    /// underline, note and ink annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The annotation creation request
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::{ZoteroClient, types::CreateAnnotationRequest};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// let request = CreateAnnotationRequest::highlight(
+    ///     "PDF_KEY",
+    ///     "Important text to highlight",
+    ///     0, // page index
+    ///     vec![[100.0, 200.0, 300.0, 220.0].to_vec()],
+    /// )
+    /// .with_comment("This is important!")
+    /// .with_color("#ff6666");
+    ///
+    /// let result = client.create_annotation(request).await?;
+    /// if result.success {
+    ///     println!("Created annotation: {:?}", result.annotation);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_annotation(
+        &self,
+        request: CreateAnnotationRequest,
+    ) -> Result<CreateAnnotationResponse> {
+        let url = format!("{}/annotations", self.base_url);
+
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound {
+                key: request.parent_item_key,
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Create an area/image annotation on a PDF attachment.
+    ///
+    /// Area annotations are used for selecting regions like figures, diagrams,
+    /// or images. They use `annotationType: "image"` and don't require text content.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The area annotation creation request
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::{ZoteroClient, types::{CreateAreaAnnotationRequest, HighlightColor}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// let request = CreateAreaAnnotationRequest::new(
+    ///     "PDF_KEY",
+    ///     0, // page index
+    ///     [100.0, 200.0, 300.0, 400.0], // rect [x1, y1, x2, y2]
+    /// )
+    /// .with_comment("Figure 1: System architecture")
+    /// .with_semantic_color(HighlightColor::Section1);
+    ///
+    /// let result = client.create_area_annotation(request).await?;
+    /// if result.success {
+    ///     println!("Created area annotation: {:?}", result.annotation);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_area_annotation(
+        &self,
+        request: CreateAreaAnnotationRequest,
+    ) -> Result<CreateAnnotationResponse> {
+        let url = format!("{}/annotations", self.base_url);
+
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound {
+                key: request.parent_item_key,
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Update an existing annotation.
+    ///
+    /// Only the fields set on the request are changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The annotation update request
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if no annotation with the key exists, or
+    /// `InvalidInput` if the request does not change anything.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::{ZoteroClient, types::{HighlightColor, UpdateAnnotationRequest}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// let request = UpdateAnnotationRequest::new("ANN_KEY")
+    ///     .with_comment("Actually a limitation, not a strength")
+    ///     .with_semantic_color(HighlightColor::Negative);
+    ///
+    /// let result = client.update_annotation(request).await?;
+    /// println!("Updated: {}", result.success);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_annotation(
+        &self,
+        request: UpdateAnnotationRequest,
+    ) -> Result<CreateAnnotationResponse> {
+        if request.is_empty() {
+            return Err(ZoteroClientError::InvalidInput(format!(
+                "update for annotation {} does not change any field",
+                request.key
+            )));
+        }
+
+        let url = format!("{}/annotations", self.base_url);
+        let mut body = serde_json::to_value(&request)?;
+        body["action"] = "update".into();
+
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound { key: request.key });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Delete an annotation.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The annotation item key
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if no annotation with the key exists.
+    pub async fn delete_annotation(&self, key: &str) -> Result<DeleteAnnotationResponse> {
+        let url = format!("{}/annotations", self.base_url);
+        let body = serde_json::json!({
+            "action": "delete",
+            "key": key
+        });
+
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+}
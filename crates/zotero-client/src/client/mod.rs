@@ -1,14 +1,18 @@
 //! HTTP client for the Zotero MCP plugin API.
 
+use std::path::{Path, PathBuf};
+
 use reqwest::Client;
 
 use crate::error::{Result, ZoteroClientError};
+use crate::resolver::AttachmentIndex;
 use crate::types::{
-    ChildrenResponse, CreateAnnotationRequest, CreateAnnotationResponse,
-    CreateAreaAnnotationRequest, ItemsResponse, PingResponse, SearchResponse, ZoteroAttachment,
-    ZoteroItem,
+    ChildrenResponse, ItemsResponse, PingResponse, SearchResponse, ZoteroAttachment, ZoteroItem,
 };
 
+mod annotations;
+mod notes;
+
 /// Default base URL for the Zotero MCP plugin.
 pub const DEFAULT_BASE_URL: &str = "http://localhost:23119/mcp";
 
@@ -40,6 +44,7 @@ pub const DEFAULT_BASE_URL: &str = "http://localhost:23119/mcp";
 pub struct ZoteroClient {
     client: Client,
     base_url: String,
+    attachments: AttachmentIndex,
 }
 
 impl Default for ZoteroClient {
@@ -67,9 +72,20 @@ impl ZoteroClient {
         Self {
             client: Client::new(),
             base_url: base_url.into(),
+            attachments: AttachmentIndex::new(),
         }
     }
 
+    /// Get the attachment key → file path index used by [`resolve_pdf_path`].
+    ///
+    /// The index is shared between all clones of this client. Use it to
+    /// invalidate entries when an attachment is moved or deleted in Zotero.
+    ///
+    /// [`resolve_pdf_path`]: Self::resolve_pdf_path
+    pub fn attachment_index(&self) -> &AttachmentIndex {
+        &self.attachments
+    }
+
     /// Check if the Zotero MCP plugin is active.
     ///
     /// # Errors
@@ -220,6 +236,7 @@ impl ZoteroClient {
     /// Get PDF attachments for an item.
     ///
     /// This is a convenience method that filters children to only PDF attachments.
+    /// The paths of the returned attachments are recorded in the attachment index.
     pub async fn get_pdf_attachments(&self, key: &str) -> Result<Vec<ZoteroAttachment>> {
         let children = self.get_children(key).await?;
         let mut pdfs = Vec::new();
@@ -227,6 +244,9 @@ impl ZoteroClient {
         for child in children.children {
             if let Ok(attachment) = serde_json::from_value::<ZoteroAttachment>(child) {
                 if attachment.content_type.as_deref() == Some("application/pdf") {
+                    if let Some(path) = &attachment.path {
+                        self.attachments.insert(&attachment.key, path);
+                    }
                     pdfs.push(attachment);
                 }
             }
@@ -235,47 +255,27 @@ impl ZoteroClient {
         Ok(pdfs)
     }
 
-    /// Create an annotation on a PDF attachment.
+    /// Get an attachment item directly by its key.
     ///
     /// # Arguments
     ///
-    /// * `request` - The annotation creation request
+    /// * `key` - The attachment item key
     ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use zotero_client::{ZoteroClient, types::CreateAnnotationRequest};
-    ///
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = ZoteroClient::new();
+    /// # Errors
     ///
-    /// let request = CreateAnnotationRequest::highlight(
-    ///     "PDF_KEY",
-    ///     "Important text to highlight",
-    ///     0, // page index
-    ///     vec![[100.0, 200.0, 300.0, 220.0].to_vec()],
-    /// )
-    /// .with_comment("This is important!")
-    /// .with_color("#ff6666");
-    ///
-    /// let result = client.create_annotation(request).await?;
-    /// if result.success {
-    ///     println!("Created annotation: {:?}", result.annotation);
-    /// }
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn create_annotation(
-        &self,
-        request: CreateAnnotationRequest,
-    ) -> Result<CreateAnnotationResponse> {
-        let url = format!("{}/annotations", self.base_url);
+    /// Returns `NotFound` if no item with this key exists, or `InvalidInput`
+    /// if the item is not an attachment.
+    pub async fn get_attachment(&self, key: &str) -> Result<ZoteroAttachment> {
+        let url = format!("{}/item", self.base_url);
+        let body = serde_json::json!({
+            "key": key
+        });
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let response = self.client.post(&url).json(&body).send().await?;
 
         if response.status().as_u16() == 404 {
             return Err(ZoteroClientError::NotFound {
-                key: request.parent_item_key,
+                key: key.to_string(),
             });
         }
 
@@ -286,63 +286,67 @@ impl ZoteroClient {
             });
         }
 
-        Ok(response.json().await?)
+        let attachment: ZoteroAttachment = response.json().await?;
+        if attachment.item_type != "attachment" {
+            return Err(ZoteroClientError::InvalidInput(format!(
+                "item {} is a {}, not an attachment",
+                key, attachment.item_type
+            )));
+        }
+
+        Ok(attachment)
     }
 
-    /// Create an area/image annotation on a PDF attachment.
+    /// Resolve a PDF attachment key to its local file path.
     ///
-    /// Area annotations are used for selecting regions like figures, diagrams,
-    /// or images. They use `annotationType: "image"` and don't require text content.
+    /// Paths are served from the attachment index when possible; otherwise the
+    /// attachment is looked up directly by key and the result is cached. Cached
+    /// entries whose file has disappeared are re-resolved automatically.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `request` - The area annotation creation request
+    /// Returns `NotFound` if the key does not refer to a PDF attachment with a
+    /// local file, or `FileNotFound` if Zotero reports a path that does not exist.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use zotero_client::{ZoteroClient, types::{CreateAreaAnnotationRequest, HighlightColor}};
-    ///
+    /// # use zotero_client::ZoteroClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = ZoteroClient::new();
-    ///
-    /// let request = CreateAreaAnnotationRequest::new(
-    ///     "PDF_KEY",
-    ///     0, // page index
-    ///     [100.0, 200.0, 300.0, 400.0], // rect [x1, y1, x2, y2]
-    /// )
-    /// .with_comment("Figure 1: System architecture")
-    /// .with_semantic_color(HighlightColor::Section1);
-    ///
-    /// let result = client.create_area_annotation(request).await?;
-    /// if result.success {
-    ///     println!("Created area annotation: {:?}", result.annotation);
-    /// }
+    /// let path = client.resolve_pdf_path("PDF_KEY").await?;
+    /// println!("PDF is at {}", path.display());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create_area_annotation(
-        &self,
-        request: CreateAreaAnnotationRequest,
-    ) -> Result<CreateAnnotationResponse> {
-        let url = format!("{}/annotations", self.base_url);
+    pub async fn resolve_pdf_path(&self, key: &str) -> Result<PathBuf> {
+        if let Some(path) = self.attachments.get(key) {
+            return Ok(path);
+        }
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let attachment = self.get_attachment(key).await.map_err(|e| match e {
+            ZoteroClientError::InvalidInput(_) => ZoteroClientError::NotFound {
+                key: key.to_string(),
+            },
+            other => other,
+        })?;
 
-        if response.status().as_u16() == 404 {
+        if attachment.content_type.as_deref() != Some("application/pdf") {
             return Err(ZoteroClientError::NotFound {
-                key: request.parent_item_key,
+                key: key.to_string(),
             });
         }
 
-        if !response.status().is_success() {
-            return Err(ZoteroClientError::Api {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+        let path = attachment.path.ok_or_else(|| ZoteroClientError::NotFound {
+            key: key.to_string(),
+        })?;
+
+        if !Path::new(&path).exists() {
+            return Err(ZoteroClientError::FileNotFound { path });
         }
 
-        Ok(response.json().await?)
+        self.attachments.insert(key, &path);
+        Ok(PathBuf::from(path))
     }
 
     /// Find an item by its BetterBibTeX citation key.
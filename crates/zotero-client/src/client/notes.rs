@@ -0,0 +1,99 @@
+//! Note operations: standalone and child notes.
+
+use super::ZoteroClient;
+use crate::error::{Result, ZoteroClientError};
+use crate::types::NoteResponse;
+
+impl ZoteroClient {
+    /// Create a note.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_key` - Key of the regular item the note belongs to, or `None`
+    ///   for a standalone note
+    /// * `html` - Note content in Zotero's note HTML
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the parent item does not exist, and an API error
+    /// if it is not a regular item (notes cannot be children of attachments).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zotero_client::ZoteroClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ZoteroClient::new();
+    ///
+    /// let response = client
+    ///     .create_note(Some("ITEM_KEY"), "<h1>Summary</h1><p>Main findings...</p>")
+    ///     .await?;
+    /// if let Some(note) = response.note {
+    ///     println!("Created note {:?}: {:?}", note.key, note.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_note(&self, parent_key: Option<&str>, html: &str) -> Result<NoteResponse> {
+        let url = format!("{}/notes", self.base_url);
+        let body = serde_json::json!({
+            "action": "create",
+            "parentItemKey": parent_key,
+            "note": html
+        });
+
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound {
+                key: parent_key.unwrap_or_default().to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Replace the content of an existing note.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The note item key
+    /// * `html` - New note content in Zotero's note HTML
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if no note with the key exists.
+    pub async fn update_note(&self, key: &str, html: &str) -> Result<NoteResponse> {
+        let url = format!("{}/notes", self.base_url);
+        let body = serde_json::json!({
+            "action": "update",
+            "key": key,
+            "note": html
+        });
+
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(ZoteroClientError::NotFound {
+                key: key.to_string(),
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(ZoteroClientError::Api {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+}
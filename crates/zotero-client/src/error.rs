@@ -29,11 +29,22 @@ pub enum ZoteroClientError {
         key: String,
     },
 
+    /// An attachment exists in Zotero but its file is missing on disk.
+    #[error("attachment file not found: {path}")]
+    FileNotFound {
+        /// The local path Zotero reported for the attachment.
+        path: String,
+    },
+
     /// PDF extraction error (only available with `pdf` feature).
     #[cfg(feature = "pdf")]
     #[error("PDF error: {0}")]
     Pdf(String),
 
+    /// Failed to read or write the full-text index.
+    #[error("index error: {0}")]
+    Index(String),
+
     /// Invalid input provided.
     #[error("invalid input: {0}")]
     InvalidInput(String),
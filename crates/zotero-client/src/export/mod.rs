@@ -0,0 +1,305 @@
+//! Export of annotations to Markdown notes for Obsidian.
+//!
+//! The exported note starts with Dataview-compatible YAML frontmatter and
+//! groups annotations by the meaning of their color: section colors form an
+//! outline of the document, the semantic colors (positive, negative,
+//! question, detail, code) each get a section, and colors outside the scheme
+//! are collected at the end. Every annotation links back to its page in the
+//! source note as `[[citekey#p. X|p. X]]`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::{HighlightColor, ZoteroAnnotation, ZoteroItem};
+
+/// Semantic colors that get their own section, in the order they appear.
+const SEMANTIC_SECTIONS: [HighlightColor; 5] = [
+    HighlightColor::Positive,
+    HighlightColor::Negative,
+    HighlightColor::Question,
+    HighlightColor::Detail,
+    HighlightColor::Code,
+];
+
+/// Render an item's annotations as a Markdown note.
+///
+/// Annotations are rendered in the order given, which for annotations from
+/// [`ZoteroClient::list_annotations`](crate::ZoteroClient::list_annotations)
+/// is reading order. `created` is written to the frontmatter as is; use
+/// [`today`] for the current date.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::export::annotations_to_markdown;
+/// use zotero_client::types::{AnnotationPosition, ZoteroAnnotation, ZoteroItem};
+///
+/// let item: ZoteroItem = serde_json::from_str(
+///     r#"{"key": "ABCD1234", "itemType": "journalArticle", "title": "A Study"}"#,
+/// )?;
+/// let annotation = ZoteroAnnotation {
+///     id: None,
+///     key: Some("ANNOT001".to_string()),
+///     parent_item_key: None,
+///     annotation_type: Some("highlight".to_string()),
+///     text: Some("A supported claim".to_string()),
+///     comment: None,
+///     color: Some("#5fb236".to_string()),
+///     page_label: Some("215".to_string()),
+///     sort_index: None,
+///     position: Some(AnnotationPosition { page_index: 2, ..Default::default() }),
+/// };
+///
+/// let markdown = annotations_to_markdown(&item, "doe2024", &[annotation], "2026-01-31");
+/// assert!(markdown.contains("source: \"[[doe2024]]\""));
+/// assert!(markdown.contains("> A supported claim [[doe2024#p. 215|p. 215]]"));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn annotations_to_markdown(
+    item: &ZoteroItem,
+    citekey: &str,
+    annotations: &[ZoteroAnnotation],
+    created: &str,
+) -> String {
+    let title = item.title.as_deref().unwrap_or(citekey);
+    let mut out = frontmatter(item, citekey, annotations.len(), created);
+    out.push_str(&format!("\n# {}\n", title));
+
+    let outline: Vec<&ZoteroAnnotation> = annotations
+        .iter()
+        .filter(|a| section_level(a).is_some())
+        .collect();
+    if !outline.is_empty() {
+        out.push_str("\n## Outline\n\n");
+        for annotation in outline {
+            out.push_str(&outline_entry(annotation, citekey));
+        }
+    }
+
+    for color in SEMANTIC_SECTIONS {
+        let group: Vec<&ZoteroAnnotation> = annotations
+            .iter()
+            .filter(|a| a.semantic_color() == Some(color))
+            .collect();
+        push_section(&mut out, color.description(), &group, citekey);
+    }
+
+    let other: Vec<&ZoteroAnnotation> = annotations
+        .iter()
+        .filter(|a| a.semantic_color().is_none())
+        .collect();
+    push_section(&mut out, "Other", &other, citekey);
+
+    out
+}
+
+/// Today's date in UTC, formatted as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    date_from_days((seconds / 86_400) as i64)
+}
+
+/// Convert days since 1970-01-01 to a `YYYY-MM-DD` date.
+///
+/// Uses the proleptic Gregorian calendar (Howard Hinnant's `civil_from_days`).
+fn date_from_days(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The YAML frontmatter block, including its `---` delimiters.
+fn frontmatter(item: &ZoteroItem, citekey: &str, count: usize, created: &str) -> String {
+    let mut yaml = vec![
+        "---".to_string(),
+        "type: annotations".to_string(),
+        format!("citekey: {}", yaml_string(citekey)),
+        format!("source: {}", yaml_string(&format!("[[{}]]", citekey))),
+    ];
+    if let Some(title) = &item.title {
+        yaml.push(format!("title: {}", yaml_string(title)));
+    }
+
+    let authors: Vec<String> = item
+        .creators
+        .iter()
+        .filter(|c| c.creator_type.as_deref().unwrap_or("author") == "author")
+        .filter_map(|c| match (&c.last_name, &c.first_name, &c.name) {
+            (Some(last), Some(first), _) if !first.is_empty() => {
+                Some(format!("{}, {}", last, first))
+            }
+            (Some(last), _, _) => Some(last.clone()),
+            (None, _, name) => name.clone(),
+        })
+        .collect();
+    yaml.push(yaml_list("authors", &authors));
+
+    if let Some(year) = item.date.as_deref().and_then(year) {
+        yaml.push(format!("year: {}", year));
+    }
+
+    let tags: Vec<String> = item.tags.iter().map(|t| t.tag.clone()).collect();
+    yaml.push(yaml_list("tags", &tags));
+
+    yaml.push(format!("annotations: {}", count));
+    yaml.push(format!("created: {}", created));
+    yaml.push("---".to_string());
+
+    yaml.join("\n") + "\n"
+}
+
+/// A YAML list field; empty lists are written inline as `[]`.
+fn yaml_list(name: &str, values: &[String]) -> String {
+    if values.is_empty() {
+        return format!("{}: []", name);
+    }
+    let entries: Vec<String> = values
+        .iter()
+        .map(|v| format!("  - {}", yaml_string(v)))
+        .collect();
+    format!("{}:\n{}", name, entries.join("\n"))
+}
+
+/// A double-quoted YAML scalar on a single line.
+fn yaml_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("\"{}\"", escaped)
+}
+
+/// The first four-digit year in a date string.
+fn year(date: &str) -> Option<&str> {
+    date.as_bytes()
+        .windows(4)
+        .position(|w| w.iter().all(u8::is_ascii_digit))
+        .map(|start| &date[start..start + 4])
+}
+
+/// Outline level of a section-colored annotation (1 to 3).
+fn section_level(annotation: &ZoteroAnnotation) -> Option<usize> {
+    match annotation.semantic_color()? {
+        HighlightColor::Section1 => Some(1),
+        HighlightColor::Section2 => Some(2),
+        HighlightColor::Section3 => Some(3),
+        _ => None,
+    }
+}
+
+/// A link to the annotation's page in the source note, if its page is known.
+fn page_link(annotation: &ZoteroAnnotation, citekey: &str) -> Option<String> {
+    let label = annotation
+        .page_label
+        .clone()
+        .filter(|l| !l.is_empty())
+        .or_else(|| annotation.page_index().map(|i| (i + 1).to_string()))?;
+    Some(format!("[[{0}#p. {1}|p. {1}]]", citekey, label))
+}
+
+/// Non-empty, trimmed text and comment of an annotation.
+fn text_and_comment(annotation: &ZoteroAnnotation) -> (Option<&str>, Option<&str>) {
+    fn non_empty(s: &Option<String>) -> Option<&str> {
+        s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+    (non_empty(&annotation.text), non_empty(&annotation.comment))
+}
+
+/// A nested list entry for a section-colored annotation.
+fn outline_entry(annotation: &ZoteroAnnotation, citekey: &str) -> String {
+    let level = section_level(annotation).unwrap_or(1);
+    let (text, comment) = text_and_comment(annotation);
+    let heading = text.or(comment).unwrap_or("(untitled)");
+
+    let mut entry = format!(
+        "{}- {}",
+        "  ".repeat(level - 1),
+        heading.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    if let Some(link) = page_link(annotation, citekey) {
+        entry.push_str(&format!(" ({})", link));
+    }
+    entry.push('\n');
+    entry
+}
+
+/// Append a titled section for a group of annotations, unless it is empty.
+fn push_section(out: &mut String, title: &str, group: &[&ZoteroAnnotation], citekey: &str) {
+    if group.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n## {}\n", title));
+    for annotation in group {
+        out.push('\n');
+        out.push_str(&entry(annotation, citekey));
+    }
+}
+
+/// Render one annotation: its text (quoted, or fenced for code), page link
+/// and comment.
+fn entry(annotation: &ZoteroAnnotation, citekey: &str) -> String {
+    let (text, comment) = text_and_comment(annotation);
+    let link = page_link(annotation, citekey);
+    let mut out = String::new();
+
+    match text {
+        Some(text) if annotation.semantic_color() == Some(HighlightColor::Code) => {
+            // A one-word comment on a code annotation names its language
+            let language = comment.filter(|c| !c.contains(char::is_whitespace));
+            out.push_str(&format!("```{}\n{}\n```\n", language.unwrap_or(""), text));
+            if let Some(link) = &link {
+                out.push_str(&format!("{}\n", link));
+            }
+            if let Some(comment) = comment.filter(|_| language.is_none()) {
+                out.push_str(&format!("\n{}\n", comment));
+            }
+            return out;
+        }
+        Some(text) => {
+            let mut lines: Vec<String> = text.lines().map(|l| format!("> {}", l.trim())).collect();
+            if let (Some(last), Some(link)) = (lines.last_mut(), &link) {
+                last.push_str(&format!(" {}", link));
+            }
+            out.push_str(&lines.join("\n"));
+            out.push('\n');
+        }
+        None => {
+            let kind = match annotation.annotation_type.as_deref() {
+                Some("image") => Some("Image"),
+                Some("ink") => Some("Ink drawing"),
+                _ => None,
+            };
+            let mut line = match (kind, comment) {
+                (Some(kind), _) => format!("*{}*", kind),
+                (None, Some(comment)) => comment.to_string(),
+                (None, None) => "*(empty annotation)*".to_string(),
+            };
+            if let Some(link) = &link {
+                line.push_str(&format!(" {}", link));
+            }
+            out.push_str(&line);
+            out.push('\n');
+            if kind.is_none() {
+                return out;
+            }
+        }
+    }
+
+    if let Some(comment) = comment {
+        out.push_str(&format!("\n{}\n", comment));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests;
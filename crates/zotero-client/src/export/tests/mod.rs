@@ -0,0 +1,188 @@
+//! Unit tests for the Markdown export module.
+
+use super::*;
+use crate::types::{AnnotationPosition, Creator, Tag};
+
+fn item() -> ZoteroItem {
+    ZoteroItem {
+        id: None,
+        key: "ITEM0001".to_string(),
+        item_type: "journalArticle".to_string(),
+        title: Some("Reading \"Closely\"".to_string()),
+        creators: vec![
+            Creator {
+                creator_type: Some("author".to_string()),
+                first_name: Some("Jane".to_string()),
+                last_name: Some("Doe".to_string()),
+                name: None,
+            },
+            Creator {
+                creator_type: Some("editor".to_string()),
+                first_name: Some("Ed".to_string()),
+                last_name: Some("Itor".to_string()),
+                name: None,
+            },
+            Creator {
+                creator_type: Some("author".to_string()),
+                first_name: None,
+                last_name: None,
+                name: Some("Research Group".to_string()),
+            },
+        ],
+        date: Some("March 2024".to_string()),
+        extra: None,
+        abstract_note: None,
+        url: None,
+        doi: None,
+        tags: vec![Tag {
+            tag: "reading".to_string(),
+            tag_type: Some(0),
+        }],
+    }
+}
+
+fn annotation(
+    annotation_type: &str,
+    color: &str,
+    text: Option<&str>,
+    comment: Option<&str>,
+    page_index: u32,
+) -> ZoteroAnnotation {
+    ZoteroAnnotation {
+        id: None,
+        key: None,
+        parent_item_key: None,
+        annotation_type: Some(annotation_type.to_string()),
+        text: text.map(str::to_string),
+        comment: comment.map(str::to_string),
+        color: Some(color.to_string()),
+        page_label: None,
+        sort_index: None,
+        position: Some(AnnotationPosition {
+            page_index,
+            ..Default::default()
+        }),
+    }
+}
+
+#[test]
+fn test_frontmatter_is_dataview_compatible() {
+    let markdown = annotations_to_markdown(&item(), "doe2024", &[], "2026-01-31");
+
+    assert!(markdown.starts_with(
+        "---\n\
+         type: annotations\n\
+         citekey: \"doe2024\"\n\
+         source: \"[[doe2024]]\"\n\
+         title: \"Reading \\\"Closely\\\"\"\n\
+         authors:\n  - \"Doe, Jane\"\n  - \"Research Group\"\n\
+         year: 2024\n\
+         tags:\n  - \"reading\"\n\
+         annotations: 0\n\
+         created: 2026-01-31\n\
+         ---\n"
+    ));
+    assert!(markdown.contains("\n# Reading \"Closely\"\n"));
+}
+
+#[test]
+fn test_groups_follow_color_semantics() {
+    let annotations = [
+        annotation("highlight", "#ffd400", Some("Why?"), None, 0),
+        annotation("highlight", "#5fb236", Some("Agreed"), None, 1),
+        annotation("highlight", "#ff6666", Some("Disagreed"), None, 2),
+        annotation("highlight", "#123456", Some("Custom"), None, 3),
+    ];
+    let markdown = annotations_to_markdown(&item(), "doe2024", &annotations, "2026-01-31");
+
+    let position = |s: &str| markdown.find(s).unwrap();
+    assert!(position("## Positive point / Agreement") < position("## Negative point"));
+    assert!(position("## Negative point") < position("## Question"));
+    assert!(position("## Question") < position("## Other"));
+    assert!(!markdown.contains("## Outline"));
+    assert!(!markdown.contains("## Code"));
+    assert!(markdown.contains("> Why? [[doe2024#p. 1|p. 1]]"));
+    assert!(markdown.contains("> Custom [[doe2024#p. 4|p. 4]]"));
+}
+
+#[test]
+fn test_section_colors_form_an_outline() {
+    let annotations = [
+        annotation("highlight", "#2ea8e5", Some("Introduction"), None, 0),
+        annotation("highlight", "#a28ae5", Some("Background"), None, 1),
+        annotation("highlight", "#e56eee", Some("Prior\nwork"), None, 1),
+    ];
+    let markdown = annotations_to_markdown(&item(), "doe2024", &annotations, "2026-01-31");
+
+    assert!(markdown.contains(
+        "## Outline\n\n\
+         - Introduction ([[doe2024#p. 1|p. 1]])\n\
+         \x20 - Background ([[doe2024#p. 2|p. 2]])\n\
+         \x20   - Prior work ([[doe2024#p. 2|p. 2]])\n"
+    ));
+}
+
+#[test]
+fn test_page_label_preferred_over_index() {
+    let mut highlight = annotation("highlight", "#5fb236", Some("Claim"), None, 0);
+    highlight.page_label = Some("xii".to_string());
+    let markdown = annotations_to_markdown(&item(), "doe2024", &[highlight], "2026-01-31");
+
+    assert!(markdown.contains("> Claim [[doe2024#p. xii|p. xii]]"));
+}
+
+#[test]
+fn test_comment_follows_quote() {
+    let highlight = annotation(
+        "highlight",
+        "#5fb236",
+        Some("First line\nsecond line"),
+        Some("IDEA: worth a note"),
+        4,
+    );
+    let markdown = annotations_to_markdown(&item(), "doe2024", &[highlight], "2026-01-31");
+
+    assert!(markdown
+        .contains("> First line\n> second line [[doe2024#p. 5|p. 5]]\n\nIDEA: worth a note\n"));
+}
+
+#[test]
+fn test_code_annotation_is_fenced() {
+    let code = annotation(
+        "highlight",
+        "#f19837",
+        Some("print(\"hello\")"),
+        Some("python"),
+        0,
+    );
+    let markdown = annotations_to_markdown(&item(), "doe2024", &[code], "2026-01-31");
+
+    assert!(markdown.contains("```python\nprint(\"hello\")\n```\n[[doe2024#p. 1|p. 1]]\n"));
+}
+
+#[test]
+fn test_note_and_image_annotations() {
+    let annotations = [
+        annotation("note", "#ffd400", None, Some("Check the method"), 2),
+        annotation("image", "#aaaaaa", None, Some("Figure 2"), 3),
+    ];
+    let markdown = annotations_to_markdown(&item(), "doe2024", &annotations, "2026-01-31");
+
+    assert!(markdown.contains("Check the method [[doe2024#p. 3|p. 3]]\n"));
+    assert!(markdown.contains("*Image* [[doe2024#p. 4|p. 4]]\n\nFigure 2\n"));
+}
+
+#[test]
+fn test_date_from_days() {
+    assert_eq!(date_from_days(0), "1970-01-01");
+    assert_eq!(date_from_days(11_016), "2000-02-29");
+    assert_eq!(date_from_days(20_484), "2026-01-31");
+    assert_eq!(today().len(), 10);
+}
+
+#[test]
+fn test_year_extraction() {
+    assert_eq!(year("2024-03-01"), Some("2024"));
+    assert_eq!(year("Spring 1999"), Some("1999"));
+    assert_eq!(year("n.d."), None);
+}
@@ -0,0 +1,359 @@
+//! Full-text index of PDF attachments.
+//!
+//! The Zotero plugin only searches item metadata. [`FulltextIndex`] keeps the
+//! text of every indexed PDF page in an inverted index and ranks pages with
+//! BM25, so a query returns the pages that best match it across the library.
+//!
+//! The index is stored on disk as JSON and updated incrementally: a document
+//! only needs to be re-extracted when its file's modification time changes
+//! (see [`FulltextIndex::is_current`]).
+//!
+//! # Example
+//!
+//! ```
+//! use zotero_client::fulltext::{DocumentInfo, FulltextIndex};
+//!
+//! let mut index = FulltextIndex::new();
+//! index.insert(
+//!     DocumentInfo {
+//!         attachment_key: "PDF00001".to_string(),
+//!         item_key: "ITEM0001".to_string(),
+//!         citekey: Some("doe2024".to_string()),
+//!         title: Some("Attention".to_string()),
+//!         modified: 1_700_000_000,
+//!     },
+//!     vec!["Transformers rely on self-attention.".to_string()],
+//! );
+//!
+//! let hits = index.search("self-attention", 10);
+//! assert_eq!(hits[0].citekey.as_deref(), Some("doe2024"));
+//! assert_eq!(hits[0].page, 1);
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ZoteroClientError};
+
+mod tokenize;
+
+pub use tokenize::{snippet, tokenize};
+
+/// Version of the on-disk format. Indexes written by another version are
+/// discarded and rebuilt.
+const INDEX_VERSION: u32 = 1;
+
+/// BM25 term frequency saturation.
+const K1: f64 = 1.2;
+
+/// BM25 page length normalization.
+const B: f64 = 0.75;
+
+/// Metadata of an indexed PDF attachment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentInfo {
+    /// Zotero attachment key of the PDF.
+    pub attachment_key: String,
+    /// Key of the parent item.
+    pub item_key: String,
+    /// BetterBibTeX citation key of the parent item, if known.
+    pub citekey: Option<String>,
+    /// Title of the parent item.
+    pub title: Option<String>,
+    /// Modification time of the PDF file, in seconds since the Unix epoch.
+    pub modified: u64,
+}
+
+/// An indexed document: its metadata and the text of each page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDocument {
+    id: u32,
+    #[serde(flatten)]
+    info: DocumentInfo,
+    pages: Vec<IndexedPage>,
+}
+
+/// The text of a page and its number of terms.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedPage {
+    text: String,
+    length: u32,
+}
+
+/// Occurrences of a term on a page: `(document id, page index, term frequency)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Posting(u32, u32, u32);
+
+/// A page matching a full-text query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// Zotero attachment key of the PDF.
+    pub attachment_key: String,
+    /// Key of the parent item.
+    pub item_key: String,
+    /// BetterBibTeX citation key of the parent item, if known.
+    pub citekey: Option<String>,
+    /// Title of the parent item.
+    pub title: Option<String>,
+    /// 1-based page number.
+    pub page: u32,
+    /// BM25 relevance score; higher is better.
+    pub score: f64,
+    /// Excerpt of the page around the first matching term.
+    pub snippet: String,
+}
+
+/// An on-disk inverted index of PDF page text, ranked with BM25.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FulltextIndex {
+    version: u32,
+    updated_at: Option<u64>,
+    next_id: u32,
+    documents: BTreeMap<String, IndexedDocument>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl Default for FulltextIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            updated_at: None,
+            next_id: 0,
+            documents: BTreeMap::new(),
+            postings: HashMap::new(),
+        }
+    }
+}
+
+impl FulltextIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load an index from disk.
+    ///
+    /// Returns an empty index if the file does not exist, cannot be parsed
+    /// (for example after a crash while it was written) or was written by
+    /// another version of the index format, so that it is rebuilt on the
+    /// next synchronization.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => {
+                return Err(ZoteroClientError::Index(format!(
+                    "Failed to read '{}': {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        match serde_json::from_slice::<Self>(&data) {
+            Ok(index) if index.version == INDEX_VERSION => Ok(index),
+            _ => Ok(Self::new()),
+        }
+    }
+
+    /// Save the index to disk, creating parent directories as needed.
+    ///
+    /// The index is written to a temporary file first and then renamed, so a
+    /// concurrent reader never sees a partially written index.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| {
+            ZoteroClientError::Index(format!("Failed to write '{}': {}", path.display(), e))
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_vec(self)?).map_err(io_error)?;
+        std::fs::rename(&temp, path).map_err(io_error)
+    }
+
+    /// Number of indexed documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Whether no document is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Number of indexed pages across all documents.
+    pub fn page_count(&self) -> usize {
+        self.documents.values().map(|doc| doc.pages.len()).sum()
+    }
+
+    /// When the index was last synchronized with the library, in seconds since
+    /// the Unix epoch. `None` if it never was.
+    pub fn updated_at(&self) -> Option<u64> {
+        self.updated_at
+    }
+
+    /// Record that the index was synchronized with the library at `time`.
+    pub fn mark_updated(&mut self, time: u64) {
+        self.updated_at = Some(time);
+    }
+
+    /// Whether an attachment is indexed from a file with this modification time.
+    pub fn is_current(&self, attachment_key: &str, modified: u64) -> bool {
+        self.documents
+            .get(attachment_key)
+            .is_some_and(|doc| doc.info.modified == modified)
+    }
+
+    /// Index the pages of a document, replacing any previous version of it.
+    pub fn insert(&mut self, info: DocumentInfo, pages: Vec<String>) {
+        self.remove(&info.attachment_key);
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut indexed = Vec::with_capacity(pages.len());
+        for (page, text) in pages.into_iter().enumerate() {
+            let terms = tokenize(&text);
+            let mut frequencies: HashMap<String, u32> = HashMap::new();
+            for term in &terms {
+                *frequencies.entry(term.clone()).or_default() += 1;
+            }
+            for (term, frequency) in frequencies {
+                self.postings
+                    .entry(term)
+                    .or_default()
+                    .push(Posting(id, page as u32, frequency));
+            }
+            indexed.push(IndexedPage {
+                text,
+                length: terms.len() as u32,
+            });
+        }
+
+        self.documents.insert(
+            info.attachment_key.clone(),
+            IndexedDocument {
+                id,
+                info,
+                pages: indexed,
+            },
+        );
+    }
+
+    /// Remove a document from the index. Returns whether it was indexed.
+    pub fn remove(&mut self, attachment_key: &str) -> bool {
+        self.retain(|key| key != attachment_key) > 0
+    }
+
+    /// Keep only the documents whose attachment key satisfies the predicate.
+    /// Returns the number of documents removed.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) -> usize {
+        let mut removed = HashSet::new();
+        self.documents.retain(|key, doc| {
+            let kept = keep(key);
+            if !kept {
+                removed.insert(doc.id);
+            }
+            kept
+        });
+
+        if !removed.is_empty() {
+            self.postings.retain(|_, postings| {
+                postings.retain(|posting| !removed.contains(&posting.0));
+                !postings.is_empty()
+            });
+        }
+        removed.len()
+    }
+
+    /// Find the pages that best match a query, best first.
+    ///
+    /// The query is split into terms like the indexed text; pages matching
+    /// any term are ranked with BM25.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+
+        let documents: HashMap<u32, &IndexedDocument> =
+            self.documents.values().map(|doc| (doc.id, doc)).collect();
+        let pages = self.page_count() as f64;
+        let total_length: f64 = self
+            .documents
+            .values()
+            .flat_map(|doc| &doc.pages)
+            .map(|page| page.length as f64)
+            .sum();
+        let average_length = (total_length / pages.max(1.0)).max(1.0);
+
+        let mut scores: HashMap<(u32, u32), f64> = HashMap::new();
+        for postings in terms.iter().filter_map(|term| self.postings.get(term)) {
+            let matching = postings.len() as f64;
+            let idf = (1.0 + (pages - matching + 0.5) / (matching + 0.5)).ln();
+            for &Posting(doc, page, frequency) in postings {
+                let length = documents[&doc].pages[page as usize].length as f64;
+                let frequency = frequency as f64;
+                *scores.entry((doc, page)).or_default() += idf * frequency * (K1 + 1.0)
+                    / (frequency + K1 * (1.0 - B + B * length / average_length));
+            }
+        }
+
+        let mut ranked: Vec<((u32, u32), f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|((doc, page), score)| {
+                let document = documents[&doc];
+                SearchHit {
+                    attachment_key: document.info.attachment_key.clone(),
+                    item_key: document.info.item_key.clone(),
+                    citekey: document.info.citekey.clone(),
+                    title: document.info.title.clone(),
+                    page: page + 1,
+                    score,
+                    snippet: snippet(&document.pages[page as usize].text, &terms),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Default location of the index: `zotero-mcp/fulltext.json` in the user's
+/// cache directory (`$XDG_CACHE_HOME`, or `~/.cache`).
+pub fn default_index_path() -> PathBuf {
+    let cache = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    cache.join("zotero-mcp").join("fulltext.json")
+}
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Extract the text of every page of a PDF, for [`FulltextIndex::insert`].
+#[cfg(feature = "pdf")]
+pub fn extract_pages(path: impl AsRef<Path>) -> Result<Vec<String>> {
+    let path = path.as_ref();
+    let page_count = crate::pdf::get_page_count(path)?;
+    (0..page_count)
+        .map(|page| crate::pdf::extract_text(path, page))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,165 @@
+//! Unit tests for the full-text index.
+
+use super::*;
+
+fn info(attachment_key: &str, modified: u64) -> DocumentInfo {
+    DocumentInfo {
+        attachment_key: attachment_key.to_string(),
+        item_key: format!("ITEM{}", &attachment_key[3..]),
+        citekey: Some(format!("cite{}", &attachment_key[3..])),
+        title: None,
+        modified,
+    }
+}
+
+fn pages(texts: &[&str]) -> Vec<String> {
+    texts.iter().map(|text| text.to_string()).collect()
+}
+
+fn library() -> FulltextIndex {
+    let mut index = FulltextIndex::new();
+    index.insert(
+        info("PDF0001", 1),
+        pages(&[
+            "Introduction to graph neural networks.",
+            "Message passing between nodes of the graph. Graph convolutions.",
+        ]),
+    );
+    index.insert(
+        info("PDF0002", 1),
+        pages(&["Transformers replace recurrence with attention over tokens."]),
+    );
+    index
+}
+
+#[test]
+fn test_tokenize_lowercases_and_drops_stopwords() {
+    assert_eq!(
+        tokenize("The Graph of a Network, 2024!"),
+        vec!["graph", "network", "2024"]
+    );
+}
+
+#[test]
+fn test_tokenize_joins_hyphenated_line_breaks() {
+    assert_eq!(tokenize("compu-\ntation"), vec!["computation"]);
+    assert_eq!(tokenize("self-attention"), vec!["self", "attention"]);
+    assert_eq!(tokenize("pre-\nTraining"), vec!["pre", "training"]);
+}
+
+#[test]
+fn test_tokenize_expands_ligatures() {
+    assert_eq!(
+        tokenize("eﬃcient ﬁne-tuning"),
+        vec!["efficient", "fine", "tuning"]
+    );
+}
+
+#[test]
+fn test_snippet_centers_on_first_match() {
+    let text = format!("{} needle {}", "word ".repeat(50), "tail ".repeat(50));
+    let snippet = snippet(&text, &["needle".to_string()]);
+    assert!(snippet.starts_with('…'));
+    assert!(snippet.ends_with('…'));
+    assert!(snippet.contains("needle"));
+    assert!(snippet.len() < text.len());
+}
+
+#[test]
+fn test_snippet_of_short_page_is_whole_page() {
+    assert_eq!(
+        snippet("Short\n page.", &["missing".to_string()]),
+        "Short page."
+    );
+}
+
+#[test]
+fn test_search_ranks_pages_by_term_frequency() {
+    let hits = library().search("graph", 10);
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].attachment_key, "PDF0001");
+    assert_eq!(hits[0].page, 2);
+    assert_eq!(hits[1].page, 1);
+    assert!(hits[0].score > hits[1].score);
+    assert_eq!(hits[0].citekey.as_deref(), Some("cite0001"));
+    assert!(hits[0].snippet.contains("graph"));
+}
+
+#[test]
+fn test_search_prefers_rare_terms() {
+    let hits = library().search("attention graph", 10);
+    assert_eq!(hits[0].attachment_key, "PDF0002");
+}
+
+#[test]
+fn test_search_respects_limit_and_unknown_terms() {
+    let index = library();
+    assert_eq!(index.search("graph", 1).len(), 1);
+    assert!(index.search("quantum", 10).is_empty());
+    assert!(index.search("the of", 10).is_empty());
+}
+
+#[test]
+fn test_insert_replaces_previous_version() {
+    let mut index = library();
+    assert!(index.is_current("PDF0002", 1));
+    assert!(!index.is_current("PDF0002", 2));
+
+    index.insert(info("PDF0002", 2), pages(&["Diffusion models."]));
+    assert!(index.is_current("PDF0002", 2));
+    assert_eq!(index.len(), 2);
+    assert!(index.search("attention", 10).is_empty());
+    assert_eq!(index.search("diffusion", 10).len(), 1);
+}
+
+#[test]
+fn test_retain_removes_documents_and_postings() {
+    let mut index = library();
+    assert_eq!(index.retain(|key| key == "PDF0002"), 1);
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.page_count(), 1);
+    assert!(index.search("graph", 10).is_empty());
+    assert!(index.postings.keys().all(|term| term != "graph"));
+
+    assert!(index.remove("PDF0002"));
+    assert!(!index.remove("PDF0002"));
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let dir = std::env::temp_dir().join(format!("zotero-fulltext-{}", std::process::id()));
+    let path = dir.join("nested").join("fulltext.json");
+
+    let mut index = library();
+    index.mark_updated(42);
+    index.save(&path).unwrap();
+
+    let loaded = FulltextIndex::load(&path).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded.updated_at(), Some(42));
+    assert_eq!(loaded.search("graph", 10), index.search("graph", 10));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_load_missing_outdated_or_corrupt_index_is_empty() {
+    let dir = std::env::temp_dir().join(format!("zotero-fulltext-old-{}", std::process::id()));
+    assert!(FulltextIndex::load(dir.join("missing.json"))
+        .unwrap()
+        .is_empty());
+
+    let mut index = library();
+    index.version = INDEX_VERSION + 1;
+    let path = dir.join("fulltext.json");
+    index.save(&path).unwrap();
+    assert!(FulltextIndex::load(&path).unwrap().is_empty());
+
+    // Cut off mid-write
+    let data = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &data[..data.len() / 2]).unwrap();
+    assert!(FulltextIndex::load(&path).unwrap().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,112 @@
+//! Word splitting for the full-text index.
+
+/// Words too common to be useful search terms.
+const STOPWORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "in", "is", "it",
+    "its", "of", "on", "or", "that", "the", "this", "to", "was", "were", "which", "with",
+];
+
+/// Characters on either side of the first match kept in a snippet.
+const SNIPPET_RADIUS: usize = 100;
+
+/// A word of a text, with its byte range and normalized token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Word {
+    pub start: usize,
+    pub end: usize,
+    pub token: String,
+}
+
+/// Split text into words.
+///
+/// Tokens are lowercased, ligatures are expanded, and words hyphenated across
+/// a line break are joined ("compu-\ntation" becomes "computation").
+pub(crate) fn words(text: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut current: Option<Word> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c.is_alphanumeric() {
+            let word = current.get_or_insert_with(|| Word {
+                start: i,
+                end: i,
+                token: String::new(),
+            });
+            push_folded(&mut word.token, c);
+            word.end = i + c.len_utf8();
+        } else if c == '-' && current.is_some() && continues_on_next_line(&text[i + 1..]) {
+            while chars.next_if(|(_, next)| next.is_whitespace()).is_some() {}
+        } else if let Some(word) = current.take() {
+            words.push(word);
+        }
+    }
+    words.extend(current);
+    words
+}
+
+/// The search terms of a text: its words, without stopwords and single characters.
+pub fn tokenize(text: &str) -> Vec<String> {
+    words(text)
+        .into_iter()
+        .map(|word| word.token)
+        .filter(|token| is_term(token))
+        .collect()
+}
+
+/// A short excerpt of a page around the first occurrence of any of the terms,
+/// with whitespace collapsed. Falls back to the start of the page.
+pub fn snippet(text: &str, terms: &[String]) -> String {
+    let center = words(text)
+        .into_iter()
+        .find(|word| terms.contains(&word.token))
+        .map_or(0, |word| word.start);
+
+    let mut start = center.saturating_sub(SNIPPET_RADIUS);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (center + SNIPPET_RADIUS).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let excerpt = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        excerpt,
+        if end < text.len() { "…" } else { "" }
+    )
+}
+
+/// Whether a token is worth indexing.
+fn is_term(token: &str) -> bool {
+    token.chars().count() > 1 && !STOPWORDS.contains(&token)
+}
+
+/// Whether the text after a hyphen is a line break followed by the rest of a word.
+fn continues_on_next_line(rest: &str) -> bool {
+    let rest = rest.trim_start_matches([' ', '\t', '\r']);
+    rest.starts_with('\n')
+        && rest
+            .trim_start()
+            .chars()
+            .next()
+            .is_some_and(char::is_lowercase)
+}
+
+/// Append a lowercased character to a token, expanding ligatures.
+fn push_folded(token: &mut String, c: char) {
+    match c {
+        'ﬀ' => token.push_str("ff"),
+        'ﬁ' => token.push_str("fi"),
+        'ﬂ' => token.push_str("fl"),
+        'ﬃ' => token.push_str("ffi"),
+        'ﬄ' => token.push_str("ffl"),
+        _ => token.extend(c.to_lowercase()),
+    }
+}
@@ -1,11 +1,67 @@
-//! Figure detection using heuristics.
+//! Figure detection from the page's drawings, images and captions.
 //!
-//! This module provides functionality to detect figure regions in PDF pages
-//! using whitespace analysis and content density heuristics.
+//! The page is run through a device that records the bounding box of every
+//! vector drawing and image it paints. Nearby graphics are grouped into
+//! regions, which take in the text blocks drawn over them (axis labels,
+//! legends, boxes of a diagram). "Figure N" and "Table N" captions are then
+//! matched to the closest region, usually below a figure and above a table;
+//! a figure caption without drawings above it marks the blank space there,
+//! where a figure the device cannot see (such as a form XObject made only of
+//! text) sits.
 
 use crate::error::{Result, ZoteroClientError};
-use mupdf::{Document, TextPageOptions};
+use crate::pdf::union;
+use mupdf::{
+    ColorParams, Colorspace, Device, Document, Image, Matrix, NativeDevice, Point, StrokeState,
+    TextBlockType, TextPageOptions,
+};
+use regex::Regex;
 use std::path::Path;
+use std::sync::LazyLock;
+
+/// Distance within which graphics belong to the same figure, in points.
+const GRAPHICS_GAP: f64 = 12.0;
+
+/// Distance within which a text block is part of the figure it is drawn over, in points.
+const TEXT_GAP: f64 = 6.0;
+
+/// Share of a text block's width that must lie within a figure for it to be taken in.
+const MIN_TEXT_OVERLAP: f64 = 0.8;
+
+/// Farthest a caption may be from its figure, in points.
+const MAX_CAPTION_GAP: f64 = 36.0;
+
+/// Added to the distance of a caption on the unusual side of its figure
+/// (above a figure or below a table), in points.
+const CAPTION_SIDE_PENALTY: f64 = 24.0;
+
+/// Smallest width and height of a figure, in points.
+const MIN_FIGURE_SIZE: f64 = 30.0;
+
+/// Smallest area of a figure, in square points.
+const MIN_FIGURE_AREA: f64 = 5000.0;
+
+/// Share of the page a filled path may cover before it is taken for a background.
+const MAX_BACKGROUND_SHARE: f64 = 0.9;
+
+/// Space kept between a figure found from its caption and the text around it, in points.
+const CAPTION_REGION_MARGIN: f64 = 2.0;
+
+/// A caption: "Figure 3:", "Fig. 3.", "Table 2:", "TABLE II" or "Figure S1 Overview".
+/// The number must be followed by punctuation, a capitalized word or the end of
+/// the block, so that sentences like "Figure 3 shows" are not taken for captions.
+static CAPTION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?i:(fig(?:ure)?\.?|tab(?:le)?\.?))\s*([A-Z]?\d+[a-z]?|[IVXLC]+)(?:\s*[.:|\u{2013}\u{2014}-]|\s+\p{Lu}|\s*$)",
+    )
+    .unwrap()
+});
+
+/// A figure label given by a user: "Figure 3", "fig. 3", "Table II" or just "3".
+static LABEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?i:(fig(?:ure)?\.?|tab(?:le)?\.?)\s*)?([A-Za-z]?\d+[a-z]?|[IVXLCivxlc]+)\.?$")
+        .unwrap()
+});
 
 /// Type of detected figure region.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +72,8 @@ pub enum FigureType {
     Chart,
     /// Likely a diagram or schematic
     Diagram,
+    /// A table, from its "Table N" caption
+    Table,
     /// Unknown figure type
     Unknown,
 }
@@ -27,6 +85,7 @@ impl FigureType {
             Self::Image => "image",
             Self::Chart => "chart",
             Self::Diagram => "diagram",
+            Self::Table => "table",
             Self::Unknown => "figure",
         }
     }
@@ -43,6 +102,10 @@ pub struct FigureRegion {
     pub figure_type: FigureType,
     /// Confidence score (0.0 to 1.0)
     pub confidence: f32,
+    /// Full text of the figure's caption, if one was found
+    pub caption: Option<String>,
+    /// Number from the caption ("3" for "Figure 3:", "II" for "TABLE II")
+    pub number: Option<String>,
 }
 
 impl FigureRegion {
@@ -64,282 +127,557 @@ impl FigureRegion {
             1.0
         }
     }
-}
 
-/// Detect figure regions on a PDF page using heuristics.
-///
-/// This function analyzes the page content to identify regions that likely
-/// contain figures, charts, diagrams, or images. It uses:
-///
-/// 1. Whitespace analysis to find non-text regions
-/// 2. Content density to estimate figure boundaries
-/// 3. Aspect ratio and size filtering
-///
-/// # Arguments
-///
-/// * `path` - Path to the PDF file
-/// * `page_num` - Zero-based page number
-///
-/// # Returns
-///
-/// A vector of `FigureRegion` structures, sorted by confidence score.
-///
-/// # Limitations
-///
-/// - Works best on standard academic paper layouts
-/// - May miss small inline figures
-/// - May incorrectly identify dense text blocks as figures
-/// - Does not detect figures spanning multiple columns
-///
-/// # Example
-///
-/// ```no_run
-/// use zotero_client::image::detect_figures;
-///
-/// let figures = detect_figures("/path/to/file.pdf", 0)?;
-/// for fig in figures {
-///     println!("Figure {} at {:?}: {}", fig.index, fig.rect, fig.figure_type.description());
-/// }
-/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
-/// ```
-pub fn detect_figures<P: AsRef<Path>>(path: P, page_num: usize) -> Result<Vec<FigureRegion>> {
-    let path = path.as_ref();
+    /// The label of a captioned figure: "Figure 3" or "Table 2".
+    pub fn label(&self) -> Option<String> {
+        let kind = if self.figure_type == FigureType::Table {
+            "Table"
+        } else {
+            "Figure"
+        };
+        self.number.as_ref().map(|n| format!("{} {}", kind, n))
+    }
 
-    let doc = Document::open(path).map_err(|e| {
-        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
-    })?;
+    /// Whether the figure has the given label: "Figure 3", "Fig. 3", "Table 2"
+    /// or a bare number, which refers to a figure.
+    pub fn matches_label(&self, label: &str) -> bool {
+        let Some(caps) = LABEL.captures(label.trim()) else {
+            return false;
+        };
+        let table = caps
+            .get(1)
+            .is_some_and(|kind| kind.as_str().to_lowercase().starts_with("tab"));
+        let Some(number) = &self.number else {
+            return false;
+        };
+        table == (self.figure_type == FigureType::Table) && number.eq_ignore_ascii_case(&caps[2])
+    }
+}
 
-    let page = doc
-        .load_page(page_num as i32)
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+/// A vector drawing or image painted on the page, in PDF coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Graphic {
+    pub(super) rect: [f64; 4],
+    pub(super) image: bool,
+}
 
-    // Get page dimensions
-    let page_bounds = page
-        .bounds()
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
-    let page_width = (page_bounds.x1 - page_bounds.x0) as f64;
-    let page_height = (page_bounds.y1 - page_bounds.y0) as f64;
+/// A text block of the page, in PDF coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TextBlock {
+    pub(super) rect: [f64; 4],
+    pub(super) text: String,
+}
 
-    // Get text content to identify text regions
-    let text_page = page
-        .to_text_page(TextPageOptions::empty())
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get text page: {}", e)))?;
+/// Whether a caption belongs to a table, and its number, if the text starts with one.
+pub(super) fn parse_caption(text: &str) -> Option<(bool, String)> {
+    let caps = CAPTION.captures(text.trim_start())?;
+    let table = caps[1].to_lowercase().starts_with("tab");
+    Some((table, caps[2].to_string()))
+}
 
-    // Collect text block bounding boxes (in MuPDF coordinates, origin top-left)
-    let mut text_regions: Vec<[f64; 4]> = Vec::new();
-    for block in text_page.blocks() {
-        let bounds = block.bounds();
-        text_regions.push([
-            bounds.x0 as f64,
-            bounds.y0 as f64,
-            bounds.x1 as f64,
-            bounds.y1 as f64,
-        ]);
-    }
+fn area(r: [f64; 4]) -> f64 {
+    (r[2] - r[0]).max(0.0) * (r[3] - r[1]).max(0.0)
+}
 
-    // Find gaps between text regions that might contain figures
-    let figure_candidates = find_figure_gaps(&text_regions, page_width, page_height);
+fn intersection(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+    [
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    ]
+}
 
-    // Filter and score candidates
-    let mut figures: Vec<FigureRegion> = figure_candidates
-        .into_iter()
-        .enumerate()
-        .filter_map(|(index, rect)| {
-            let width = rect[2] - rect[0];
-            let height = rect[3] - rect[1];
+/// Whether two rectangles are within `gap` of each other.
+fn near(a: [f64; 4], b: [f64; 4], gap: f64) -> bool {
+    a[0] - gap <= b[2] && b[0] - gap <= a[2] && a[1] - gap <= b[3] && b[1] - gap <= a[3]
+}
 
-            // Filter out regions that are too small or too narrow
-            let min_dimension = 50.0; // Minimum dimension in points
-            let min_area = 5000.0; // Minimum area in square points
+/// Width shared by two rectangles.
+fn horizontal_overlap(a: [f64; 4], b: [f64; 4]) -> f64 {
+    (a[2].min(b[2]) - a[0].max(b[0])).max(0.0)
+}
 
-            if width < min_dimension || height < min_dimension {
-                return None;
-            }
-            if width * height < min_area {
-                return None;
-            }
+/// Graphics grouped into figure candidates.
+#[derive(Debug, Clone)]
+struct Cluster {
+    rect: [f64; 4],
+    image_area: f64,
+}
 
-            // Filter out regions that span almost the full page (likely headers/footers)
-            if width > page_width * 0.95 && height < page_height * 0.15 {
-                return None;
+/// Group graphics that lie within `GRAPHICS_GAP` of each other.
+fn cluster_graphics(graphics: &[Graphic]) -> Vec<Cluster> {
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for graphic in graphics {
+        let image_area = if graphic.image {
+            area(graphic.rect)
+        } else {
+            0.0
+        };
+        match clusters
+            .iter_mut()
+            .find(|c| near(c.rect, graphic.rect, GRAPHICS_GAP))
+        {
+            Some(cluster) => {
+                cluster.rect = union(cluster.rect, graphic.rect);
+                cluster.image_area += image_area;
             }
+            None => clusters.push(Cluster {
+                rect: graphic.rect,
+                image_area,
+            }),
+        }
+    }
+    merge_clusters(clusters)
+}
 
-            // Calculate confidence based on region properties
-            let confidence = calculate_confidence(width, height, page_width, page_height);
-
-            if confidence < 0.3 {
-                return None;
+/// Merge clusters that grew to within `GRAPHICS_GAP` of each other.
+fn merge_clusters(mut clusters: Vec<Cluster>) -> Vec<Cluster> {
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for i in 0..clusters.len() {
+            for j in i + 1..clusters.len() {
+                if near(clusters[i].rect, clusters[j].rect, GRAPHICS_GAP) {
+                    let other = clusters.swap_remove(j);
+                    clusters[i].rect = union(clusters[i].rect, other.rect);
+                    clusters[i].image_area += other.image_area;
+                    merged = true;
+                    break 'outer;
+                }
             }
-
-            // Estimate figure type based on aspect ratio
-            let aspect_ratio = width / height;
-            let figure_type = estimate_figure_type(aspect_ratio, width, height);
-
-            // Convert MuPDF coordinates (origin top-left) to PDF coordinates (origin bottom-left)
-            let pdf_y1 = page_height - rect[3];
-            let pdf_y2 = page_height - rect[1];
-
-            Some(FigureRegion {
-                index,
-                rect: [rect[0], pdf_y1, rect[2], pdf_y2],
-                figure_type,
-                confidence,
-            })
-        })
-        .collect();
-
-    // Sort by confidence (highest first)
-    figures.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
-
-    // Re-index after sorting
-    for (i, fig) in figures.iter_mut().enumerate() {
-        fig.index = i;
+        }
     }
-
-    Ok(figures)
+    clusters
 }
 
-/// Find gaps between text regions that might contain figures.
-fn find_figure_gaps(text_regions: &[[f64; 4]], page_width: f64, page_height: f64) -> Vec<[f64; 4]> {
-    if text_regions.is_empty() {
-        // No text - the entire page might be an image
-        return vec![[0.0, 0.0, page_width, page_height]];
+/// Estimate the type of a vector figure from its aspect ratio.
+fn estimate_figure_type(aspect_ratio: f64) -> FigureType {
+    // Square-ish and wide regions are often charts
+    if (0.8..=1.2).contains(&aspect_ratio) || aspect_ratio > 1.5 {
+        return FigureType::Chart;
     }
 
-    let mut candidates = Vec::new();
+    // Tall regions might be diagrams
+    if aspect_ratio < 0.7 {
+        return FigureType::Diagram;
+    }
 
-    // Sort text regions by vertical position
-    let mut sorted_regions = text_regions.to_vec();
-    sorted_regions.sort_by(|a, b| a[1].partial_cmp(&b[1]).unwrap());
+    FigureType::Unknown
+}
 
-    // Find vertical gaps between text blocks
-    let mut last_bottom = 0.0;
-    for region in &sorted_regions {
-        let gap_height = region[1] - last_bottom;
+/// Confidence that a region is a figure.
+fn calculate_confidence(captioned: bool, from_graphics: bool, area_ratio: f64) -> f32 {
+    let mut confidence: f32 = 0.4;
+    if captioned {
+        confidence += 0.3;
+    }
+    if from_graphics {
+        confidence += 0.2;
+    }
+    if (0.02..=0.8).contains(&area_ratio) {
+        confidence += 0.1;
+    }
+    confidence.min(1.0)
+}
 
-        // If there's a significant gap, it might contain a figure
-        if gap_height > 50.0 {
-            candidates.push([0.0, last_bottom, page_width, region[1]]);
+/// Locate the figures of a page from its graphics and text blocks, all in
+/// PDF coordinates, ordered top to bottom and left to right.
+pub(super) fn locate_figures(
+    graphics: &[Graphic],
+    blocks: &[TextBlock],
+    page_width: f64,
+    page_height: f64,
+) -> Vec<FigureRegion> {
+    let page = [0.0, 0.0, page_width, page_height];
+    let page_area = page_width * page_height;
+
+    let graphics: Vec<Graphic> = graphics
+        .iter()
+        .map(|g| Graphic {
+            rect: intersection(g.rect, page),
+            image: g.image,
+        })
+        .filter(|g| g.rect[2] >= g.rect[0] && g.rect[3] >= g.rect[1])
+        .filter(|g| g.image || area(g.rect) < MAX_BACKGROUND_SHARE * page_area)
+        .collect();
+    let mut clusters = cluster_graphics(&graphics);
+
+    let (captions, mut text): (Vec<_>, Vec<_>) = blocks
+        .iter()
+        .partition(|b| parse_caption(&b.text).is_some());
+
+    // Take in the text drawn over or right next to the graphics, top to
+    // bottom, so that the rows of a ruled table join its rules.
+    text.sort_by(|a, b| b.rect[3].total_cmp(&a.rect[3]));
+    for block in text {
+        let width = block.rect[2] - block.rect[0];
+        if let Some(cluster) = clusters.iter_mut().find(|c| {
+            near(c.rect, block.rect, TEXT_GAP)
+                && horizontal_overlap(c.rect, block.rect) >= MIN_TEXT_OVERLAP * width
+        }) {
+            cluster.rect = union(cluster.rect, block.rect);
         }
-
-        last_bottom = last_bottom.max(region[3]);
     }
+    let clusters: Vec<Cluster> = merge_clusters(clusters)
+        .into_iter()
+        .filter(|c| {
+            let (width, height) = (c.rect[2] - c.rect[0], c.rect[3] - c.rect[1]);
+            width >= MIN_FIGURE_SIZE
+                && height >= MIN_FIGURE_SIZE
+                && width * height >= MIN_FIGURE_AREA
+        })
+        .collect();
 
-    // Check for gap at the bottom of the page
-    let gap_height = page_height - last_bottom;
-    if gap_height > 50.0 {
-        candidates.push([0.0, last_bottom, page_width, page_height]);
+    // Match captions to regions, closest pairs first
+    let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+    for (ci, caption) in captions.iter().enumerate() {
+        let table = parse_caption(&caption.text).is_some_and(|(table, _)| table);
+        for (ri, cluster) in clusters.iter().enumerate() {
+            let width = (caption.rect[2] - caption.rect[0]).min(cluster.rect[2] - cluster.rect[0]);
+            if horizontal_overlap(caption.rect, cluster.rect) < 0.5 * width {
+                continue;
+            }
+            let below = cluster.rect[1] - caption.rect[3];
+            let above = caption.rect[1] - cluster.rect[3];
+            let (usual, unusual) = if table {
+                (above, below)
+            } else {
+                (below, above)
+            };
+            let distance = if usual >= -TEXT_GAP {
+                usual.max(0.0)
+            } else if unusual >= -TEXT_GAP {
+                unusual.max(0.0) + CAPTION_SIDE_PENALTY
+            } else {
+                continue;
+            };
+            if distance <= MAX_CAPTION_GAP + CAPTION_SIDE_PENALTY {
+                pairs.push((distance, ci, ri));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut caption_of: Vec<Option<usize>> = vec![None; clusters.len()];
+    let mut placed = vec![false; captions.len()];
+    for (_, ci, ri) in pairs {
+        if !placed[ci] && caption_of[ri].is_none() {
+            caption_of[ri] = Some(ci);
+            placed[ci] = true;
+        }
     }
 
-    // Also look for horizontal gaps within vertical bands
-    // This helps detect figures in multi-column layouts
-    for region in &sorted_regions {
-        // Check for wide gaps to the left or right of text blocks
-        let left_gap = region[0];
-        let right_gap = page_width - region[2];
-
-        // If there's a significant gap and the text is narrow,
-        // there might be a figure beside it
-        let text_width = region[2] - region[0];
-        let text_height = region[3] - region[1];
-
-        if left_gap > page_width * 0.3 && text_width < page_width * 0.5 && text_height > 100.0 {
-            candidates.push([0.0, region[1], left_gap - 10.0, region[3]]);
+    let region = |rect: [f64; 4], caption: Option<&TextBlock>, image_area: f64, from_graphics| {
+        let parsed = caption.and_then(|c| parse_caption(&c.text));
+        let figure_type = match &parsed {
+            Some((true, _)) => FigureType::Table,
+            _ if image_area >= 0.5 * area(rect) && image_area > 0.0 => FigureType::Image,
+            _ if from_graphics => {
+                estimate_figure_type((rect[2] - rect[0]) / (rect[3] - rect[1]).max(1.0))
+            }
+            _ => FigureType::Unknown,
+        };
+        FigureRegion {
+            index: 0,
+            rect,
+            figure_type,
+            confidence: calculate_confidence(
+                caption.is_some(),
+                from_graphics,
+                area(rect) / page_area,
+            ),
+            caption: caption.map(|c| c.text.clone()),
+            number: parsed.map(|(_, number)| number),
         }
+    };
+
+    let mut figures: Vec<FigureRegion> = clusters
+        .iter()
+        .zip(&caption_of)
+        .map(|(cluster, caption)| {
+            region(
+                cluster.rect,
+                caption.map(|ci| captions[ci]),
+                cluster.image_area,
+                true,
+            )
+        })
+        .collect();
 
-        if right_gap > page_width * 0.3 && text_width < page_width * 0.5 && text_height > 100.0 {
-            candidates.push([region[2] + 10.0, region[1], page_width, region[3]]);
+    // A figure caption with nothing drawn above it: the figure is the blank
+    // space between the caption and the text above.
+    for (ci, caption) in captions.iter().enumerate() {
+        if placed[ci] || parse_caption(&caption.text).is_some_and(|(table, _)| table) {
+            continue;
+        }
+        let top = blocks
+            .iter()
+            .filter(|b| {
+                b.rect[1] >= caption.rect[3] && horizontal_overlap(b.rect, caption.rect) > 0.0
+            })
+            .map(|b| b.rect[1])
+            .fold(page_height, f64::min);
+        let rect = [
+            caption.rect[0],
+            caption.rect[3] + CAPTION_REGION_MARGIN,
+            caption.rect[2],
+            top - CAPTION_REGION_MARGIN,
+        ];
+        let (width, height) = (rect[2] - rect[0], rect[3] - rect[1]);
+        if width >= MIN_FIGURE_SIZE
+            && height >= MIN_FIGURE_SIZE
+            && width * height >= MIN_FIGURE_AREA
+        {
+            figures.push(region(rect, Some(caption), 0.0, false));
         }
     }
 
-    // Merge overlapping candidates
-    merge_overlapping_regions(candidates)
-}
-
-/// Merge overlapping rectangular regions.
-fn merge_overlapping_regions(regions: Vec<[f64; 4]>) -> Vec<[f64; 4]> {
-    if regions.is_empty() {
-        return vec![];
+    figures.sort_by(|a, b| {
+        b.rect[3]
+            .total_cmp(&a.rect[3])
+            .then(a.rect[0].total_cmp(&b.rect[0]))
+    });
+    for (i, fig) in figures.iter_mut().enumerate() {
+        fig.index = i;
     }
+    figures
+}
 
-    let mut merged = vec![regions[0]];
-
-    for region in regions.into_iter().skip(1) {
-        let mut was_merged = false;
+/// Records the bounding box of every drawing and image a page paints.
+struct GraphicsRecorder {
+    page_height: f64,
+    graphics: Vec<Graphic>,
+}
 
-        for existing in &mut merged {
-            if regions_overlap(existing, &region) {
-                // Merge by taking the bounding box
-                existing[0] = existing[0].min(region[0]);
-                existing[1] = existing[1].min(region[1]);
-                existing[2] = existing[2].max(region[2]);
-                existing[3] = existing[3].max(region[3]);
-                was_merged = true;
-                break;
-            }
+impl GraphicsRecorder {
+    fn push(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, image: bool) {
+        // Transform to PDF coords (origin bottom-left)
+        let rect = [
+            x0 as f64,
+            self.page_height - y1 as f64,
+            x1 as f64,
+            self.page_height - y0 as f64,
+        ];
+        if rect.iter().all(|v| v.is_finite()) {
+            self.graphics.push(Graphic { rect, image });
         }
+    }
 
-        if !was_merged {
-            merged.push(region);
+    fn push_path(&mut self, path: &mupdf::Path, stroke: &StrokeState, cmt: &Matrix) {
+        if let Ok(r) = path.bounds(stroke, cmt) {
+            self.push(r.x0, r.y0, r.x1, r.y1, false);
         }
     }
 
-    merged
-}
-
-/// Check if two rectangles overlap.
-fn regions_overlap(a: &[f64; 4], b: &[f64; 4]) -> bool {
-    !(a[2] < b[0] || b[2] < a[0] || a[3] < b[1] || b[3] < a[1])
+    fn push_image(&mut self, cmt: &Matrix) {
+        // Images are drawn into the unit square
+        let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+            .map(|(x, y)| Point::new(x, y).transform(cmt));
+        let xs = corners.map(|p| p.x);
+        let ys = corners.map(|p| p.y);
+        self.push(
+            xs.into_iter().fold(f32::INFINITY, f32::min),
+            ys.into_iter().fold(f32::INFINITY, f32::min),
+            xs.into_iter().fold(f32::NEG_INFINITY, f32::max),
+            ys.into_iter().fold(f32::NEG_INFINITY, f32::max),
+            true,
+        );
+    }
 }
 
-/// Calculate confidence score for a potential figure region.
-fn calculate_confidence(width: f64, height: f64, page_width: f64, page_height: f64) -> f32 {
-    let mut confidence: f32 = 0.5;
+impl NativeDevice for GraphicsRecorder {
+    fn fill_path(
+        &mut self,
+        path: &mupdf::Path,
+        _even_odd: bool,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        self.push_path(path, &StrokeState::default(), &cmt);
+    }
 
-    // Prefer regions that are a reasonable size relative to the page
-    let area_ratio = (width * height) / (page_width * page_height);
-    if (0.05..=0.5).contains(&area_ratio) {
-        confidence += 0.2;
+    fn stroke_path(
+        &mut self,
+        path: &mupdf::Path,
+        stroke_state: &StrokeState,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        self.push_path(path, stroke_state, &cmt);
     }
 
-    // Prefer regions with reasonable aspect ratios
-    let aspect_ratio = width / height;
-    if (0.5..=2.0).contains(&aspect_ratio) {
-        confidence += 0.2;
+    fn fill_image(&mut self, _img: &Image, cmt: Matrix, _alpha: f32, _cp: ColorParams) {
+        self.push_image(&cmt);
     }
 
-    // Prefer regions that are somewhat centered horizontally
-    let center_x = width / 2.0;
-    let page_center = page_width / 2.0;
-    let center_offset = (center_x - page_center).abs() / page_center;
-    if center_offset < 0.3 {
-        confidence += 0.1;
+    fn fill_image_mask(
+        &mut self,
+        _img: &Image,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        self.push_image(&cmt);
     }
+}
 
-    confidence.min(1.0)
+/// Detect figure regions on a PDF page.
+///
+/// The page's vector drawings and images are grouped into regions, together
+/// with the text drawn over them, and "Figure N:" / "Table N:" captions are
+/// matched to the closest region. Captioned regions report the caption text
+/// and number, so a figure can be found by its label with
+/// [`FigureRegion::matches_label`].
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_num` - Zero-based page number
+///
+/// # Returns
+///
+/// A vector of `FigureRegion` structures, ordered top to bottom and left to right.
+///
+/// # Limitations
+///
+/// - Works best on standard academic paper layouts
+/// - Tables without ruling lines are only found through their caption if they
+///   are drawn with graphics
+/// - Figures drawn closer than 12pt to each other are reported as one
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::image::detect_figures;
+///
+/// let figures = detect_figures("/path/to/file.pdf", 0)?;
+/// for fig in figures {
+///     println!("Figure {} at {:?}: {}", fig.index, fig.rect, fig.figure_type.description());
+///     if let Some(label) = fig.label() {
+///         println!("  {}: {}", label, fig.caption.unwrap_or_default());
+///     }
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn detect_figures<P: AsRef<Path>>(path: P, page_num: usize) -> Result<Vec<FigureRegion>> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    page_figures(&doc, page_num)
 }
 
-/// Estimate the figure type based on region properties.
-fn estimate_figure_type(aspect_ratio: f64, width: f64, height: f64) -> FigureType {
-    // Square-ish regions are often charts or diagrams
-    if (0.8..=1.2).contains(&aspect_ratio) {
-        return FigureType::Chart;
-    }
+/// Find a figure or table by its caption label, such as "Figure 3" or "Table 2".
+///
+/// Pages are searched in order; returns the zero-based page number and the
+/// region of the first figure whose caption has the label.
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::image::find_figure;
+///
+/// if let Some((page, fig)) = find_figure("/path/to/file.pdf", "Figure 3")? {
+///     println!("Figure 3 is on page {} at {:?}", page + 1, fig.rect);
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn find_figure<P: AsRef<Path>>(path: P, label: &str) -> Result<Option<(usize, FigureRegion)>> {
+    let path = path.as_ref();
 
-    // Wide regions are often charts or diagrams
-    if aspect_ratio > 1.5 {
-        return FigureType::Chart;
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    for page_num in 0..total_pages {
+        let figure = page_figures(&doc, page_num)?
+            .into_iter()
+            .find(|fig| fig.matches_label(label));
+        if let Some(figure) = figure {
+            return Ok(Some((page_num, figure)));
+        }
     }
+    Ok(None)
+}
 
-    // Tall regions might be diagrams
-    if aspect_ratio < 0.7 {
-        return FigureType::Diagram;
+/// Detect the figure regions of a page of an open document.
+pub(super) fn page_figures(doc: &Document, page_num: usize) -> Result<Vec<FigureRegion>> {
+    let page = doc
+        .load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+
+    // Get page dimensions
+    let page_bounds = page
+        .bounds()
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
+    let page_width = (page_bounds.x1 - page_bounds.x0) as f64;
+    let page_height = (page_bounds.y1 - page_bounds.y0) as f64;
+
+    let mut recorder = GraphicsRecorder {
+        page_height,
+        graphics: Vec::new(),
+    };
+    {
+        let device = Device::from_native(&mut recorder).map_err(|e| {
+            ZoteroClientError::Pdf(format!("Failed to create graphics device: {}", e))
+        })?;
+        page.run(&device, &Matrix::IDENTITY).map_err(|e| {
+            ZoteroClientError::Pdf(format!(
+                "Failed to read graphics of page {}: {}",
+                page_num, e
+            ))
+        })?;
     }
 
-    // Large regions are more likely to be images
-    if width > 300.0 && height > 300.0 {
-        return FigureType::Image;
+    let text_page = page
+        .to_text_page(TextPageOptions::empty())
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get text page: {}", e)))?;
+
+    // Collect text blocks, in PDF coordinates
+    let mut blocks = Vec::new();
+    for block in text_page.blocks() {
+        if block.r#type() != TextBlockType::Text {
+            continue;
+        }
+        let text: Vec<String> = block
+            .lines()
+            .map(|line| line.chars().filter_map(|c| c.char()).collect::<String>())
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if text.is_empty() {
+            continue;
+        }
+        let bounds = block.bounds();
+        blocks.push(TextBlock {
+            rect: [
+                bounds.x0 as f64,
+                page_height - bounds.y1 as f64,
+                bounds.x1 as f64,
+                page_height - bounds.y0 as f64,
+            ],
+            text: text.join(" "),
+        });
     }
 
-    FigureType::Unknown
+    Ok(locate_figures(
+        &recorder.graphics,
+        &blocks,
+        page_width,
+        page_height,
+    ))
 }
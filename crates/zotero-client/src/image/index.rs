@@ -0,0 +1,96 @@
+//! Document-wide figure index.
+//!
+//! [`detect_figures`](super::detect_figures) works on one page. To find
+//! "Figure 5" without knowing its page, [`index_figures`] runs detection over
+//! every page and [`FigureIndexCache`] keeps the result per file, rebuilding
+//! it when the file's modification time changes.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use mupdf::Document;
+
+use super::detection::{page_figures, FigureRegion};
+use crate::error::{Result, ZoteroClientError};
+use crate::util::{file_modified, FileCache};
+
+/// A figure of a document and the page it is on.
+#[derive(Debug, Clone)]
+pub struct IndexedFigure {
+    /// Zero-based page number
+    pub page: u32,
+    /// The figure, with its index among the figures of its page
+    pub figure: FigureRegion,
+}
+
+/// The figures of every page of a document, in page order.
+#[derive(Debug, Clone, Default)]
+pub struct FigureIndex {
+    /// Modification time of the file the index was built from, in seconds
+    /// since the Unix epoch
+    pub modified: u64,
+    /// The figures, page by page and top to bottom on each page
+    pub figures: Vec<IndexedFigure>,
+}
+
+impl FigureIndex {
+    /// The first figure with a caption label, such as "Figure 5" or "Table 2".
+    pub fn find(&self, label: &str) -> Option<&IndexedFigure> {
+        self.figures.iter().find(|f| f.figure.matches_label(label))
+    }
+
+    /// The figures that have a caption.
+    pub fn captioned(&self) -> impl Iterator<Item = &IndexedFigure> {
+        self.figures.iter().filter(|f| f.figure.caption.is_some())
+    }
+}
+
+/// Detect the figures of every page of a PDF.
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::image::index_figures;
+///
+/// let index = index_figures("/path/to/file.pdf")?;
+/// for entry in index.captioned() {
+///     println!("p. {}: {:?}", entry.page + 1, entry.figure.label());
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn index_figures<P: AsRef<Path>>(path: P) -> Result<FigureIndex> {
+    let path = path.as_ref();
+    let modified = file_modified(path)?;
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    let mut figures = Vec::new();
+    for page_num in 0..total_pages {
+        figures.extend(
+            page_figures(&doc, page_num)?
+                .into_iter()
+                .map(|figure| IndexedFigure {
+                    page: page_num as u32,
+                    figure,
+                }),
+        );
+    }
+
+    Ok(FigureIndex { modified, figures })
+}
+
+/// A cache of figure indexes by file path, rebuilt when the file changes.
+pub type FigureIndexCache = FileCache<FigureIndex>;
+
+impl FileCache<FigureIndex> {
+    /// The figure index of a PDF, built on first use and after the file changes.
+    pub fn get(&self, path: impl AsRef<Path>) -> Result<Arc<FigureIndex>> {
+        self.get_or_insert_with(path, |path| index_figures(path))
+    }
+}
@@ -4,9 +4,11 @@
 //! analyzed by vision AI models (Claude, GPT-4V, etc.). It supports:
 //!
 //! - Rendering full pages at configurable DPI
+//! - Fitting rendered images to pixel and byte limits by lowering the DPI or JPEG quality
 //! - Rendering specific rectangular regions
 //! - Extracting embedded images from PDFs
-//! - Detecting figure regions using heuristics
+//! - Detecting figures and tables with their captions from the page's drawings and images
+//! - Indexing the figures of a whole document, cached per file
 //!
 //! Images are returned as base64-encoded strings suitable for MCP tool responses.
 //!
@@ -15,22 +17,24 @@
 //! # Example
 //!
 //! ```no_run
-//! use zotero_client::image::{render_page, ImageFormat};
+//! use zotero_client::image::{render_page, ImageFormat, RenderLimits};
 //!
 //! // Render page 1 at 150 DPI as PNG
-//! let result = render_page("/path/to/file.pdf", 0, 150, ImageFormat::Png)?;
+//! let result = render_page("/path/to/file.pdf", 0, 150, ImageFormat::Png, RenderLimits::default())?;
 //! println!("Base64 image: {} bytes", result.data.len());
 //! println!("MIME type: {}", result.mime_type);
 //! # Ok::<(), zotero_client::error::ZoteroClientError>(())
 //! ```
 
 mod detection;
+mod index;
 mod render;
 
-pub use detection::{detect_figures, FigureRegion, FigureType};
+pub use detection::{detect_figures, find_figure, FigureRegion, FigureType};
+pub use index::{index_figures, FigureIndex, FigureIndexCache, IndexedFigure};
 pub use render::{
     extract_embedded_images, render_page, render_page_to_file, render_region,
-    render_region_to_file, EmbeddedImage, ImageFormat, ImageOutput,
+    render_region_to_file, EmbeddedImage, ImageFile, ImageFormat, ImageOutput, RenderLimits,
 };
 
 #[cfg(test)]
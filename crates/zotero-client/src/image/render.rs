@@ -4,9 +4,17 @@ use crate::error::{Result, ZoteroClientError};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use image::codecs::jpeg::JpegEncoder;
 use image::ImageEncoder;
-use mupdf::{Colorspace, Document, Matrix};
+use mupdf::{Colorspace, Device, Document, IRect, Matrix, Page, Pixmap, Rect};
 use std::path::Path;
 
+/// JPEG qualities tried in turn to fit [`RenderLimits::max_bytes`]; the
+/// first is the quality used without a byte limit.
+const JPEG_QUALITIES: [u8; 5] = [85, 70, 55, 40, 25];
+
+/// Margin kept below the DPI estimated to fit a byte limit, since the
+/// encoded size does not shrink exactly with the pixel count.
+const BYTES_DPI_MARGIN: f64 = 0.9;
+
 /// Output format for rendered images.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ImageFormat {
@@ -38,6 +46,81 @@ pub struct ImageOutput {
     pub width: u32,
     /// Image height in pixels
     pub height: u32,
+    /// Resolution the image was rendered at, lower than requested when
+    /// the image had to fit [`RenderLimits`]
+    pub dpi: u32,
+}
+
+/// A rendered image saved to a file.
+#[derive(Debug, Clone)]
+pub struct ImageFile {
+    /// Absolute path to the image file
+    pub path: String,
+    /// Image width in pixels
+    pub width: u32,
+    /// Image height in pixels
+    pub height: u32,
+    /// Resolution the image was rendered at, lower than requested when
+    /// the image had to fit [`RenderLimits`]
+    pub dpi: u32,
+}
+
+/// Size limits for a rendered image.
+///
+/// The DPI is lowered until the image fits `max_width` and `max_height`.
+/// When the encoded image is larger than `max_bytes`, JPEG images are first
+/// encoded at lower qualities, then PNG and JPEG images are rendered again
+/// at a lower DPI.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::image::RenderLimits;
+///
+/// let limits = RenderLimits { max_width: Some(1568), ..Default::default() };
+/// // A US Letter page (612pt wide) fits 1568 pixels up to 184 DPI.
+/// assert_eq!(limits.fit_dpi(300, 612.0, 792.0), 184);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderLimits {
+    /// Maximum image width in pixels.
+    pub max_width: Option<u32>,
+    /// Maximum image height in pixels.
+    pub max_height: Option<u32>,
+    /// Maximum size of the encoded image in bytes. Base64 data is a third
+    /// larger.
+    pub max_bytes: Option<usize>,
+}
+
+impl RenderLimits {
+    /// The highest DPI up to `dpi` at which an area of `width` x `height`
+    /// points fits the pixel limits, or 0 if none does.
+    pub fn fit_dpi(&self, dpi: u32, width: f32, height: f32) -> u32 {
+        let fit = |max: Option<u32>, points: f32| match max {
+            Some(max) if points > 0.0 => (max as f64 * 72.0 / points as f64).floor() as u32,
+            _ => u32::MAX,
+        };
+        dpi.min(fit(self.max_width, width))
+            .min(fit(self.max_height, height))
+    }
+
+    /// A DPI below `dpi` at which an image that encoded to `bytes` bytes
+    /// should fit `max_bytes`, assuming the size follows the pixel count.
+    pub(super) fn dpi_for_bytes(&self, dpi: u32, bytes: usize) -> u32 {
+        let Some(max_bytes) = self.max_bytes else {
+            return dpi;
+        };
+        let ratio = (max_bytes as f64 / bytes as f64).sqrt() * BYTES_DPI_MARGIN;
+        ((dpi as f64 * ratio).floor() as u32).min(dpi.saturating_sub(1))
+    }
+}
+
+/// An encoded image and the resolution it was rendered at.
+struct Rendered {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    dpi: u32,
 }
 
 /// An embedded image extracted from a PDF.
@@ -65,6 +148,7 @@ pub struct EmbeddedImage {
 /// * `page_num` - Zero-based page number
 /// * `dpi` - Resolution in dots per inch (default: 150)
 /// * `format` - Output format (PNG or JPEG)
+/// * `limits` - Size limits the image must fit, lowering the DPI or JPEG quality
 ///
 /// # Returns
 ///
@@ -73,10 +157,11 @@ pub struct EmbeddedImage {
 /// # Example
 ///
 /// ```no_run
-/// use zotero_client::image::{render_page, ImageFormat};
+/// use zotero_client::image::{render_page, ImageFormat, RenderLimits};
 ///
-/// let output = render_page("/path/to/file.pdf", 0, 150, ImageFormat::Png)?;
-/// println!("Rendered {}x{} image", output.width, output.height);
+/// let limits = RenderLimits { max_width: Some(1568), max_height: Some(1568), ..Default::default() };
+/// let output = render_page("/path/to/file.pdf", 0, 300, ImageFormat::Png, limits)?;
+/// println!("Rendered {}x{} image at {} DPI", output.width, output.height, output.dpi);
 /// # Ok::<(), zotero_client::error::ZoteroClientError>(())
 /// ```
 pub fn render_page<P: AsRef<Path>>(
@@ -84,44 +169,11 @@ pub fn render_page<P: AsRef<Path>>(
     page_num: usize,
     dpi: u32,
     format: ImageFormat,
+    limits: RenderLimits,
 ) -> Result<ImageOutput> {
-    let path = path.as_ref();
-    let dpi = if dpi == 0 { 150 } else { dpi };
-
-    let doc = Document::open(path).map_err(|e| {
-        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
-    })?;
-
-    let page = doc
-        .load_page(page_num as i32)
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
-
-    // Calculate scale factor from DPI (PDF default is 72 DPI)
-    let scale = dpi as f32 / 72.0;
-    let ctm = Matrix::new_scale(scale, scale);
-
-    // Render the page to a pixmap
-    let pixmap = page
-        .to_pixmap(&ctm, &Colorspace::device_rgb(), true, true)
-        .map_err(|e| {
-            ZoteroClientError::Pdf(format!("Failed to render page {}: {}", page_num, e))
-        })?;
-
-    let width = pixmap.width();
-    let height = pixmap.height();
-
-    // Get raw pixel data (RGBA format when alpha=true)
-    let samples = pixmap.samples();
-
-    // Encode to the requested format
-    let encoded = encode_image(samples, width, height, format, 85)?;
-
-    Ok(ImageOutput {
-        data: STANDARD.encode(&encoded),
-        mime_type: format.mime_type().to_string(),
-        width,
-        height,
-    })
+    let page = load_page(path.as_ref(), page_num)?;
+    let rendered = render_fitted(&page, page_num, None, dpi, format, limits)?;
+    Ok(rendered.into_output(format))
 }
 
 /// Render a specific region of a PDF page as an image.
@@ -133,6 +185,7 @@ pub fn render_page<P: AsRef<Path>>(
 /// * `rect` - Bounding box [x1, y1, x2, y2] in PDF coordinates
 /// * `dpi` - Resolution in dots per inch (default: 150)
 /// * `format` - Output format (PNG or JPEG)
+/// * `limits` - Size limits the image must fit, lowering the DPI or JPEG quality
 ///
 /// # Returns
 ///
@@ -142,28 +195,105 @@ pub fn render_page<P: AsRef<Path>>(
 ///
 /// The rect coordinates are in PDF coordinate system (origin bottom-left).
 /// If the rect extends beyond page boundaries, it will be clipped.
+///
+/// Only the region is rasterized, so time and memory grow with the size of
+/// the region rather than the page. The pixels are the same as those of the
+/// region in a full-page rendering at the same DPI.
 pub fn render_region<P: AsRef<Path>>(
     path: P,
     page_num: usize,
     rect: [f64; 4],
     dpi: u32,
     format: ImageFormat,
+    limits: RenderLimits,
 ) -> Result<ImageOutput> {
-    let path = path.as_ref();
-    let dpi = if dpi == 0 { 150 } else { dpi };
+    let page = load_page(path.as_ref(), page_num)?;
+    let rendered = render_fitted(&page, page_num, Some(rect), dpi, format, limits)?;
+    Ok(rendered.into_output(format))
+}
 
+pub(super) fn load_page(path: &Path, page_num: usize) -> Result<Page> {
     let doc = Document::open(path).map_err(|e| {
         ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
     })?;
 
-    let page = doc
-        .load_page(page_num as i32)
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+    doc.load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))
+}
+
+/// Render a page, or the region `rect` of it, within `limits`.
+///
+/// The DPI is first lowered to fit the pixel limits. While the encoded
+/// image is over the byte limit, JPEG images are encoded at lower qualities
+/// and then the page is rendered again at a DPI estimated to fit.
+fn render_fitted(
+    page: &Page,
+    page_num: usize,
+    rect: Option<[f64; 4]>,
+    dpi: u32,
+    format: ImageFormat,
+    limits: RenderLimits,
+) -> Result<Rendered> {
+    let dpi = if dpi == 0 { 150 } else { dpi };
 
     // Get page bounds for coordinate transformation
     let page_bounds = page
         .bounds()
         .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
+    let clip = rect.map(|rect| region_clip(&page_bounds, rect));
+    let area = clip.as_ref().unwrap_or(&page_bounds);
+
+    let qualities = match format {
+        ImageFormat::Png => &JPEG_QUALITIES[..1],
+        ImageFormat::Jpeg => &JPEG_QUALITIES[..],
+    };
+
+    let mut dpi = limits.fit_dpi(dpi, area.x1 - area.x0, area.y1 - area.y0);
+    loop {
+        if dpi == 0 {
+            return Err(ZoteroClientError::InvalidInput(format!(
+                "Cannot render page {} within the size limits {:?}",
+                page_num, limits
+            )));
+        }
+
+        let pixmap = match &clip {
+            Some(clip) => region_pixmap(page, page_num, &page_bounds, clip, dpi)?,
+            None => page_pixmap(page, page_num, dpi)?,
+        };
+        let width = pixmap.width();
+        let height = pixmap.height();
+
+        let mut data = Vec::new();
+        for &quality in qualities {
+            data = encode_image(pixmap.samples(), width, height, format, quality)?;
+            if limits.max_bytes.is_none_or(|max| data.len() <= max) {
+                return Ok(Rendered {
+                    data,
+                    width,
+                    height,
+                    dpi,
+                });
+            }
+        }
+
+        dpi = limits.dpi_for_bytes(dpi, data.len());
+    }
+}
+
+/// Render a whole page at `dpi`.
+pub(super) fn page_pixmap(page: &Page, page_num: usize, dpi: u32) -> Result<Pixmap> {
+    // Calculate scale factor from DPI (PDF default is 72 DPI)
+    let scale = dpi as f32 / 72.0;
+    let ctm = Matrix::new_scale(scale, scale);
+
+    // Render the page to a pixmap (RGBA since alpha=true)
+    page.to_pixmap(&ctm, &Colorspace::device_rgb(), true, true)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to render page {}: {}", page_num, e)))
+}
+
+/// The part of the page inside `rect` (PDF coordinates), in MuPDF coordinates.
+pub(super) fn region_clip(page_bounds: &Rect, rect: [f64; 4]) -> Rect {
     let page_height = page_bounds.y1 - page_bounds.y0;
 
     // Convert PDF coordinates (origin bottom-left) to MuPDF coordinates (origin top-left)
@@ -173,64 +303,70 @@ pub fn render_region<P: AsRef<Path>>(
     let mupdf_y2 = page_height - rect[1] as f32; // PDF y1 -> MuPDF y2
 
     // Clip to page bounds
-    let clip_x1 = (rect[0] as f32).max(page_bounds.x0);
-    let clip_y1 = mupdf_y1.max(page_bounds.y0);
-    let clip_x2 = (rect[2] as f32).min(page_bounds.x1);
-    let clip_y2 = mupdf_y2.min(page_bounds.y1);
+    Rect {
+        x0: (rect[0] as f32).max(page_bounds.x0),
+        y0: mupdf_y1.max(page_bounds.y0),
+        x1: (rect[2] as f32).min(page_bounds.x1),
+        y1: mupdf_y2.min(page_bounds.y1),
+    }
+}
 
+/// Rasterize the part of a page inside `clip` (MuPDF coordinates).
+///
+/// The pixmap covers the pixels the region takes up in the page pixmap of
+/// `Page::to_pixmap` at the same scale, and a draw device clipped to them
+/// keeps MuPDF from rendering anything else.
+pub(super) fn region_pixmap(
+    page: &Page,
+    page_num: usize,
+    page_bounds: &Rect,
+    clip: &Rect,
+    dpi: u32,
+) -> Result<Pixmap> {
     // Calculate scale factor
     let scale = dpi as f32 / 72.0;
 
-    // Render the full page first (MuPDF doesn't have native clip support in to_pixmap)
-    let full_pixmap = page
-        .to_pixmap(
-            &Matrix::new_scale(scale, scale),
-            &Colorspace::device_rgb(),
-            true,
-            true,
-        )
-        .map_err(|e| {
-            ZoteroClientError::Pdf(format!("Failed to render page {}: {}", page_num, e))
-        })?;
-
-    // Calculate pixel coordinates for the region
-    let px_x1 = ((clip_x1 - page_bounds.x0) * scale) as u32;
-    let px_y1 = ((clip_y1 - page_bounds.y0) * scale) as u32;
-    let px_x2 = ((clip_x2 - page_bounds.x0) * scale) as u32;
-    let px_y2 = ((clip_y2 - page_bounds.y0) * scale) as u32;
+    // Calculate pixel coordinates for the region, relative to the page pixmap
+    let px_x1 = ((clip.x0 - page_bounds.x0) * scale) as u32;
+    let px_y1 = ((clip.y0 - page_bounds.y0) * scale) as u32;
+    let px_x2 = ((clip.x1 - page_bounds.x0) * scale) as u32;
+    let px_y2 = ((clip.y1 - page_bounds.y0) * scale) as u32;
 
-    let region_width = px_x2.saturating_sub(px_x1);
-    let region_height = px_y2.saturating_sub(px_y1);
-
-    if region_width == 0 || region_height == 0 {
+    if px_x2 <= px_x1 || px_y2 <= px_y1 {
         return Err(ZoteroClientError::Pdf(
             "Region has zero width or height".to_string(),
         ));
     }
 
-    // Extract the region from the full pixmap
-    let full_samples = full_pixmap.samples();
-    let full_width = full_pixmap.width();
-    let n = full_pixmap.n() as u32; // Components per pixel (typically 4 for RGBA)
-
-    let mut region_samples = Vec::with_capacity((region_width * region_height * n) as usize);
-    for y in px_y1..px_y2 {
-        let start = ((y * full_width + px_x1) * n) as usize;
-        let end = start + (region_width * n) as usize;
-        if end <= full_samples.len() {
-            region_samples.extend_from_slice(&full_samples[start..end]);
-        }
+    // The page pixmap starts at the scaled page origin, rounded the way
+    // MuPDF rounds it (fz_round_rect)
+    let origin_x = (page_bounds.x0 * scale + 0.001).floor() as i32;
+    let origin_y = (page_bounds.y0 * scale + 0.001).floor() as i32;
+    let bbox = IRect::new(
+        origin_x + px_x1 as i32,
+        origin_y + px_y1 as i32,
+        origin_x + px_x2 as i32,
+        origin_y + px_y2 as i32,
+    );
+
+    // Transparent like the page pixmap of `to_pixmap` with alpha
+    let mut pixmap = Pixmap::new_with_rect(&Colorspace::device_rgb(), bbox, true)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to create pixmap: {}", e)))?;
+    pixmap
+        .clear()
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to clear pixmap: {}", e)))?;
+
+    {
+        // The device must be closed (dropped) before the samples are read
+        let device = Device::from_pixmap_with_clip(&pixmap, bbox)
+            .map_err(|e| ZoteroClientError::Pdf(format!("Failed to create device: {}", e)))?;
+        page.run(&device, &Matrix::new_scale(scale, scale))
+            .map_err(|e| {
+                ZoteroClientError::Pdf(format!("Failed to render page {}: {}", page_num, e))
+            })?;
     }
 
-    // Encode the region
-    let encoded = encode_image(&region_samples, region_width, region_height, format, 85)?;
-
-    Ok(ImageOutput {
-        data: STANDARD.encode(&encoded),
-        mime_type: format.mime_type().to_string(),
-        width: region_width,
-        height: region_height,
-    })
+    Ok(pixmap)
 }
 
 /// Extract embedded images from a PDF page.
@@ -296,55 +432,18 @@ pub fn extract_embedded_images<P: AsRef<Path>>(
 ///
 /// # Returns
 ///
-/// The absolute path to the saved image file.
+/// The saved image file, with its absolute path.
 pub fn render_page_to_file<P: AsRef<Path>, O: AsRef<Path>>(
     pdf_path: P,
     page_num: usize,
     dpi: u32,
     format: ImageFormat,
+    limits: RenderLimits,
     output_path: O,
-) -> Result<String> {
-    let pdf_path = pdf_path.as_ref();
-    let output_path = output_path.as_ref();
-    let dpi = if dpi == 0 { 150 } else { dpi };
-
-    let doc = Document::open(pdf_path).map_err(|e| {
-        ZoteroClientError::Pdf(format!(
-            "Failed to open PDF '{}': {}",
-            pdf_path.display(),
-            e
-        ))
-    })?;
-
-    let page = doc
-        .load_page(page_num as i32)
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
-
-    let scale = dpi as f32 / 72.0;
-    let ctm = Matrix::new_scale(scale, scale);
-
-    let pixmap = page
-        .to_pixmap(&ctm, &Colorspace::device_rgb(), true, true)
-        .map_err(|e| {
-            ZoteroClientError::Pdf(format!("Failed to render page {}: {}", page_num, e))
-        })?;
-
-    let samples = pixmap.samples();
-    let encoded = encode_image(samples, pixmap.width(), pixmap.height(), format, 85)?;
-
-    std::fs::write(output_path, &encoded).map_err(|e| {
-        ZoteroClientError::Pdf(format!(
-            "Failed to write image to '{}': {}",
-            output_path.display(),
-            e
-        ))
-    })?;
-
-    Ok(output_path
-        .canonicalize()
-        .unwrap_or_else(|_| output_path.to_path_buf())
-        .display()
-        .to_string())
+) -> Result<ImageFile> {
+    let page = load_page(pdf_path.as_ref(), page_num)?;
+    let rendered = render_fitted(&page, page_num, None, dpi, format, limits)?;
+    rendered.save(output_path.as_ref())
 }
 
 /// Render a region of a PDF page and save it to a file.
@@ -354,30 +453,45 @@ pub fn render_region_to_file<P: AsRef<Path>, O: AsRef<Path>>(
     rect: [f64; 4],
     dpi: u32,
     format: ImageFormat,
+    limits: RenderLimits,
     output_path: O,
-) -> Result<String> {
-    // Use existing render_region to get the image, then save it
-    let output = render_region(pdf_path, page_num, rect, dpi, format)?;
-    let output_path = output_path.as_ref();
-
-    // Decode base64 and write to file
-    let decoded = STANDARD
-        .decode(&output.data)
-        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to decode base64 image: {}", e)))?;
-
-    std::fs::write(output_path, &decoded).map_err(|e| {
-        ZoteroClientError::Pdf(format!(
-            "Failed to write image to '{}': {}",
-            output_path.display(),
-            e
-        ))
-    })?;
+) -> Result<ImageFile> {
+    let page = load_page(pdf_path.as_ref(), page_num)?;
+    let rendered = render_fitted(&page, page_num, Some(rect), dpi, format, limits)?;
+    rendered.save(output_path.as_ref())
+}
 
-    Ok(output_path
-        .canonicalize()
-        .unwrap_or_else(|_| output_path.to_path_buf())
-        .display()
-        .to_string())
+impl Rendered {
+    fn into_output(self, format: ImageFormat) -> ImageOutput {
+        ImageOutput {
+            data: STANDARD.encode(&self.data),
+            mime_type: format.mime_type().to_string(),
+            width: self.width,
+            height: self.height,
+            dpi: self.dpi,
+        }
+    }
+
+    fn save(self, output_path: &Path) -> Result<ImageFile> {
+        std::fs::write(output_path, &self.data).map_err(|e| {
+            ZoteroClientError::Pdf(format!(
+                "Failed to write image to '{}': {}",
+                output_path.display(),
+                e
+            ))
+        })?;
+
+        Ok(ImageFile {
+            path: output_path
+                .canonicalize()
+                .unwrap_or_else(|_| output_path.to_path_buf())
+                .display()
+                .to_string(),
+            width: self.width,
+            height: self.height,
+            dpi: self.dpi,
+        })
+    }
 }
 
 /// Encode raw RGBA pixel data to PNG or JPEG format.
@@ -1,5 +1,6 @@
 //! Unit tests for image extraction module.
 
+use super::detection::{locate_figures, parse_caption, Graphic, TextBlock};
 use super::*;
 
 #[test]
@@ -20,6 +21,8 @@ fn test_figure_region_dimensions() {
         rect: [100.0, 200.0, 400.0, 500.0],
         figure_type: FigureType::Image,
         confidence: 0.8,
+        caption: None,
+        number: None,
     };
 
     assert_eq!(fig.width(), 300.0);
@@ -34,6 +37,8 @@ fn test_figure_region_aspect_ratio() {
         rect: [0.0, 0.0, 400.0, 200.0],
         figure_type: FigureType::Chart,
         confidence: 0.7,
+        caption: None,
+        number: None,
     };
 
     assert!((wide_fig.aspect_ratio() - 2.0).abs() < 0.01);
@@ -43,6 +48,8 @@ fn test_figure_region_aspect_ratio() {
         rect: [0.0, 0.0, 100.0, 400.0],
         figure_type: FigureType::Diagram,
         confidence: 0.6,
+        caption: None,
+        number: None,
     };
 
     assert!((tall_fig.aspect_ratio() - 0.25).abs() < 0.01);
@@ -53,6 +60,7 @@ fn test_figure_type_descriptions() {
     assert_eq!(FigureType::Image.description(), "image");
     assert_eq!(FigureType::Chart.description(), "chart");
     assert_eq!(FigureType::Diagram.description(), "diagram");
+    assert_eq!(FigureType::Table.description(), "table");
     assert_eq!(FigureType::Unknown.description(), "figure");
 }
 
@@ -63,10 +71,12 @@ fn test_image_output_structure() {
         mime_type: "image/png".to_string(),
         width: 100,
         height: 200,
+        dpi: 150,
     };
 
     assert_eq!(output.width, 100);
     assert_eq!(output.height, 200);
+    assert_eq!(output.dpi, 150);
     assert_eq!(output.mime_type, "image/png");
 }
 
@@ -85,3 +95,326 @@ fn test_embedded_image_structure() {
     assert_eq!(img.width, 100);
     assert_eq!(img.rect[0], 10.0);
 }
+
+/// A US Letter page.
+const WIDTH: f64 = 612.0;
+const HEIGHT: f64 = 792.0;
+
+fn block(rect: [f64; 4], text: &str) -> TextBlock {
+    TextBlock {
+        rect,
+        text: text.to_string(),
+    }
+}
+
+fn drawing(rect: [f64; 4]) -> Graphic {
+    Graphic { rect, image: false }
+}
+
+#[test]
+fn test_parse_caption() {
+    assert_eq!(
+        parse_caption("Figure 3: Overview of the model."),
+        Some((false, "3".to_string()))
+    );
+    assert_eq!(
+        parse_caption("Fig. 12. Results"),
+        Some((false, "12".to_string()))
+    );
+    assert_eq!(
+        parse_caption("TABLE II ABLATION RESULTS"),
+        Some((true, "II".to_string()))
+    );
+    assert_eq!(
+        parse_caption("Table S1: Hyperparameters"),
+        Some((true, "S1".to_string()))
+    );
+    assert_eq!(parse_caption("Figure 3 shows the results."), None);
+    assert_eq!(parse_caption("As Figure 3: shows"), None);
+}
+
+#[test]
+fn test_figure_with_caption_below() {
+    // A chart drawn as many paths, with axis labels as text, and its caption below
+    let mut graphics: Vec<Graphic> = (0..10)
+        .map(|i| {
+            let x = 100.0 + 30.0 * i as f64;
+            drawing([x, 450.0, x + 20.0, 450.0 + 15.0 * i as f64])
+        })
+        .collect();
+    graphics.push(drawing([95.0, 448.0, 400.0, 449.0]));
+    let blocks = vec![
+        block([72.0, 620.0, 540.0, 700.0], "Body text above the figure."),
+        block([100.0, 436.0, 400.0, 446.0], "0 1 2 3 4 5 6 7 8 9"),
+        block([72.0, 400.0, 540.0, 424.0], "Figure 2: Accuracy per epoch."),
+        block([72.0, 300.0, 540.0, 390.0], "Body text below the caption."),
+    ];
+
+    let figures = locate_figures(&graphics, &blocks, WIDTH, HEIGHT);
+    assert_eq!(figures.len(), 1);
+    let fig = &figures[0];
+    assert_eq!(fig.rect, [95.0, 436.0, 400.0, 585.0]);
+    assert_eq!(
+        fig.caption.as_deref(),
+        Some("Figure 2: Accuracy per epoch.")
+    );
+    assert_eq!(fig.label().as_deref(), Some("Figure 2"));
+    assert!(fig.matches_label("Figure 2"));
+    assert!(fig.matches_label("fig. 2"));
+    assert!(fig.matches_label("2"));
+    assert!(!fig.matches_label("Table 2"));
+    assert!(!fig.matches_label("Figure 3"));
+    assert!(fig.confidence > 0.8);
+}
+
+#[test]
+fn test_ruled_table_takes_in_its_rows() {
+    let graphics = vec![
+        drawing([100.0, 599.5, 500.0, 600.5]),
+        drawing([100.0, 583.5, 500.0, 584.5]),
+        drawing([100.0, 529.5, 500.0, 530.5]),
+    ];
+    let blocks = vec![
+        block(
+            [100.0, 610.0, 500.0, 630.0],
+            "Table 1: Results on the test set.",
+        ),
+        block([105.0, 587.0, 495.0, 597.0], "Model Accuracy F1"),
+        block([105.0, 570.0, 495.0, 580.0], "BERT 84.5 0.81"),
+        block([105.0, 557.0, 495.0, 567.0], "RoBERTa 87.2 0.85"),
+        block([105.0, 544.0, 495.0, 554.0], "Ours 89.0 0.88"),
+        block([105.0, 533.0, 495.0, 542.0], "Ours + pretraining 90.1 0.89"),
+        block([72.0, 400.0, 540.0, 510.0], "Body text after the table."),
+    ];
+
+    let figures = locate_figures(&graphics, &blocks, WIDTH, HEIGHT);
+    assert_eq!(figures.len(), 1);
+    let table = &figures[0];
+    assert_eq!(table.figure_type, FigureType::Table);
+    assert_eq!(table.rect, [100.0, 529.5, 500.0, 600.5]);
+    assert_eq!(table.label().as_deref(), Some("Table 1"));
+    assert!(table.matches_label("Table 1"));
+    assert!(!table.matches_label("Figure 1"));
+}
+
+#[test]
+fn test_images_and_drawings_side_by_side() {
+    let graphics = vec![
+        Graphic {
+            rect: [72.0, 500.0, 290.0, 700.0],
+            image: true,
+        },
+        drawing([320.0, 500.0, 540.0, 700.0]),
+        // A page-sized background and a header rule are not figures
+        drawing([0.0, 0.0, 612.0, 792.0]),
+        drawing([72.0, 750.0, 540.0, 751.0]),
+    ];
+    let blocks = vec![
+        block([72.0, 470.0, 290.0, 490.0], "Figure 1. A photograph."),
+        block([320.0, 470.0, 540.0, 490.0], "Figure 2. A sketch."),
+    ];
+
+    let figures = locate_figures(&graphics, &blocks, WIDTH, HEIGHT);
+    assert_eq!(figures.len(), 2);
+    assert_eq!(figures[0].figure_type, FigureType::Image);
+    assert_eq!(figures[0].number.as_deref(), Some("1"));
+    assert_eq!(figures[1].rect, [320.0, 500.0, 540.0, 700.0]);
+    assert_eq!(figures[1].number.as_deref(), Some("2"));
+    assert_eq!(figures[1].index, 1);
+}
+
+#[test]
+fn test_caption_without_drawings_marks_space_above() {
+    let blocks = vec![
+        block([72.0, 650.0, 540.0, 720.0], "Body text above the figure."),
+        block(
+            [72.0, 400.0, 540.0, 420.0],
+            "Figure 4: A figure drawn as text.",
+        ),
+    ];
+
+    let figures = locate_figures(&[], &blocks, WIDTH, HEIGHT);
+    assert_eq!(figures.len(), 1);
+    assert_eq!(figures[0].rect, [72.0, 422.0, 540.0, 648.0]);
+    assert_eq!(figures[0].figure_type, FigureType::Unknown);
+    assert_eq!(figures[0].label().as_deref(), Some("Figure 4"));
+}
+
+fn captioned(number: &str) -> FigureRegion {
+    FigureRegion {
+        index: 0,
+        rect: [72.0, 400.0, 540.0, 700.0],
+        figure_type: FigureType::Chart,
+        confidence: 0.9,
+        caption: Some(format!("Figure {}: A chart.", number)),
+        number: Some(number.to_string()),
+    }
+}
+
+#[test]
+fn test_figure_index_finds_label_on_any_page() {
+    let index = FigureIndex {
+        modified: 1,
+        figures: vec![
+            IndexedFigure {
+                page: 1,
+                figure: captioned("1"),
+            },
+            IndexedFigure {
+                page: 3,
+                figure: FigureRegion {
+                    caption: None,
+                    number: None,
+                    ..captioned("2")
+                },
+            },
+            IndexedFigure {
+                page: 4,
+                figure: captioned("5"),
+            },
+        ],
+    };
+
+    assert_eq!(index.find("Figure 5").map(|f| f.page), Some(4));
+    assert_eq!(index.find("fig. 1").map(|f| f.page), Some(1));
+    assert!(index.find("Table 5").is_none());
+    assert_eq!(index.captioned().count(), 2);
+}
+
+#[test]
+fn test_render_limits_fit_dpi() {
+    let unlimited = RenderLimits::default();
+    assert_eq!(unlimited.fit_dpi(300, 612.0, 792.0), 300);
+
+    // 1000px over 612pt is 117.6 DPI; the height limit is looser.
+    let limits = RenderLimits {
+        max_width: Some(1000),
+        max_height: Some(2000),
+        ..Default::default()
+    };
+    assert_eq!(limits.fit_dpi(300, 612.0, 792.0), 117);
+    assert_eq!(limits.fit_dpi(100, 612.0, 792.0), 100);
+
+    // A 48 x 36 inch poster
+    let limits = RenderLimits {
+        max_height: Some(1568),
+        ..Default::default()
+    };
+    assert_eq!(limits.fit_dpi(300, 3456.0, 2592.0), 43);
+
+    let limits = RenderLimits {
+        max_width: Some(10),
+        ..Default::default()
+    };
+    assert_eq!(limits.fit_dpi(150, 3456.0, 2592.0), 0);
+}
+
+#[test]
+fn test_render_limits_dpi_for_bytes() {
+    let limits = RenderLimits {
+        max_bytes: Some(1_000_000),
+        ..Default::default()
+    };
+    // A quarter of the bytes needs half the DPI, less the margin.
+    assert_eq!(limits.dpi_for_bytes(300, 4_000_000), 135);
+    // Always lower than the DPI that was too large
+    assert_eq!(limits.dpi_for_bytes(150, 1_000_001), 134);
+    assert_eq!(limits.dpi_for_bytes(1, 2_000_000), 0);
+    assert_eq!(RenderLimits::default().dpi_for_bytes(150, 4_000_000), 150);
+}
+
+/// A one-page PDF with a fractional page size, shapes with anti-aliased
+/// edges and text.
+fn shapes_pdf() -> Vec<u8> {
+    let content = "0.2 0.4 0.8 rg 20.3 30.7 120.25 80.5 re f\n\
+                   1 0 0 RG 1.5 w 10.5 10.25 m 290.75 190.5 l S\n\
+                   BT /F1 14 Tf 40.5 150.25 Td (Region rendering) Tj ET";
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 300.5 200.75] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .bytes(),
+    );
+    pdf
+}
+
+#[test]
+fn test_region_pixmap_matches_cropped_page() {
+    use super::render::{load_page, page_pixmap, region_clip, region_pixmap};
+
+    let path = std::env::temp_dir().join(format!("zotero-render-{}.pdf", std::process::id()));
+    std::fs::write(&path, shapes_pdf()).unwrap();
+    let page = load_page(&path, 0).unwrap();
+    let bounds = page.bounds().unwrap();
+
+    // Regions inside the page, across shape edges and over the page edge
+    let rects = [
+        [0.0, 100.0, 150.25, 200.75],
+        [15.3, 25.1, 145.9, 115.6],
+        [33.33, 140.1, 210.7, 170.9],
+        [200.0, -20.0, 400.0, 120.0],
+    ];
+    for dpi in [72, 150, 217] {
+        let scale = dpi as f32 / 72.0;
+        let full = page_pixmap(&page, 0, dpi).unwrap();
+        let n = full.n() as usize;
+
+        for rect in rects {
+            // Copy the rows of the region out of the page pixmap
+            let clip = region_clip(&bounds, rect);
+            let px_x1 = ((clip.x0 - bounds.x0) * scale) as usize;
+            let px_y1 = ((clip.y0 - bounds.y0) * scale) as usize;
+            let px_x2 = ((clip.x1 - bounds.x0) * scale) as usize;
+            let px_y2 = ((clip.y1 - bounds.y0) * scale) as usize;
+            let mut cropped = Vec::new();
+            for y in px_y1..px_y2 {
+                let start = (y * full.width() as usize + px_x1) * n;
+                cropped.extend_from_slice(&full.samples()[start..start + (px_x2 - px_x1) * n]);
+            }
+
+            let region = region_pixmap(&page, 0, &bounds, &clip, dpi).unwrap();
+            assert_eq!(
+                (region.width() as usize, region.height() as usize),
+                (px_x2 - px_x1, px_y2 - px_y1),
+                "size of {:?} at {} DPI",
+                rect,
+                dpi
+            );
+            assert!(
+                region.samples() == cropped.as_slice(),
+                "samples of {:?} at {} DPI",
+                rect,
+                dpi
+            );
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -5,8 +5,14 @@
 //! This library allows you to:
 //! - Search and list items in your Zotero library
 //! - Retrieve item details and attachments
-//! - Create annotations (highlights, notes) on PDF attachments
+//! - Resolve attachment keys to local file paths (cached, see [`resolver`])
+//! - List annotations on PDF attachments, filtered by type, color or page
+//! - Create, update and delete annotations (highlights, underlines, notes, ink, areas) on PDF attachments
+//! - Create and update notes, standalone or attached to an item
+//! - Export annotations to Markdown notes for Obsidian (see [`export`])
+//! - Search the text of PDF attachments with a local BM25 index (see [`fulltext`])
 //! - Extract text from PDFs with position information (optional `pdf` feature)
+//! - Extract page text as blocks, lines and spans with font name, size and style (optional `pdf` feature)
 //!
 //! ## Quick Start
 //!
@@ -89,7 +95,11 @@
 
 pub mod client;
 pub mod error;
+pub mod export;
+pub mod fulltext;
+pub mod resolver;
 pub mod types;
+pub mod util;
 
 #[cfg(feature = "pdf")]
 pub mod pdf;
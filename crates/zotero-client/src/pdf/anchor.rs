@@ -0,0 +1,430 @@
+//! Text anchoring: locating quoted passages in a PDF.
+//!
+//! MuPDF's native search only matches text within a single page and fails on
+//! passages that wrap across hyphenated line ends. Anchoring instead builds a
+//! normalized character stream over consecutive pages, where whitespace runs
+//! collapse to one space and hyphens at line ends are removed, and matches the
+//! (equally normalized) quote against it. Every matched character keeps its
+//! page and position, so a match can be split into one segment per page.
+
+use mupdf::{Document, TextPageOptions};
+use std::path::Path;
+
+use super::normalize::{is_hyphen, match_keys, normalize_text};
+use super::sort_index;
+use crate::error::{Result, ZoteroClientError};
+
+/// A character on a PDF page with its bounding box.
+///
+/// The rectangle is in PDF/Zotero coordinates (origin bottom-left),
+/// in the format [x1, y1, x2, y2] where y1 < y2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedChar {
+    /// The character.
+    pub ch: char,
+    /// Bounding box of the character.
+    pub rect: [f64; 4],
+}
+
+/// The text of a PDF page as lines of positioned characters, in the order
+/// MuPDF extracts them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageText {
+    /// Zero-based page number.
+    pub page: u32,
+    /// Page height in points, used to measure distances from the top.
+    pub height: f64,
+    /// Lines of characters on the page.
+    pub lines: Vec<Vec<PositionedChar>>,
+}
+
+/// The part of an anchored passage that lies on a single page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorSegment {
+    /// Zero-based page number.
+    pub page: u32,
+    /// The matched text on this page, normalized.
+    pub text: String,
+    /// One rectangle per matched line, in PDF/Zotero coordinates.
+    pub rects: Vec<[f64; 4]>,
+    /// Index of the first matched character among the page's characters.
+    pub offset: usize,
+    /// Distance in points from the top of the page to the first rect.
+    pub top: f64,
+}
+
+impl AnchorSegment {
+    /// The Zotero `sortIndex` placing this segment in reading order.
+    pub fn sort_index(&self) -> String {
+        sort_index(self.page, self.offset, self.top)
+    }
+}
+
+/// A passage located in a PDF, split into per-page segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextAnchor {
+    /// The full matched text, normalized.
+    pub text: String,
+    /// Segments in reading order, one per page the passage touches.
+    pub segments: Vec<AnchorSegment>,
+}
+
+impl TextAnchor {
+    /// Whether the passage crosses a page break.
+    pub fn spans_pages(&self) -> bool {
+        self.segments.len() > 1
+    }
+}
+
+/// Where a character of the normalized stream came from.
+#[derive(Debug, Clone, Copy)]
+struct Origin {
+    page: u32,
+    page_height: f64,
+    line: usize,
+    /// Index of the character among all characters of its page.
+    offset: usize,
+    rect: [f64; 4],
+}
+
+/// A character of the normalized stream. Inserted spaces have no origin.
+#[derive(Debug, Clone, Copy)]
+struct StreamChar {
+    ch: char,
+    origin: Option<Origin>,
+}
+
+/// Append a single space unless the stream is empty or already ends in one.
+fn push_space(stream: &mut Vec<StreamChar>) {
+    if stream.last().is_some_and(|c| c.ch != ' ') {
+        stream.push(StreamChar {
+            ch: ' ',
+            origin: None,
+        });
+    }
+}
+
+/// Build the normalized character stream over consecutive pages.
+///
+/// Lines are joined with a single space, except when a line ends with a
+/// hyphen directly after a letter: the hyphen is dropped and the word is
+/// joined with the next line.
+fn build_stream(pages: &[PageText]) -> Vec<StreamChar> {
+    let mut stream = Vec::new();
+    let mut line_id = 0;
+
+    for page in pages {
+        let mut line_offset = 0;
+        for line in &page.lines {
+            let end = line
+                .iter()
+                .rposition(|c| !c.ch.is_whitespace())
+                .map_or(0, |i| i + 1);
+            let hyphenated =
+                end >= 2 && is_hyphen(line[end - 1].ch) && line[end - 2].ch.is_alphabetic();
+            let content_end = if hyphenated { end - 1 } else { end };
+
+            for (i, c) in line[..content_end].iter().enumerate() {
+                if c.ch.is_whitespace() {
+                    push_space(&mut stream);
+                } else {
+                    stream.push(StreamChar {
+                        ch: c.ch,
+                        origin: Some(Origin {
+                            page: page.page,
+                            page_height: page.height,
+                            line: line_id,
+                            offset: line_offset + i,
+                            rect: c.rect,
+                        }),
+                    });
+                }
+            }
+
+            if !hyphenated {
+                push_space(&mut stream);
+            }
+            line_id += 1;
+            line_offset += line.len();
+        }
+    }
+
+    while stream.last().is_some_and(|c| c.ch == ' ') {
+        stream.pop();
+    }
+    stream
+}
+
+/// Merge the rectangles of consecutive characters on the same line.
+fn line_rects(origins: &[Origin]) -> Vec<[f64; 4]> {
+    let mut rects: Vec<[f64; 4]> = Vec::new();
+    let mut current_line = None;
+
+    for origin in origins {
+        if current_line == Some(origin.line) {
+            if let Some(rect) = rects.last_mut() {
+                rect[0] = rect[0].min(origin.rect[0]);
+                rect[1] = rect[1].min(origin.rect[1]);
+                rect[2] = rect[2].max(origin.rect[2]);
+                rect[3] = rect[3].max(origin.rect[3]);
+            }
+        } else {
+            rects.push(origin.rect);
+            current_line = Some(origin.line);
+        }
+    }
+
+    rects
+}
+
+/// Split a matched stream range into per-page segments.
+fn segments_for(stream: &[StreamChar]) -> Vec<AnchorSegment> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+
+    while start < stream.len() {
+        let Some(page) = stream[start].origin.map(|o| o.page) else {
+            start += 1;
+            continue;
+        };
+
+        // Extend over every character up to the last one from this page
+        let mut end = start;
+        for (i, c) in stream.iter().enumerate().skip(start) {
+            match c.origin {
+                Some(o) if o.page != page => break,
+                Some(_) => end = i,
+                None => {}
+            }
+        }
+
+        let slice = &stream[start..=end];
+        let origins: Vec<Origin> = slice.iter().filter_map(|c| c.origin).collect();
+        let rects = line_rects(&origins);
+        segments.push(AnchorSegment {
+            page,
+            text: slice
+                .iter()
+                .map(|c| c.ch)
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            offset: origins[0].offset,
+            top: origins[0].page_height - rects[0][3],
+            rects,
+        });
+
+        start = end + 1;
+    }
+
+    segments
+}
+
+/// The normalized stream over a run of pages, with its match keys.
+pub(super) struct Haystack {
+    first_page: u32,
+    stream: Vec<StreamChar>,
+    /// Match keys with the index of the stream character each came from.
+    pub(super) keys: Vec<(char, usize)>,
+}
+
+impl Haystack {
+    /// Build the haystack for pages; `None` if there are no pages.
+    pub(super) fn new(pages: &[PageText]) -> Option<Self> {
+        let first_page = pages.first()?.page;
+        let stream = build_stream(pages);
+        let keys = match_keys(stream.iter().map(|c| c.ch));
+        Some(Self {
+            first_page,
+            stream,
+            keys,
+        })
+    }
+
+    /// Whether the character behind a key lies on the first page.
+    pub(super) fn on_first_page(&self, key: usize) -> bool {
+        self.stream[self.keys[key].1]
+            .origin
+            .is_some_and(|o| o.page == self.first_page)
+    }
+
+    /// Build the anchor covering the keys `first..=last`.
+    pub(super) fn anchor(&self, first: usize, last: usize) -> TextAnchor {
+        let matched = &self.stream[self.keys[first].1..=self.keys[last].1];
+        TextAnchor {
+            text: matched
+                .iter()
+                .map(|c| c.ch)
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            segments: segments_for(matched),
+        }
+    }
+}
+
+/// Reduce a quote to the keys used for matching.
+pub(super) fn needle_keys(needle: &str) -> Vec<char> {
+    match_keys(normalize_text(needle).chars())
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect()
+}
+
+/// Locate a passage in already extracted page text.
+///
+/// The match must start on the first page in `pages`; later pages are only
+/// used for passages that continue past the page break.
+pub fn anchor_in_pages(pages: &[PageText], needle: &str) -> Option<TextAnchor> {
+    let hay = Haystack::new(pages)?;
+    let needle = needle_keys(needle);
+
+    if needle.is_empty() || needle.len() > hay.keys.len() {
+        return None;
+    }
+
+    let start = (0..=hay.keys.len() - needle.len()).find(|&start| {
+        hay.on_first_page(start)
+            && hay.keys[start..start + needle.len()]
+                .iter()
+                .zip(&needle)
+                .all(|((k, _), n)| k == n)
+    })?;
+
+    Some(hay.anchor(start, start + needle.len() - 1))
+}
+
+/// Extract the positioned characters of a page from an open document.
+pub(super) fn page_text(doc: &Document, page_num: usize) -> Result<PageText> {
+    let page = doc
+        .load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+
+    let page_bounds = page
+        .bounds()
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
+    let page_height = (page_bounds.y1 - page_bounds.y0) as f64;
+
+    let text_page = page.to_text_page(TextPageOptions::empty()).map_err(|e| {
+        ZoteroClientError::Pdf(format!(
+            "Failed to extract text from page {}: {}",
+            page_num, e
+        ))
+    })?;
+
+    let mut lines = Vec::new();
+    for block in text_page.blocks() {
+        for line in block.lines() {
+            let chars: Vec<PositionedChar> = line
+                .chars()
+                .filter_map(|ch| {
+                    let c = ch.char()?;
+                    let q = ch.quad();
+                    // Transform to PDF/Zotero coords (origin bottom-left)
+                    let x1 = q.ul.x.min(q.ll.x) as f64;
+                    let x2 = q.ur.x.max(q.lr.x) as f64;
+                    let top_y = q.ul.y.min(q.ur.y) as f64;
+                    let bottom_y = q.ll.y.max(q.lr.y) as f64;
+                    Some(PositionedChar {
+                        ch: c,
+                        rect: [x1, page_height - bottom_y, x2, page_height - top_y],
+                    })
+                })
+                .collect();
+
+            if !chars.is_empty() {
+                lines.push(chars);
+            }
+        }
+    }
+
+    Ok(PageText {
+        page: page_num as u32,
+        height: page_height,
+        lines,
+    })
+}
+
+/// Extract the positioned characters of a PDF page.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_num` - Zero-based page number
+pub fn extract_page_text<P: AsRef<Path>>(path: P, page_num: usize) -> Result<PageText> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    page_text(&doc, page_num)
+}
+
+/// Locate a quoted passage that starts on a given page.
+///
+/// Whitespace differences and hyphenated line ends are ignored, and the
+/// passage may continue onto the following pages. Following pages are only
+/// loaded as far as the quote could reach.
+///
+/// Returns `None` if the passage is not found.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `start_page` - Zero-based page number where the passage starts
+/// * `needle` - The quoted text
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::anchor_text;
+///
+/// if let Some(anchor) = anchor_text("/path/to/file.pdf", 2, "a sentence that wraps onto the next page")? {
+///     for segment in &anchor.segments {
+///         println!("page {}: {} rects", segment.page + 1, segment.rects.len());
+///     }
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn anchor_text<P: AsRef<Path>>(
+    path: P,
+    start_page: usize,
+    needle: &str,
+) -> Result<Option<TextAnchor>> {
+    let pages = load_pages(path.as_ref(), start_page, needle)?;
+    Ok(anchor_in_pages(&pages, needle))
+}
+
+/// Load the page where a quote starts plus as many following pages as the
+/// rest of the quote could reach.
+pub(super) fn load_pages(path: &Path, start_page: usize, needle: &str) -> Result<Vec<PageText>> {
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    if start_page >= total_pages {
+        return Err(ZoteroClientError::Pdf(format!(
+            "Page {} is out of range (PDF has {} pages)",
+            start_page + 1,
+            total_pages
+        )));
+    }
+
+    let needle_len = normalize_text(needle).chars().count();
+    let mut pages = vec![page_text(&doc, start_page)?];
+    let mut following_len = 0;
+
+    while following_len < needle_len && start_page + pages.len() < total_pages {
+        let page = page_text(&doc, start_page + pages.len())?;
+        following_len += page.lines.iter().map(Vec::len).sum::<usize>();
+        pages.push(page);
+    }
+
+    Ok(pages)
+}
@@ -0,0 +1,122 @@
+//! Approximate anchoring for quotes that do not match the PDF text exactly.
+//!
+//! Uses Sellers' algorithm: an edit-distance alignment of the quote against
+//! any substring of the page text, which finds the span with the fewest
+//! character insertions, deletions and substitutions.
+
+use std::path::Path;
+
+use super::anchor::{load_pages, needle_keys, Haystack};
+use super::{PageText, TextAnchor};
+use crate::error::Result;
+
+/// The best approximate match of a quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// The matched passage, with the actual PDF text and its rects.
+    pub anchor: TextAnchor,
+    /// Similarity between the quote and the matched text, from 0.0 to 1.0.
+    ///
+    /// Computed as `1 - edit_distance / quote_length` over normalized text,
+    /// so an exact match scores 1.0.
+    pub score: f64,
+}
+
+/// Find the passage most similar to a quote in already extracted page text.
+///
+/// Like [`anchor_in_pages`](super::anchor_in_pages), the match must start on
+/// the first page in `pages`. Returns `None` only if the quote is empty or
+/// there is no text; otherwise the best span is returned whatever its score.
+pub fn fuzzy_anchor_in_pages(pages: &[PageText], needle: &str) -> Option<FuzzyMatch> {
+    let hay = Haystack::new(pages)?;
+    let needle = needle_keys(needle);
+
+    if needle.is_empty() || hay.keys.is_empty() {
+        return None;
+    }
+
+    let m = needle.len();
+
+    // One DP column per text position: cost of aligning the first i quote
+    // characters so that the alignment ends here, and where it started.
+    let mut prev_cost: Vec<usize> = (0..=m).collect();
+    let mut prev_start: Vec<usize> = vec![0; m + 1];
+    let mut cur_cost = vec![0; m + 1];
+    let mut cur_start = vec![0; m + 1];
+
+    // (distance, first key, last key)
+    let mut best: Option<(usize, usize, usize)> = None;
+
+    for (j, &(t, _)) in hay.keys.iter().enumerate() {
+        cur_cost[0] = 0;
+        cur_start[0] = j + 1;
+
+        for i in 1..=m {
+            let substitute = prev_cost[i - 1] + usize::from(needle[i - 1] != t);
+            let skip_quote = cur_cost[i - 1] + 1;
+            let skip_text = prev_cost[i] + 1;
+
+            (cur_cost[i], cur_start[i]) = if substitute <= skip_quote && substitute <= skip_text {
+                (substitute, prev_start[i - 1])
+            } else if skip_quote <= skip_text {
+                (skip_quote, cur_start[i - 1])
+            } else {
+                (skip_text, prev_start[i])
+            };
+        }
+
+        let (distance, start) = (cur_cost[m], cur_start[m]);
+        if start <= j
+            && hay.on_first_page(start)
+            && best.is_none_or(|(best_distance, _, _)| distance < best_distance)
+        {
+            best = Some((distance, start, j));
+            if distance == 0 {
+                break;
+            }
+        }
+
+        std::mem::swap(&mut prev_cost, &mut cur_cost);
+        std::mem::swap(&mut prev_start, &mut cur_start);
+    }
+
+    let (distance, first, last) = best?;
+    Some(FuzzyMatch {
+        anchor: hay.anchor(first, last),
+        score: (1.0 - distance as f64 / m as f64).max(0.0),
+    })
+}
+
+/// Find the passage most similar to a quote that starts on a given page.
+///
+/// Tolerates everything [`anchor_text`](super::anchor_text) does, plus
+/// ligatures, typographic quotes, missing diacritics, and small differences
+/// such as OCR errors or paraphrased words. Check [`FuzzyMatch::score`] before
+/// trusting the result.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `start_page` - Zero-based page number where the passage starts
+/// * `needle` - The quoted text
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::fuzzy_anchor_text;
+///
+/// if let Some(found) = fuzzy_anchor_text("/path/to/file.pdf", 0, "an \"efficient\" method")? {
+///     if found.score >= 0.9 {
+///         println!("Matched '{}' (score {:.2})", found.anchor.text, found.score);
+///     }
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn fuzzy_anchor_text<P: AsRef<Path>>(
+    path: P,
+    start_page: usize,
+    needle: &str,
+) -> Result<Option<FuzzyMatch>> {
+    let pages = load_pages(path.as_ref(), start_page, needle)?;
+    Ok(fuzzy_anchor_in_pages(&pages, needle))
+}
@@ -0,0 +1,363 @@
+//! Outlines inferred from section headings, for PDFs without bookmarks.
+//!
+//! Most preprints have no embedded outline. Their section headings can
+//! still be recognized: they are set in bold or in a font larger than the
+//! body text, and are either numbered ("1 Introduction", "3.2 Method",
+//! "IV. RESULTS") or carry one of the usual section names ("Abstract",
+//! "References"). Numbered headings must follow each other in order, which
+//! rules out numbered lists and table rows set in bold.
+//!
+//! Inferring an outline reads every page, so [`OutlineCache`] keeps the
+//! outline of each file until the file's modification time changes.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use mupdf::Document;
+use regex::Regex;
+
+use super::get_pdf_outline;
+use super::structure::{structured_page, StructuredLine, StructuredPage};
+use crate::error::{Result, ZoteroClientError};
+use crate::types::{OutlineItem, PdfOutline};
+use crate::util::FileCache;
+
+/// Points by which a heading is larger than the body text, if it is not bold.
+const MIN_SIZE_INCREASE: f64 = 1.0;
+
+/// Longest heading, in characters.
+const MAX_HEADING_LENGTH: usize = 100;
+
+/// Deepest numbered heading level ("1.2.3").
+const MAX_DEPTH: usize = 3;
+
+/// Unnumbered section names, lowercase.
+const SECTION_NAMES: &[&str] = &[
+    "abstract",
+    "introduction",
+    "background",
+    "related work",
+    "preliminaries",
+    "method",
+    "methods",
+    "methodology",
+    "materials and methods",
+    "experiments",
+    "experimental setup",
+    "evaluation",
+    "results",
+    "results and discussion",
+    "discussion",
+    "limitations",
+    "conclusion",
+    "conclusions",
+    "future work",
+    "acknowledgments",
+    "acknowledgements",
+    "acknowledgment",
+    "acknowledgement",
+    "references",
+    "bibliography",
+    "appendix",
+    "supplementary material",
+];
+
+/// "1 Introduction", "3.2. Method"
+static NUMBERED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{1,2}(?:\.\d{1,2})*)\.?\s+(\p{L}.*)$").unwrap());
+
+/// "IV. RESULTS", in capitals
+static ROMAN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([IVX]{1,5})\.\s+(\p{Lu}.*)$").unwrap());
+
+/// "Appendix A", "A Proofs", "B.1 Datasets"
+static APPENDIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:Appendix\s+)?([A-H](?:\.\d{1,2})*)\.?\s+(\p{Lu}.*)$").unwrap()
+});
+
+/// Table of contents entries end with a page number.
+static TOC_ENTRY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:\s|\.)\d+$").unwrap());
+
+/// A recognized heading.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Heading {
+    pub(super) title: String,
+    pub(super) level: usize,
+    pub(super) page: u32,
+    /// Distance in points from the top of the page to the heading.
+    pub(super) top: f64,
+}
+
+/// How a heading is numbered, to check that numbered headings are in order.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum Numbering {
+    Arabic(Vec<u32>),
+    Appendix(Vec<u32>),
+}
+
+/// The most common font size of the text, weighted by characters.
+fn body_size(pages: &[StructuredPage]) -> f64 {
+    let mut counts: Vec<(i64, usize)> = Vec::new();
+    let spans = pages
+        .iter()
+        .flat_map(|page| &page.blocks)
+        .flat_map(|block| &block.lines)
+        .flat_map(|line| &line.spans);
+    for span in spans {
+        // Half-point buckets
+        let size = (span.size * 2.0).round() as i64;
+        let chars = span.text.chars().filter(|c| !c.is_whitespace()).count();
+        match counts.iter_mut().find(|(s, _)| *s == size) {
+            Some((_, count)) => *count += chars,
+            None => counts.push((size, chars)),
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .map_or(0.0, |(size, _)| size as f64 / 2.0)
+}
+
+/// The text at the start of a line set in heading style (bold or larger than the body).
+///
+/// This is the whole line for a heading on its own line, and the heading
+/// part of a run-in heading ("3.2 Method. We train ...").
+fn styled_prefix(line: &StructuredLine, body_size: f64) -> String {
+    let mut prefix = String::new();
+    for span in &line.spans {
+        let styled = span.bold || span.size >= body_size + MIN_SIZE_INCREASE;
+        if !styled && !span.text.trim().is_empty() {
+            break;
+        }
+        prefix.push_str(&span.text);
+    }
+    let prefix = prefix.split_whitespace().collect::<Vec<_>>().join(" ");
+    prefix.trim_end_matches(['.', ':']).to_string()
+}
+
+fn roman_value(numeral: &str) -> u32 {
+    let value = |c| match c {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        _ => 0,
+    };
+    let digits: Vec<u32> = numeral.chars().map(value).collect();
+    digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| match digits.get(i + 1) {
+            Some(&next) if next > d => -(d as i64),
+            _ => d as i64,
+        })
+        .sum::<i64>() as u32
+}
+
+/// Classify a heading candidate as `(numbering, level)`.
+///
+/// Returns `None` for text that is not a heading.
+fn classify(text: &str, styled: bool) -> Option<(Option<Numbering>, usize)> {
+    let length = text.chars().count();
+    if !(2..=MAX_HEADING_LENGTH).contains(&length) || TOC_ENTRY.is_match(text) {
+        return None;
+    }
+
+    if let Some(caps) = NUMBERED.captures(text) {
+        let numbers: Vec<u32> = caps[1].split('.').filter_map(|n| n.parse().ok()).collect();
+        let title = &caps[2];
+        let words = title.split_whitespace().count();
+        if styled && numbers.len() <= MAX_DEPTH && numbers[0] > 0 && words <= 12 {
+            return Some((Some(Numbering::Arabic(numbers.clone())), numbers.len()));
+        }
+        return None;
+    }
+
+    if let Some(caps) = ROMAN.captures(text) {
+        // IEEE papers set their section headings in small capitals, which
+        // are extracted as upper case, rather than in bold
+        if caps[2].chars().all(|c| !c.is_lowercase()) {
+            return Some((Some(Numbering::Arabic(vec![roman_value(&caps[1])])), 1));
+        }
+        return None;
+    }
+
+    let lower = text.to_lowercase();
+    let name = lower.trim_end_matches(|c: char| !c.is_alphanumeric());
+    if styled && SECTION_NAMES.contains(&name) {
+        return Some((None, 1));
+    }
+
+    if styled {
+        if let Some(caps) = APPENDIX.captures(text) {
+            let mut parts = caps[1].split('.');
+            let letter = parts.next()?.chars().next()? as u32 - 'A' as u32 + 1;
+            let mut numbers = vec![letter];
+            numbers.extend(parts.filter_map(|n| n.parse::<u32>().ok()));
+            let level = numbers.len();
+            if level <= MAX_DEPTH && text.split_whitespace().count() <= 12 {
+                return Some((Some(Numbering::Appendix(numbers)), level));
+            }
+        }
+    }
+
+    None
+}
+
+/// Find the section headings in a document's structured text.
+pub(super) fn find_headings(pages: &[StructuredPage]) -> Vec<Heading> {
+    let body_size = body_size(pages);
+    let mut headings: Vec<Heading> = Vec::new();
+    let mut last_numbering: Option<Numbering> = None;
+
+    for page in pages {
+        for line in page.blocks.iter().flat_map(|block| &block.lines) {
+            let prefix = styled_prefix(line, body_size);
+            let text = line.text().split_whitespace().collect::<Vec<_>>().join(" ");
+
+            let candidate = if prefix.is_empty() {
+                classify(&text, false)
+                    .filter(|(numbering, _)| numbering.is_some())
+                    .map(|c| (text.clone(), c))
+            } else {
+                classify(&prefix, true).map(|c| (prefix.clone(), c))
+            };
+            let Some((title, (numbering, level))) = candidate else {
+                continue;
+            };
+
+            // A lettered heading before any numbered one is more likely a
+            // title ("A Study of ...") than an appendix
+            if matches!(numbering, Some(Numbering::Appendix(_)))
+                && last_numbering.is_none()
+                && !title.starts_with("Appendix")
+            {
+                continue;
+            }
+
+            if let Some(numbering) = numbering {
+                // Numbered headings follow each other; anything else is a
+                // list item, a table row or a running header repeating one
+                let in_order = match (&last_numbering, &numbering) {
+                    (None, _) => true,
+                    (Some(last), next) => next > last,
+                };
+                if !in_order {
+                    continue;
+                }
+                last_numbering = Some(numbering);
+            } else if headings
+                .iter()
+                .any(|h| h.title.eq_ignore_ascii_case(&title))
+            {
+                continue;
+            }
+
+            headings.push(Heading {
+                title,
+                level,
+                page: page.page,
+                top: page.height - line.bbox[3],
+            });
+        }
+    }
+
+    headings
+}
+
+/// Nest headings into outline items by level.
+fn build_tree(headings: &[Heading]) -> Vec<OutlineItem> {
+    fn insert(items: &mut Vec<OutlineItem>, heading: &Heading, depth: usize) {
+        match items.last_mut() {
+            Some(parent) if depth < heading.level => {
+                insert(&mut parent.children, heading, depth + 1)
+            }
+            _ => items.push(OutlineItem {
+                title: heading.title.clone(),
+                page: Some(heading.page),
+                top: Some(heading.top),
+                children: Vec::new(),
+            }),
+        }
+    }
+
+    let mut items = Vec::new();
+    for heading in headings {
+        insert(&mut items, heading, 1);
+    }
+    items
+}
+
+/// Infer an outline from the section headings of a document's structured text.
+pub(super) fn infer_outline_from_pages(pages: &[StructuredPage], total_pages: usize) -> PdfOutline {
+    let items = build_tree(&find_headings(pages));
+    PdfOutline {
+        has_outline: !items.is_empty(),
+        total_pages,
+        items,
+        inferred: true,
+    }
+}
+
+/// Infer an outline from the section headings of a PDF.
+///
+/// Headings are recognized by font size and weight and by numbering
+/// patterns. The outline is flagged as `inferred`; `has_outline` is false
+/// if no headings were found.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::infer_outline;
+///
+/// let outline = infer_outline("/path/to/preprint.pdf")?;
+/// for item in &outline.items {
+///     println!("{} starts at page {:?}", item.title, item.page);
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn infer_outline<P: AsRef<Path>>(path: P) -> Result<PdfOutline> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    let pages = (0..total_pages)
+        .map(|page| structured_page(&doc, page))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(infer_outline_from_pages(&pages, total_pages))
+}
+
+/// Get the PDF outline, or infer one from its headings if it has none.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+pub fn get_or_infer_outline<P: AsRef<Path>>(path: P) -> Result<PdfOutline> {
+    let outline = get_pdf_outline(&path)?;
+    if outline.has_outline {
+        return Ok(outline);
+    }
+    infer_outline(path)
+}
+
+/// A cache of [`get_or_infer_outline`] results by file path, read again
+/// when the file changes.
+pub type OutlineCache = FileCache<PdfOutline>;
+
+impl FileCache<PdfOutline> {
+    /// The outline of a PDF, read or inferred on first use and after the file changes.
+    pub fn get(&self, path: impl AsRef<Path>) -> Result<Arc<PdfOutline>> {
+        self.get_or_insert_with(path, |path| get_or_infer_outline(path))
+    }
+}
@@ -0,0 +1,271 @@
+//! Page labels: the page numbers printed in a PDF, as opposed to page indices.
+//!
+//! A document may number its front matter "i, ii, iii" and start the body at
+//! "1", or carry the page numbers of the journal issue it was taken from. The
+//! labels are stored in the catalog's `/PageLabels` number tree as ranges,
+//! each starting at a page index with a numbering style, prefix and start
+//! number.
+
+use mupdf::pdf::{PdfDocument, PdfObject};
+use mupdf::Document;
+use std::path::Path;
+
+use crate::error::{Result, ZoteroClientError};
+
+/// Maximum depth of the `/PageLabels` number tree that is followed.
+const MAX_TREE_DEPTH: usize = 32;
+
+/// How the numeric part of a page label is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageLabelStyle {
+    /// Decimal numbers: 1, 2, 3.
+    Decimal,
+    /// Uppercase Roman numerals: I, II, III.
+    UpperRoman,
+    /// Lowercase Roman numerals: i, ii, iii.
+    LowerRoman,
+    /// Uppercase letters: A to Z, then AA to ZZ, and so on.
+    UpperLetters,
+    /// Lowercase letters: a to z, then aa to zz, and so on.
+    LowerLetters,
+}
+
+impl PageLabelStyle {
+    /// Parse the `/S` name of a page label dictionary.
+    fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"D" => Some(Self::Decimal),
+            b"R" => Some(Self::UpperRoman),
+            b"r" => Some(Self::LowerRoman),
+            b"A" => Some(Self::UpperLetters),
+            b"a" => Some(Self::LowerLetters),
+            _ => None,
+        }
+    }
+
+    /// Write a number in this style.
+    pub fn format(self, number: u32) -> String {
+        match self {
+            Self::Decimal => number.to_string(),
+            Self::UpperRoman => roman(number),
+            Self::LowerRoman => roman(number).to_lowercase(),
+            Self::UpperLetters => letters(number),
+            Self::LowerLetters => letters(number).to_lowercase(),
+        }
+    }
+}
+
+/// A run of pages numbered the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageLabelRange {
+    /// Zero-based index of the first page in the range.
+    pub start_page: u32,
+    /// Numbering style; `None` means labels consist of the prefix only.
+    pub style: Option<PageLabelStyle>,
+    /// Text placed before the number.
+    pub prefix: String,
+    /// Number of the first page in the range.
+    pub first_number: u32,
+}
+
+impl PageLabelRange {
+    /// The label of a page in this range.
+    fn label(&self, page_index: u32) -> String {
+        let number = self.first_number + (page_index - self.start_page);
+        match self.style {
+            Some(style) => format!("{}{}", self.prefix, style.format(number)),
+            None => self.prefix.clone(),
+        }
+    }
+}
+
+/// The label of a page given the document's label ranges.
+///
+/// Pages not covered by any range (including every page of a document
+/// without page labels) are labelled with their one-based page number.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::pdf::{label_for_page, PageLabelRange, PageLabelStyle};
+///
+/// let ranges = [
+///     PageLabelRange { start_page: 0, style: Some(PageLabelStyle::LowerRoman), prefix: String::new(), first_number: 1 },
+///     PageLabelRange { start_page: 4, style: Some(PageLabelStyle::Decimal), prefix: String::new(), first_number: 1 },
+/// ];
+/// assert_eq!(label_for_page(&ranges, 2), "iii");
+/// assert_eq!(label_for_page(&ranges, 4), "1");
+/// ```
+pub fn label_for_page(ranges: &[PageLabelRange], page_index: u32) -> String {
+    ranges
+        .iter()
+        .filter(|range| range.start_page <= page_index)
+        .max_by_key(|range| range.start_page)
+        .map_or_else(
+            || (page_index + 1).to_string(),
+            |range| range.label(page_index),
+        )
+}
+
+/// Read the page label ranges of a PDF, sorted by first page.
+///
+/// Returns an empty list for documents without page labels and for files
+/// that are not PDFs.
+pub fn page_label_ranges<P: AsRef<Path>>(path: P) -> Result<Vec<PageLabelRange>> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+    let Ok(doc) = PdfDocument::try_from(doc) else {
+        return Ok(Vec::new());
+    };
+
+    let read = || -> std::result::Result<Vec<PageLabelRange>, mupdf::Error> {
+        let mut ranges = Vec::new();
+        if let Some(tree) = doc.catalog()?.get_dict("PageLabels")? {
+            collect_ranges(&tree, 0, &mut ranges)?;
+        }
+        Ok(ranges)
+    };
+    let mut ranges =
+        read().map_err(|e| ZoteroClientError::Pdf(format!("Failed to read page labels: {}", e)))?;
+
+    ranges.sort_by_key(|range| range.start_page);
+    Ok(ranges)
+}
+
+/// Read the label of a single page.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_index` - Zero-based page number
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::page_label;
+///
+/// // "xii" for a preface, "215" for the first page of a journal article
+/// let label = page_label("/path/to/file.pdf", 0)?;
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn page_label<P: AsRef<Path>>(path: P, page_index: u32) -> Result<String> {
+    Ok(label_for_page(&page_label_ranges(path)?, page_index))
+}
+
+/// Walk a `/PageLabels` number tree node, collecting its ranges.
+fn collect_ranges(
+    node: &PdfObject,
+    depth: usize,
+    ranges: &mut Vec<PageLabelRange>,
+) -> std::result::Result<(), mupdf::Error> {
+    if depth > MAX_TREE_DEPTH {
+        return Ok(());
+    }
+
+    if let Some(nums) = node.get_dict("Nums")? {
+        for i in (0..nums.len()? as i32).step_by(2) {
+            let (Some(start), Some(dict)) = (nums.get_array(i)?, nums.get_array(i + 1)?) else {
+                continue;
+            };
+            if let Some(range) = parse_range(&start, &dict)? {
+                ranges.push(range);
+            }
+        }
+    }
+
+    if let Some(kids) = node.get_dict("Kids")? {
+        for i in 0..kids.len()? as i32 {
+            if let Some(kid) = kids.get_array(i)? {
+                collect_ranges(&kid, depth + 1, ranges)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse one entry of a `/Nums` array: a page index and a label dictionary.
+fn parse_range(
+    start: &PdfObject,
+    dict: &PdfObject,
+) -> std::result::Result<Option<PageLabelRange>, mupdf::Error> {
+    if !start.is_int()? || !dict.is_dict()? {
+        return Ok(None);
+    }
+
+    let style = match dict.get_dict("S")? {
+        Some(name) => PageLabelStyle::from_name(name.as_name()?),
+        None => None,
+    };
+    let prefix = match dict.get_dict("P")? {
+        Some(prefix) => decode_text_string(prefix.as_bytes()?),
+        None => String::new(),
+    };
+    let first_number = match dict.get_dict("St")? {
+        Some(number) => number.as_int()?.max(1) as u32,
+        None => 1,
+    };
+
+    Ok(Some(PageLabelRange {
+        start_page: start.as_int()?.max(0) as u32,
+        style,
+        prefix,
+        first_number,
+    }))
+}
+
+/// Decode a PDF text string: UTF-16BE with a byte order mark, UTF-8 with a
+/// byte order mark, or otherwise PDFDocEncoding (treated as Latin-1).
+pub(super) fn decode_text_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8_lossy(utf8).into_owned()
+    } else {
+        bytes.iter().map(|&b| char::from(b)).collect()
+    }
+}
+
+/// Write a number as uppercase Roman numerals.
+fn roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            out.push_str(numeral);
+            number -= value;
+        }
+    }
+    out
+}
+
+/// Write a number as uppercase letters: A to Z, then AA to ZZ, and so on.
+fn letters(number: u32) -> String {
+    if number == 0 {
+        return String::new();
+    }
+    let letter = char::from(b'A' + ((number - 1) % 26) as u8);
+    letter.to_string().repeat(((number - 1) / 26 + 1) as usize)
+}
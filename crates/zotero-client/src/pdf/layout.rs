@@ -0,0 +1,287 @@
+//! Reading order for multi-column pages.
+//!
+//! MuPDF returns text blocks in the order they appear in the content stream,
+//! which interleaves the columns of two-column papers and mixes running
+//! headers, footers and page numbers into the body text. [`PageLayout`]
+//! detects the column gutters of a page from the block positions and orders
+//! the blocks column by column, with blocks that span the gutters (titles,
+//! abstracts, wide figures) separating the column sections.
+
+use std::path::Path;
+
+use mupdf::{Document, TextBlockType, TextPageOptions};
+
+use crate::error::{Result, ZoteroClientError};
+
+/// Narrowest horizontal gap between blocks that counts as a column gutter, in points.
+const MIN_GUTTER: f64 = 6.0;
+
+/// Widest block, as a fraction of the text width, that can sit in a column.
+const MAX_COLUMN_WIDTH: f64 = 0.6;
+
+/// Least combined height of the blocks on either side of a gutter, as a
+/// fraction of the page height, so that a few short blocks side by side are
+/// not taken for columns.
+const MIN_COLUMN_HEIGHT: f64 = 0.2;
+
+/// Height of the top and bottom page margins where running headers, footers
+/// and page numbers are looked for, as a fraction of the page height.
+const MARGIN: f64 = 0.08;
+
+/// Longest block in a margin that is taken for running text, in characters.
+const MAX_RUNNING_TEXT: usize = 120;
+
+/// How [`extract_text_in_reading_order`] extracts a page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutOptions {
+    /// Leave out running headers, footers and page numbers.
+    pub strip_headers_footers: bool,
+}
+
+/// A block of text lines on a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutBlock {
+    /// Bounding box in PDF/Zotero coordinates (origin bottom-left).
+    pub rect: [f64; 4],
+    /// Lines of text, top to bottom.
+    pub lines: Vec<String>,
+}
+
+impl LayoutBlock {
+    fn top(&self) -> f64 {
+        self.rect[3]
+    }
+
+    fn bottom(&self) -> f64 {
+        self.rect[1]
+    }
+
+    fn width(&self) -> f64 {
+        self.rect[2] - self.rect[0]
+    }
+
+    fn height(&self) -> f64 {
+        self.rect[3] - self.rect[1]
+    }
+}
+
+/// The text blocks of a page with the page size.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageLayout {
+    /// Zero-based page number.
+    pub page: u32,
+    /// Page width in points.
+    pub width: f64,
+    /// Page height in points.
+    pub height: f64,
+    /// Text blocks in the order MuPDF extracts them.
+    pub blocks: Vec<LayoutBlock>,
+}
+
+impl PageLayout {
+    /// The column gutters of the page as `(left, right)` x ranges, left to right.
+    ///
+    /// A gutter is a vertical strip at least 6pt wide that no column-width
+    /// block crosses, with column text on both sides. Blocks wider than 60%
+    /// of the text width are ignored, since titles and wide figures cross
+    /// the gutters.
+    pub fn gutters(&self) -> Vec<(f64, f64)> {
+        let left = self
+            .blocks
+            .iter()
+            .map(|b| b.rect[0])
+            .fold(f64::MAX, f64::min);
+        let right = self
+            .blocks
+            .iter()
+            .map(|b| b.rect[2])
+            .fold(f64::MIN, f64::max);
+        let max_width = (right - left) * MAX_COLUMN_WIDTH;
+
+        let mut narrow: Vec<&LayoutBlock> = self
+            .blocks
+            .iter()
+            .filter(|b| b.width() <= max_width)
+            .collect();
+        narrow.sort_by(|a, b| a.rect[0].total_cmp(&b.rect[0]));
+
+        let mut gutters = Vec::new();
+        let mut covered: Option<f64> = None;
+        for block in &narrow {
+            if let Some(edge) = covered {
+                if block.rect[0] - edge >= MIN_GUTTER {
+                    gutters.push((edge, block.rect[0]));
+                }
+            }
+            covered = Some(covered.map_or(block.rect[2], |edge| edge.max(block.rect[2])));
+        }
+
+        let min_height = self.height * MIN_COLUMN_HEIGHT;
+        gutters.retain(|&(start, end)| {
+            let (mut left, mut right) = (0.0, 0.0);
+            for block in &narrow {
+                if block.rect[2] <= start {
+                    left += block.height();
+                } else if block.rect[0] >= end {
+                    right += block.height();
+                }
+            }
+            left >= min_height && right >= min_height
+        });
+        gutters
+    }
+
+    /// Whether a block is a running header, footer or page number.
+    ///
+    /// Running text is short and lies entirely within the top or bottom
+    /// 8% of the page.
+    pub fn is_running_text(&self, block: &LayoutBlock) -> bool {
+        let margin = self.height * MARGIN;
+        let in_margin = block.bottom() >= self.height - margin || block.top() <= margin;
+        let length: usize = block.lines.iter().map(|l| l.trim().chars().count()).sum();
+        in_margin && block.lines.len() <= 2 && length <= MAX_RUNNING_TEXT
+    }
+
+    /// The blocks in reading order.
+    ///
+    /// Blocks between two gutter-spanning blocks are read column by column,
+    /// each column top to bottom. A page without gutters is read top to
+    /// bottom, left to right.
+    pub fn reading_order(&self) -> Vec<&LayoutBlock> {
+        self.reading_order_indices()
+            .into_iter()
+            .map(|index| &self.blocks[index])
+            .collect()
+    }
+
+    /// Indices into `blocks` in reading order.
+    pub(super) fn reading_order_indices(&self) -> Vec<usize> {
+        let gutters = self.gutters();
+
+        let mut indices: Vec<usize> = (0..self.blocks.len()).collect();
+        indices.sort_by(|&a, &b| {
+            let (a, b) = (&self.blocks[a], &self.blocks[b]);
+            b.top()
+                .total_cmp(&a.top())
+                .then(a.rect[0].total_cmp(&b.rect[0]))
+        });
+
+        let mut ordered = Vec::with_capacity(indices.len());
+        let mut columns: Vec<Vec<usize>> = vec![Vec::new(); gutters.len() + 1];
+        for index in indices {
+            let block = &self.blocks[index];
+            let spans_gutter = gutters
+                .iter()
+                .any(|&(start, end)| block.rect[0] < end && block.rect[2] > start);
+            if spans_gutter {
+                ordered.extend(columns.iter_mut().flat_map(std::mem::take));
+                ordered.push(index);
+            } else {
+                let center = (block.rect[0] + block.rect[2]) / 2.0;
+                let column = gutters.iter().filter(|g| g.1 <= center).count();
+                columns[column].push(index);
+            }
+        }
+        ordered.extend(columns.into_iter().flatten());
+        ordered
+    }
+
+    /// The text of the page in reading order, with blocks separated by blank lines.
+    pub fn text(&self, options: LayoutOptions) -> String {
+        self.reading_order()
+            .into_iter()
+            .filter(|block| !(options.strip_headers_footers && self.is_running_text(block)))
+            .map(|block| block.lines.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Read the text blocks of a page.
+pub(super) fn page_layout(doc: &Document, page_num: usize) -> Result<PageLayout> {
+    let page = doc
+        .load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+
+    let bounds = page
+        .bounds()
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
+    let width = (bounds.x1 - bounds.x0) as f64;
+    let height = (bounds.y1 - bounds.y0) as f64;
+
+    let text_page = page.to_text_page(TextPageOptions::empty()).map_err(|e| {
+        ZoteroClientError::Pdf(format!(
+            "Failed to extract text from page {}: {}",
+            page_num, e
+        ))
+    })?;
+
+    let mut blocks = Vec::new();
+    for block in text_page.blocks() {
+        if block.r#type() != TextBlockType::Text {
+            continue;
+        }
+        let lines: Vec<String> = block
+            .lines()
+            .map(|line| line.chars().filter_map(|ch| ch.char()).collect::<String>())
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+
+        // Transform to PDF/Zotero coords (origin bottom-left)
+        let r = block.bounds();
+        blocks.push(LayoutBlock {
+            rect: [
+                r.x0 as f64,
+                height - r.y1 as f64,
+                r.x1 as f64,
+                height - r.y0 as f64,
+            ],
+            lines,
+        });
+    }
+
+    Ok(PageLayout {
+        page: page_num as u32,
+        width,
+        height,
+        blocks,
+    })
+}
+
+/// Extract text from a page in reading order.
+///
+/// Unlike [`extract_text`](super::extract_text), which keeps MuPDF's block
+/// order, the columns of multi-column pages are read one after the other.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_num` - Zero-based page number
+/// * `options` - Whether to leave out running headers, footers and page numbers
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::{extract_text_in_reading_order, LayoutOptions};
+///
+/// let options = LayoutOptions { strip_headers_footers: true };
+/// let text = extract_text_in_reading_order("/path/to/file.pdf", 0, options)?;
+/// println!("{}", text);
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn extract_text_in_reading_order<P: AsRef<Path>>(
+    path: P,
+    page_num: usize,
+    options: LayoutOptions,
+) -> Result<String> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    Ok(page_layout(&doc, page_num)?.text(options))
+}
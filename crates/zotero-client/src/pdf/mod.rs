@@ -7,6 +7,37 @@
 //! Uses the same underlying MuPDF library as PyMuPDF, ensuring identical
 //! text extraction and positioning behavior.
 //!
+//! Quoted passages that wrap across lines or pages can be located with
+//! [`anchor_text`], which tolerates whitespace differences and hyphenated
+//! line ends, or approximately with [`fuzzy_anchor_text`], which also scores
+//! how closely the PDF text matches the quote.
+//!
+//! A document can be searched with [`TextMatcher`] and [`search_pages`], which
+//! support case-sensitive, whole-word and regular expression queries and
+//! report each match with a snippet and its rectangles.
+//!
+//! Multi-column pages are read column by column with
+//! [`extract_text_in_reading_order`], which can also leave out running
+//! headers, footers and page numbers.
+//!
+//! For PDFs without bookmarks, [`infer_outline`] builds an outline from the
+//! section headings, recognized by font and numbering.
+//! [`extract_section_text`] reads a section from its heading to the next
+//! heading rather than as whole pages.
+//!
+//! [`extract_references`] parses the reference list into authors, title,
+//! year, venue and DOI; [`parse_reference`] parses a single entry and
+//! [`CitationMarker`] finds the entries an in-text citation refers to.
+//!
+//! [`extract_structured_text`] returns a page as blocks, lines and spans with
+//! font name, size and style, for finding headings, code and equations.
+//!
+//! [`extract_table`] reads the rows and columns of a table from the text
+//! positions and ruling lines of a page, as Markdown or CSV.
+//!
+//! Annotations are placed in Zotero's reading order with [`sort_index`] and
+//! labelled with the printed page number from [`page_label`].
+//!
 //! Requires the `pdf` feature to be enabled.
 
 use crate::error::{Result, ZoteroClientError};
@@ -15,6 +46,37 @@ use crate::types::{OutlineItem, PdfOutline, TextFragment};
 use mupdf::{Document, Outline, Quad, TextPageOptions};
 use std::path::Path;
 
+mod anchor;
+mod fuzzy;
+mod headings;
+mod labels;
+mod layout;
+mod normalize;
+mod position;
+mod references;
+mod search;
+mod sections;
+mod structure;
+mod tables;
+
+pub use anchor::{
+    anchor_in_pages, anchor_text, extract_page_text, AnchorSegment, PageText, PositionedChar,
+    TextAnchor,
+};
+pub use fuzzy::{fuzzy_anchor_in_pages, fuzzy_anchor_text, FuzzyMatch};
+pub use headings::{get_or_infer_outline, infer_outline, OutlineCache};
+pub use labels::{label_for_page, page_label, page_label_ranges, PageLabelRange, PageLabelStyle};
+pub use layout::{extract_text_in_reading_order, LayoutBlock, LayoutOptions, PageLayout};
+pub use normalize::normalize_text;
+pub use position::sort_index;
+pub use references::{extract_references, parse_reference, CitationMarker, Reference};
+pub use search::{search_pages, SearchOptions, TextMatch, TextMatcher};
+pub use sections::{extract_section_text, SectionPage};
+pub use structure::{
+    extract_structured_text, StructuredBlock, StructuredLine, StructuredPage, StructuredSpan,
+};
+pub use tables::{extract_table, Table};
+
 /// A quad (4-point polygon) representing text position.
 /// This is more accurate than rectangles for rotated or skewed text.
 #[derive(Debug, Clone, PartialEq)]
@@ -371,11 +433,23 @@ pub fn extract_text<P: AsRef<Path>>(path: P, page_num: usize) -> Result<String>
     })
 }
 
+/// The smallest rectangle [x1, y1, x2, y2] containing both `a` and `b`.
+pub(crate) fn union(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+    [
+        a[0].min(b[0]),
+        a[1].min(b[1]),
+        a[2].max(b[2]),
+        a[3].max(b[3]),
+    ]
+}
+
 /// Convert mupdf Outline to our OutlineItem type.
 fn convert_outline(outline: &Outline) -> OutlineItem {
     OutlineItem {
         title: outline.title.clone(),
         page: outline.page,
+        // Destinations without a position have no (or a zero) y
+        top: (outline.y.is_finite() && outline.y > 0.0).then_some(outline.y as f64),
         children: outline.down.iter().map(convert_outline).collect(),
     }
 }
@@ -425,6 +499,7 @@ pub fn get_pdf_outline<P: AsRef<Path>>(path: P) -> Result<PdfOutline> {
         has_outline,
         total_pages,
         items,
+        inferred: false,
     })
 }
 
@@ -501,7 +576,8 @@ pub fn resolve_section_to_pages(outline: &PdfOutline, section: &str) -> Result<(
     match find_outline_item(&outline.items, section, outline.total_pages) {
         Some((item, end_page)) => {
             let start_page = item.page.map(|p| p as usize).unwrap_or(0);
-            Ok((start_page, end_page))
+            // The next section may start on the same page
+            Ok((start_page, end_page.max(start_page + 1)))
         }
         None => {
             // Build a list of available sections for the error message
@@ -559,241 +635,4 @@ pub fn resolve_sections_to_pages(outline: &PdfOutline, sections: &str) -> Result
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_text_quad_to_rect() {
-        let quad = TextQuad {
-            ul: (10.0, 20.0),
-            ur: (50.0, 20.0),
-            ll: (10.0, 35.0),
-            lr: (50.0, 35.0),
-        };
-
-        let rect = quad.to_rect();
-        assert_eq!(rect[0], 10.0); // x1
-        assert_eq!(rect[1], 20.0); // y1
-        assert_eq!(rect[2], 50.0); // x2
-        assert_eq!(rect[3], 35.0); // y2
-    }
-
-    #[test]
-    fn test_text_fragment_creation() {
-        let frag = TextFragment {
-            text: "Hello".to_string(),
-            page: 0,
-            rect: [10.0, 20.0, 50.0, 35.0],
-        };
-
-        assert_eq!(frag.text, "Hello");
-        assert_eq!(frag.page, 0);
-        assert_eq!(frag.rect[0], 10.0); // x1
-        assert_eq!(frag.rect[1], 20.0); // y1
-        assert_eq!(frag.rect[2], 50.0); // x2
-        assert_eq!(frag.rect[3], 35.0); // y2
-    }
-
-    #[test]
-    fn test_outline_item_creation() {
-        let item = OutlineItem {
-            title: "Introduction".to_string(),
-            page: Some(0),
-            children: vec![OutlineItem {
-                title: "Background".to_string(),
-                page: Some(2),
-                children: vec![],
-            }],
-        };
-
-        assert_eq!(item.title, "Introduction");
-        assert_eq!(item.page, Some(0));
-        assert_eq!(item.children.len(), 1);
-        assert_eq!(item.children[0].title, "Background");
-    }
-
-    #[test]
-    fn test_resolve_section_exact_match() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![
-                OutlineItem {
-                    title: "Introduction".to_string(),
-                    page: Some(0),
-                    children: vec![],
-                },
-                OutlineItem {
-                    title: "Methods".to_string(),
-                    page: Some(5),
-                    children: vec![],
-                },
-                OutlineItem {
-                    title: "Results".to_string(),
-                    page: Some(10),
-                    children: vec![],
-                },
-            ],
-        };
-
-        let (start, end) = resolve_section_to_pages(&outline, "Methods").unwrap();
-        assert_eq!(start, 5);
-        assert_eq!(end, 10); // Next section starts at 10
-    }
-
-    #[test]
-    fn test_resolve_section_case_insensitive() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![OutlineItem {
-                title: "Introduction".to_string(),
-                page: Some(0),
-                children: vec![],
-            }],
-        };
-
-        let (start, end) = resolve_section_to_pages(&outline, "introduction").unwrap();
-        assert_eq!(start, 0);
-        assert_eq!(end, 20); // Last section goes to end
-    }
-
-    #[test]
-    fn test_resolve_section_partial_match() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![OutlineItem {
-                title: "1. Introduction and Background".to_string(),
-                page: Some(0),
-                children: vec![],
-            }],
-        };
-
-        let (start, end) = resolve_section_to_pages(&outline, "Introduction").unwrap();
-        assert_eq!(start, 0);
-        assert_eq!(end, 20);
-    }
-
-    #[test]
-    fn test_resolve_section_not_found() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![OutlineItem {
-                title: "Introduction".to_string(),
-                page: Some(0),
-                children: vec![],
-            }],
-        };
-
-        let result = resolve_section_to_pages(&outline, "Nonexistent");
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("not found"));
-        assert!(err.contains("Introduction")); // Available sections listed
-    }
-
-    #[test]
-    fn test_resolve_section_no_outline() {
-        let outline = PdfOutline {
-            has_outline: false,
-            total_pages: 20,
-            items: vec![],
-        };
-
-        let result = resolve_section_to_pages(&outline, "Introduction");
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("no outline"));
-    }
-
-    #[test]
-    fn test_resolve_sections_multiple() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![
-                OutlineItem {
-                    title: "Introduction".to_string(),
-                    page: Some(0),
-                    children: vec![],
-                },
-                OutlineItem {
-                    title: "Methods".to_string(),
-                    page: Some(5),
-                    children: vec![],
-                },
-                OutlineItem {
-                    title: "Results".to_string(),
-                    page: Some(10),
-                    children: vec![],
-                },
-            ],
-        };
-
-        let pages = resolve_sections_to_pages(&outline, "Introduction,Results").unwrap();
-        // Introduction: 0-4, Results: 10-19
-        assert!(pages.contains(&0));
-        assert!(pages.contains(&4));
-        assert!(pages.contains(&10));
-        assert!(pages.contains(&19));
-        // Methods pages should not be included
-        assert!(!pages.contains(&6));
-    }
-
-    #[test]
-    fn test_resolve_nested_section() {
-        let outline = PdfOutline {
-            has_outline: true,
-            total_pages: 20,
-            items: vec![OutlineItem {
-                title: "Methods".to_string(),
-                page: Some(5),
-                children: vec![
-                    OutlineItem {
-                        title: "Data Collection".to_string(),
-                        page: Some(6),
-                        children: vec![],
-                    },
-                    OutlineItem {
-                        title: "Analysis".to_string(),
-                        page: Some(8),
-                        children: vec![],
-                    },
-                ],
-            }],
-        };
-
-        // Find nested section - sibling "Analysis" starts at 8, so Data Collection ends at 8
-        let (start, end) = resolve_section_to_pages(&outline, "Data Collection").unwrap();
-        assert_eq!(start, 6);
-        assert_eq!(end, 8); // Next sibling (Analysis) starts at 8
-    }
-
-    #[test]
-    fn test_collect_section_names() {
-        let items = vec![
-            OutlineItem {
-                title: "Introduction".to_string(),
-                page: Some(0),
-                children: vec![],
-            },
-            OutlineItem {
-                title: "Methods".to_string(),
-                page: Some(5),
-                children: vec![OutlineItem {
-                    title: "Data Collection".to_string(),
-                    page: Some(6),
-                    children: vec![],
-                }],
-            },
-        ];
-
-        let names = collect_section_names(&items);
-        assert_eq!(names.len(), 3);
-        assert!(names.contains(&"Introduction".to_string()));
-        assert!(names.contains(&"Methods".to_string()));
-        assert!(names.contains(&"Data Collection".to_string()));
-    }
-}
+mod tests;
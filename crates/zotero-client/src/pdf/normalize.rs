@@ -0,0 +1,159 @@
+//! Text normalization shared by exact and fuzzy anchoring.
+//!
+//! Quotes produced by people or language models rarely reproduce the PDF text
+//! byte for byte. Matching therefore compares *keys*: lowercase characters with
+//! ligatures expanded, typographic quotes and dashes unified, diacritics
+//! stripped, and hyphens ignored.
+
+/// Whether a character is a hyphen that may break a word at a line end.
+pub(super) fn is_hyphen(c: char) -> bool {
+    matches!(c, '-' | '\u{00AD}' | '\u{2010}' | '\u{2011}')
+}
+
+/// Whether a character is a dash that quotes often render differently
+/// (hyphen, en dash, em dash, minus sign).
+fn is_dash(c: char) -> bool {
+    is_hyphen(c)
+        || matches!(
+            c,
+            '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}'
+        )
+}
+
+/// Normalize quoted text the same way page text is normalized.
+///
+/// Whitespace runs collapse to a single space and a hyphen followed by a line
+/// break (as in text copied from a PDF) joins the two parts of the word.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::pdf::normalize_text;
+///
+/// assert_eq!(normalize_text("a  hyphen-\nated\tword "), "a hyphenated word");
+/// ```
+pub fn normalize_text(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            let start = i;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            let breaks_line = chars[start..i].contains(&'\n');
+            let after_hyphen = out.ends_with(is_hyphen)
+                && out
+                    .chars()
+                    .rev()
+                    .nth(1)
+                    .is_some_and(|prev| prev.is_alphabetic());
+
+            if breaks_line && after_hyphen {
+                out.pop();
+            } else if !out.is_empty() && i < chars.len() {
+                out.push(' ');
+            }
+            continue;
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+/// Expansion of ligatures and typographic punctuation, if any.
+fn expand(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        'æ' | 'Æ' => "ae",
+        'œ' | 'Œ' => "oe",
+        'ß' => "ss",
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '`' | '´' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '«' | '»' => "\"",
+        '\u{2026}' => "...",
+        _ => return None,
+    })
+}
+
+/// Accented Latin letters grouped by their base letter.
+const DIACRITICS: &[(char, &str)] = &[
+    ('a', "àáâãäåāăą"),
+    ('c', "çćĉċč"),
+    ('d', "ďđ"),
+    ('e', "èéêëēĕėęě"),
+    ('g', "ĝğġģ"),
+    ('h', "ĥħ"),
+    ('i', "ìíîïĩīĭįı"),
+    ('j', "ĵ"),
+    ('k', "ķ"),
+    ('l', "ĺļľŀł"),
+    ('n', "ñńņňŉ"),
+    ('o', "òóôõöøōŏő"),
+    ('r', "ŕŗř"),
+    ('s', "śŝşšș"),
+    ('t', "ţťŧț"),
+    ('u', "ùúûüũūŭůűų"),
+    ('w', "ŵ"),
+    ('y', "ýÿŷ"),
+    ('z', "źżž"),
+];
+
+/// Strip the diacritic from a lowercase Latin letter.
+fn strip_diacritic(c: char) -> char {
+    if c.is_ascii() {
+        return c;
+    }
+    DIACRITICS
+        .iter()
+        .find(|(_, accented)| accented.contains(c))
+        .map_or(c, |(base, _)| *base)
+}
+
+/// Whether a character is a combining diacritical mark.
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// Reduce normalized characters to the keys used for matching.
+///
+/// Each key is paired with the index of the character it came from. Matching
+/// ignores case, diacritics and dashes (plus any space around a dash), so
+/// compounds split at a line end still match their unbroken form.
+pub(super) fn match_keys(chars: impl Iterator<Item = char>) -> Vec<(char, usize)> {
+    let mut keys: Vec<(char, usize)> = Vec::new();
+    let mut after_dash = false;
+
+    for (index, c) in chars.enumerate() {
+        if is_dash(c) {
+            while keys.last().is_some_and(|(k, _)| *k == ' ') {
+                keys.pop();
+            }
+            after_dash = true;
+            continue;
+        }
+        if (c == ' ' && after_dash) || is_combining_mark(c) {
+            continue;
+        }
+        after_dash = false;
+
+        if let Some(expansion) = expand(c) {
+            keys.extend(expansion.chars().map(|k| (k, index)));
+        } else {
+            keys.extend(c.to_lowercase().map(|k| (strip_diacritic(k), index)));
+        }
+    }
+
+    keys
+}
@@ -0,0 +1,63 @@
+//! Reading-order positions of annotations.
+//!
+//! Zotero sorts annotations in its sidebar by `sortIndex`, a string of the
+//! form `PPPPP|OOOOOO|TTTTT`: the zero-based page index, the offset of the
+//! first annotated character among the page's characters, and the distance
+//! in points from the top of the page, each zero-padded.
+
+use super::PageText;
+
+/// Format a Zotero `sortIndex`.
+///
+/// Negative or fractional distances are truncated towards zero, and every
+/// field is clamped to its width.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::pdf::sort_index;
+///
+/// assert_eq!(sort_index(3, 1250, 72.6), "00003|001250|00072");
+/// ```
+pub fn sort_index(page_index: u32, offset: usize, top: f64) -> String {
+    format!(
+        "{:05}|{:06}|{:05}",
+        page_index.min(99_999),
+        offset.min(999_999),
+        top.clamp(0.0, 99_999.0) as u32
+    )
+}
+
+impl PageText {
+    /// Index of the character closest to the top-left corner of a rect.
+    ///
+    /// Image annotations have no text of their own, so Zotero orders them by
+    /// the character nearest to where they start. Returns 0 for a page
+    /// without text.
+    ///
+    /// The rect is in PDF/Zotero coordinates, [x1, y1, x2, y2].
+    pub fn offset_near(&self, rect: [f64; 4]) -> usize {
+        let (x, y) = (rect[0].min(rect[2]), rect[1].max(rect[3]));
+        let distance = |r: &[f64; 4]| {
+            let dx = (r[0] - x).max(x - r[2]).max(0.0);
+            let dy = (r[1] - y).max(y - r[3]).max(0.0);
+            dx * dx + dy * dy
+        };
+
+        self.lines
+            .iter()
+            .flatten()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(&a.rect).total_cmp(&distance(&b.rect)))
+            .map_or(0, |(offset, _)| offset)
+    }
+
+    /// The `sortIndex` of an area annotation covering a rect on this page.
+    pub fn area_sort_index(&self, rect: [f64; 4]) -> String {
+        sort_index(
+            self.page,
+            self.offset_near(rect),
+            self.height - rect[1].max(rect[3]),
+        )
+    }
+}
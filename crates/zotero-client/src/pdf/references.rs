@@ -0,0 +1,789 @@
+//! Reference lists: the entries of a paper's References or Bibliography
+//! section, parsed into authors, title, year, venue and DOI.
+//!
+//! The section is found in the outline (embedded or inferred from the
+//! headings), or else by its heading near the end of the document. Its lines
+//! are read in reading order and split into entries by their labels ("[12]",
+//! "12.") or, for author-year styles, by the hanging indentation of
+//! continuation lines. Each entry is then parsed with the conventions of the
+//! common citation styles (IEEE, ACM, ACL, APA, Springer, Vancouver); fields
+//! that cannot be told apart are left empty rather than guessed.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use mupdf::Document;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::get_pdf_outline;
+use super::headings::infer_outline_from_pages;
+use super::sections::{
+    flatten, item_bounds, key, ordered_lines, section_lines, section_pages, OrderedLine,
+    SectionBound,
+};
+use super::structure::{structured_page, StructuredPage};
+use crate::error::{Result, ZoteroClientError};
+use crate::types::PdfOutline;
+
+/// Titles of the reference section, as [`key`]s.
+const SECTION_TITLES: &[&str] = &[
+    "references",
+    "reference list",
+    "bibliography",
+    "literature cited",
+    "cited literature",
+    "works cited",
+    "literature",
+];
+
+/// Least indentation of an entry's continuation lines, in points.
+const MIN_INDENT: f64 = 3.0;
+
+/// Most indentation of an entry's continuation lines, in points.
+const MAX_INDENT: f64 = 36.0;
+
+/// Longest citation range ("[5–7]") expanded into single citations.
+const MAX_CITATION_RANGE: u32 = 50;
+
+/// Longest author-year citation marker, in characters.
+const MAX_MARKER_LENGTH: usize = 120;
+
+/// Most words before the year in one author-year citation ("Smith, Doe and Lee").
+const MAX_MARKER_WORDS: usize = 8;
+
+/// Abbreviations whose period does not end a sentence, lowercase.
+const ABBREVIATIONS: &[&str] = &[
+    "proc", "conf", "int", "intl", "vol", "no", "pp", "eds", "ed", "jr", "sr", "inc", "univ",
+    "trans", "natl", "acad", "sci", "rev", "lett", "phys", "assoc", "comput", "symp", "res",
+    "syst", "appl", "math", "stat", "biol", "chem", "med", "vs",
+];
+
+/// Entry labels: "[12]", "(12)", "12."
+static LABEL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:\[(\d{1,4})\]|\((\d{1,4})\)|(\d{1,4})\.\s)\s*").unwrap());
+
+/// DOIs, with or without a resolver prefix.
+static DOI: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:https?://(?:dx\.)?doi\.org/|doi:\s*)?(10\.\d{4,9}/\S+)").unwrap()
+});
+
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:https?://|www\.)\S+").unwrap());
+
+/// A title in quotes, as in IEEE style.
+static QUOTED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"[“"]([^”"]{4,})[”"]"#).unwrap());
+
+static YEAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:1[89]|20)\d{2}[a-z]?\b").unwrap());
+
+/// "(2020)", "(2020a)", "(2020, June 5)"
+static YEAR_PAREN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\(((?:1[89]|20)\d{2})[a-z]?(?:,[^)]*)?\)").unwrap());
+
+/// A sentence holding only a year, as in ACL style ("Smith. 2020. Title.").
+static YEAR_ONLY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\(?((?:1[89]|20)\d{2})[a-z]?\)?$").unwrap());
+
+/// Vancouver-style authors: "Smith J, Doe AB, et al."
+static VANCOUVER: LazyLock<Regex> = LazyLock::new(|| {
+    let name = r"\p{Lu}[\p{L}'’\-]+(?: \p{Lu}[\p{L}'’\-]+)* \p{Lu}{1,3}";
+    Regex::new(&format!(r"^((?:{name}, )*{name}(?:,? et al)?)\. ")).unwrap()
+});
+
+/// Where the venue ends and volume, pages or year start.
+static VENUE_END: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:,\s*(?:pp?\.|pages?\b|vol\.|volume\b|no\.|\d)|\s\d+\s*[(:,]|[,.]?\s\(?(?:1[89]|20)\d{2}[a-z]?\)?(?:[,.;]|$))",
+    )
+    .unwrap()
+});
+
+/// "[12]", "[3, 5–7]", "12"
+static NUMERIC_CITATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[\[(]?\s*(\d{1,4}(?:\s*[,;\-–]\s*\d{1,4})*)\s*[\])]?$").unwrap()
+});
+
+/// A capitalized name, as cited in author-year citations.
+static SURNAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\p{Lu}[\p{L}'’\-]+").unwrap());
+
+/// An entry of a reference list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+    /// Label of a numbered entry ("12" for "[12]" or "12.").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Author names as written in the entry.
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    /// Journal, proceedings or other container of the work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    /// The full text of the entry.
+    pub text: String,
+    /// Zero-based page where the entry starts, if it was read from a PDF.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+/// Whether a heading is the title of a reference section.
+///
+/// Section numbers ("7 References", "VII. REFERENCES") are ignored.
+pub(super) fn is_reference_title(title: &str) -> bool {
+    let title = key(title);
+    let title = title.trim_start_matches(|c: char| c.is_ascii_digit() || c == ' ');
+    let title = match title.split_once(' ') {
+        Some((numeral, rest)) if numeral.chars().all(|c| matches!(c, 'i' | 'v' | 'x')) => rest,
+        _ => title,
+    };
+    SECTION_TITLES.contains(&title)
+}
+
+/// Find the reference section in an outline: the last item with a
+/// reference section title, up to the next item of the same or a higher level.
+pub(super) fn reference_bounds(
+    outline: &PdfOutline,
+) -> Option<(SectionBound, Option<SectionBound>)> {
+    let mut items = Vec::new();
+    flatten(&outline.items, 0, &mut items);
+    let index = items
+        .iter()
+        .rposition(|(item, _)| item.page.is_some() && is_reference_title(&item.title))?;
+    Some(item_bounds(&items, index))
+}
+
+/// The number of an entry label at the start of a line.
+fn label_number(text: &str) -> Option<u32> {
+    let caps = LABEL.captures(text)?;
+    caps.iter()
+        .skip(1)
+        .flatten()
+        .next()
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Whether the last word of a text is a DOI or a URL, possibly cut short.
+fn ends_with_link(text: &str) -> bool {
+    let word = text.rsplit(' ').next().unwrap_or_default();
+    let doi = word.trim_start_matches("doi:");
+    (doi.starts_with("10.") && doi.contains('/')) || URL.is_match(word)
+}
+
+/// Append a line to the text of an entry.
+///
+/// Words hyphenated at the line end are joined, and DOIs and URLs broken
+/// after a slash, dot, hyphen or underscore are joined without a space.
+fn join_line(text: &mut String, line: &str) {
+    let line = line.trim();
+    if text.is_empty() {
+        text.push_str(line);
+        return;
+    }
+
+    let link = ends_with_link(text);
+    let hyphenated = text.ends_with('-')
+        && text.chars().rev().nth(1).is_some_and(char::is_alphabetic)
+        && line.starts_with(char::is_lowercase);
+
+    if link && text.ends_with(['/', '.', '-', '_']) {
+        text.push_str(line);
+    } else if hyphenated && !link {
+        text.pop();
+        text.push_str(line);
+    } else {
+        text.push(' ');
+        text.push_str(line);
+    }
+}
+
+/// Split the lines of a reference section into entries.
+///
+/// Lines are `(page, line)` pairs in reading order. Entries start at
+/// increasing labels if most entries have one, else at lines that are not
+/// indented if continuation lines are, else at every block.
+pub(super) fn split_entries(lines: &[(usize, OrderedLine)]) -> Vec<(usize, String)> {
+    let mut entries: Vec<(usize, String)> = Vec::new();
+    let labelled = lines
+        .iter()
+        .filter(|(_, line)| label_number(&line.text).is_some())
+        .count();
+
+    if labelled >= 2 && labelled * 5 >= lines.len() {
+        let mut last_label = 0;
+        for (page, line) in lines {
+            match label_number(&line.text) {
+                Some(label) if label > last_label => {
+                    last_label = label;
+                    entries.push((*page, line.text.trim().to_string()));
+                }
+                // Lines before the first label are not part of an entry
+                _ => {
+                    if let Some((_, text)) = entries.last_mut() {
+                        join_line(text, &line.text);
+                    }
+                }
+            }
+        }
+        return entries;
+    }
+
+    // Continuation lines of an entry are indented from the column edge, which
+    // is the position of other lines of the same page just to their left
+    let indented: Vec<bool> = lines
+        .iter()
+        .map(|(page, line)| {
+            lines.iter().any(|(other_page, other)| {
+                other_page == page && (MIN_INDENT..=MAX_INDENT).contains(&(line.left - other.left))
+            })
+        })
+        .collect();
+    let indents = indented.iter().filter(|&&i| i).count();
+    let hanging = indents > 0 && indents * 5 >= lines.len();
+
+    let mut last_block = None;
+    for (i, (page, line)) in lines.iter().enumerate() {
+        let block = Some((*page, line.block));
+        let starts = if hanging {
+            !indented[i]
+        } else {
+            block != last_block
+        };
+        last_block = block;
+
+        match entries.last_mut() {
+            Some((_, text)) if !starts => join_line(text, &line.text),
+            _ => entries.push((*page, line.text.trim().to_string())),
+        }
+    }
+    entries
+}
+
+/// Years in a text, with their position, leaving out numbers that are part
+/// of identifiers such as arXiv ids ("2001.08361") or of page ranges.
+fn years(text: &str) -> impl Iterator<Item = (usize, usize, u32)> + '_ {
+    YEAR.find_iter(text).filter_map(|m| {
+        let before = text[..m.start()].chars().next_back();
+        let mut after = text[m.end()..].chars();
+        let identifier = matches!(before, Some(':' | '.' | '/' | '-' | '–'))
+            || match after.next() {
+                Some('-' | '–') => true,
+                Some('.') => after.next().is_some_and(|c| c.is_ascii_digit()),
+                _ => false,
+            };
+        let year = m.as_str()[..4].parse().ok()?;
+        (!identifier).then_some((m.start(), m.end(), year))
+    })
+}
+
+/// Whether the period ending `word` ends a sentence, given the text after it.
+fn ends_sentence(word: &str, rest: &str) -> bool {
+    let word = word.trim_start_matches('-');
+    let mut chars = word.chars();
+    let initial = matches!((chars.next(), chars.next()), (Some(c), None) if c.is_uppercase());
+    if initial || ABBREVIATIONS.contains(&word.to_lowercase().as_str()) {
+        return false;
+    }
+    rest.starts_with(|c: char| {
+        c.is_uppercase() || c.is_ascii_digit() || matches!(c, '(' | '[' | '"' | '“')
+    }) || rest.starts_with("arXiv")
+        || rest.starts_with("in ")
+}
+
+/// Split a text into sentences, keeping periods after initials and
+/// abbreviations. Question and exclamation marks are kept.
+fn split_sentences(text: &str) -> VecDeque<String> {
+    let mut sentences = VecDeque::new();
+    let mut start = 0;
+
+    for (pos, c) in text.char_indices() {
+        if !matches!(c, '.' | '?' | '!') {
+            continue;
+        }
+        let end = pos + c.len_utf8();
+        if !text[end..].starts_with(char::is_whitespace) {
+            continue;
+        }
+        let rest = text[end..].trim_start();
+        let word: String = {
+            let mut word: Vec<char> = text[start..pos]
+                .chars()
+                .rev()
+                .take_while(|c| c.is_alphanumeric() || *c == '-')
+                .collect();
+            word.reverse();
+            word.into_iter().collect()
+        };
+        if c == '.' && !ends_sentence(&word, rest) {
+            continue;
+        }
+
+        let sentence = if c == '.' {
+            &text[start..pos]
+        } else {
+            &text[start..end]
+        };
+        if !sentence.trim().is_empty() {
+            sentences.push_back(sentence.trim().to_string());
+        }
+        start = end;
+    }
+
+    let last = text[start..].trim().trim_end_matches('.');
+    if !last.is_empty() {
+        sentences.push_back(last.to_string());
+    }
+    sentences
+}
+
+/// Split an author list into names.
+///
+/// "Smith, J., Doe, A." lists surnames and initials separated by commas;
+/// these are paired back into "Smith, J." and "Doe, A.".
+fn split_authors(text: &str) -> Vec<String> {
+    let initials = |part: &str| {
+        !part.is_empty()
+            && part
+                .split(|c: char| c == '.' || c == '-' || c.is_whitespace())
+                .filter(|p| !p.is_empty())
+                .all(|p| p.chars().count() == 1 && p.chars().all(char::is_uppercase))
+    };
+
+    let text = text
+        .trim()
+        .trim_end_matches([',', ';', ':'])
+        .replace(" et al", "")
+        .replace(", and ", ", ")
+        .replace(" and ", ", ")
+        .replace(", & ", ", ")
+        .replace(" & ", ", ");
+    let parts: Vec<&str> = text
+        .split(',')
+        .map(str::trim)
+        .filter(|part| part.chars().any(char::is_alphabetic))
+        .collect();
+
+    let paired = parts.len() >= 2 && parts.iter().skip(1).step_by(2).all(|p| initials(p));
+    let names: Vec<String> = if paired {
+        parts.chunks(2).map(|pair| pair.join(", ")).collect()
+    } else {
+        parts.into_iter().map(str::to_string).collect()
+    };
+
+    // Keep the period of a final initial, not the one ending the list
+    names
+        .into_iter()
+        .map(|name| {
+            let last = name
+                .trim_end_matches('.')
+                .rsplit([' ', '.'])
+                .next()
+                .unwrap_or_default();
+            if last.chars().count() > 1 {
+                name.trim_end_matches('.').to_string()
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// Clean a title: trim punctuation and quotes left around it.
+fn clean_title(title: &str) -> Option<String> {
+    let title = title
+        .trim()
+        .trim_matches(|c: char| matches!(c, '"' | '“' | '”'))
+        .trim_end_matches([',', '.', ';', ':'])
+        .trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// The venue at the start of a text, up to its volume, pages or year.
+fn clean_venue(text: &str) -> Option<String> {
+    let text = text.trim().trim_start_matches([',', '.', ' ']);
+    let text = ["In: ", "In ", "in "]
+        .iter()
+        .find_map(|prefix| text.strip_prefix(prefix))
+        .unwrap_or(text);
+    let end = VENUE_END.find(text).map_or(text.len(), |m| m.start());
+    let venue = text[..end]
+        .trim()
+        .trim_end_matches([',', '.', ';', ':'])
+        .trim();
+    (!venue.is_empty()).then(|| venue.to_string())
+}
+
+/// Parse the text of a reference list entry.
+///
+/// Recognizes a leading label, a DOI anywhere in the entry, quoted titles
+/// (IEEE), years after the authors (APA, ACL, Harvard, Springer) or at the
+/// end (IEEE, ACM, LNCS) and Vancouver-style author lists. Fields that
+/// cannot be found are `None`.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::pdf::parse_reference;
+///
+/// let reference = parse_reference(
+///     "[3] A. Vaswani, N. Shazeer, and N. Parmar, \u{201c}Attention is all you need,\u{201d} \
+///      in Proc. NeurIPS, 2017, pp. 5998\u{2013}6008.",
+/// );
+/// assert_eq!(reference.label.as_deref(), Some("3"));
+/// assert_eq!(reference.title.as_deref(), Some("Attention is all you need"));
+/// assert_eq!(reference.year, Some(2017));
+/// assert_eq!(reference.venue.as_deref(), Some("Proc. NeurIPS"));
+/// ```
+pub fn parse_reference(text: &str) -> Reference {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut reference = Reference {
+        text: text.clone(),
+        ..Default::default()
+    };
+
+    let mut body = text.as_str();
+    if let Some(caps) = LABEL.captures(body) {
+        reference.label = caps
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|m| m.as_str().to_string());
+        body = &body[caps[0].len()..];
+    }
+
+    if let Some(caps) = DOI.captures(body) {
+        let doi = caps[1].trim_end_matches(['.', ',', ';', ']', '>']);
+        // Keep a closing parenthesis that is part of the DOI
+        let doi = if doi.ends_with(')') && doi.matches('(').count() < doi.matches(')').count() {
+            &doi[..doi.len() - 1]
+        } else {
+            doi
+        };
+        reference.doi = Some(doi.to_string());
+    }
+    let body = DOI.replace_all(body, "");
+    let body = URL.replace_all(&body, "");
+    let body = body.trim();
+
+    let paren_year = YEAR_PAREN
+        .captures(body)
+        .and_then(|caps| caps[1].parse().ok());
+    let last_year = years(body).last().map(|(_, _, year)| year);
+
+    if let Some(caps) = QUOTED.captures(body) {
+        let quote = caps.get(0).unwrap();
+        reference.authors = split_authors(&body[..quote.start()]);
+        reference.title = clean_title(&caps[1]);
+        reference.venue = clean_venue(&body[quote.end()..]);
+        reference.year = paren_year.or(last_year);
+        return reference;
+    }
+
+    let (authors, mut sentences) = match VANCOUVER.captures(body) {
+        Some(caps) => (caps[1].to_string(), split_sentences(&body[caps[0].len()..])),
+        None => {
+            let mut sentences = split_sentences(body);
+            let first = sentences.pop_front().unwrap_or_default();
+            // "Smith, J., Doe, A.: Title" (Springer LNCS)
+            match first.split_once(": ") {
+                Some((authors, title)) => {
+                    sentences.push_front(title.to_string());
+                    (authors.to_string(), sentences)
+                }
+                None => (first, sentences),
+            }
+        }
+    };
+
+    // A year right after the authors: "Smith, J. (2020). Title", "Smith J (2020) Title"
+    let mut authors = authors;
+    let mut year = None;
+    let author_year = YEAR_PAREN
+        .captures(&authors)
+        .and_then(|caps| {
+            let m = caps.get(0)?;
+            Some((m.start(), m.end(), caps[1].parse().ok()?))
+        })
+        .or_else(|| years(&authors).next());
+    if let Some((start, end, found)) = author_year {
+        if authors[..start].chars().any(char::is_alphabetic) {
+            let rest = authors[end..]
+                .trim_start_matches(|c: char| {
+                    matches!(c, ')' | '.' | ',' | ':') || c.is_whitespace()
+                })
+                .to_string();
+            if !rest.is_empty() {
+                sentences.push_front(rest);
+            }
+            authors.truncate(start);
+            year = Some(found);
+        }
+    }
+    reference.authors = split_authors(authors.trim_end_matches(['(', ' ']));
+
+    // "Smith and Doe. 2020. Title." (ACL)
+    while let Some(caps) = sentences.front().and_then(|s| YEAR_ONLY.captures(s)) {
+        year = year.or_else(|| caps[1].parse().ok());
+        sentences.pop_front();
+    }
+
+    reference.title = sentences.pop_front().and_then(|title| clean_title(&title));
+    let rest = Vec::from(sentences).join(". ");
+    reference.venue = clean_venue(&rest);
+    reference.year = year.or(paren_year).or(last_year);
+    reference
+}
+
+/// An in-text citation marker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CitationMarker {
+    /// Numbered citations: "[12]", "[3, 5–7]"
+    Numbers(Vec<u32>),
+    /// Author-year citations as `(surname, year)`: "Smith et al., 2020",
+    /// "(Smith and Doe 2020a; Lee, 2019)"
+    AuthorYear(Vec<(String, u32)>),
+}
+
+impl CitationMarker {
+    /// Parse a citation marker.
+    ///
+    /// Returns `None` for text that is not a marker, such as a full
+    /// reference entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zotero_client::pdf::CitationMarker;
+    ///
+    /// assert_eq!(CitationMarker::parse("[3, 5-7]"), Some(CitationMarker::Numbers(vec![3, 5, 6, 7])));
+    /// assert_eq!(
+    ///     CitationMarker::parse("Smith et al., 2020"),
+    ///     Some(CitationMarker::AuthorYear(vec![("Smith".to_string(), 2020)]))
+    /// );
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+
+        if let Some(caps) = NUMERIC_CITATION.captures(text) {
+            let mut numbers = Vec::new();
+            for part in caps[1].split([',', ';']) {
+                let bounds: Vec<u32> = part
+                    .split(['-', '–'])
+                    .filter_map(|n| n.trim().parse().ok())
+                    .collect();
+                match bounds[..] {
+                    [n] => numbers.push(n),
+                    // Ranges such as "5–7", bounded to keep a typo from listing thousands
+                    [from, to] if from <= to && to - from <= MAX_CITATION_RANGE => {
+                        numbers.extend(from..=to)
+                    }
+                    _ => return None,
+                }
+            }
+            return Some(Self::Numbers(numbers));
+        }
+
+        if text.chars().count() > MAX_MARKER_LENGTH {
+            return None;
+        }
+        let citations = text
+            .trim_matches(['(', ')', '[', ']'])
+            .split(';')
+            .map(|part| {
+                let (start, end, year) = years(part).next()?;
+                // The surname is the first capitalized word before the year,
+                // and nothing but punctuation follows it, unlike in an entry
+                let surname = SURNAME.find(&part[..start])?.as_str();
+                let words = part[..start].split_whitespace().count();
+                let rest = part[end..].trim_matches(|c: char| {
+                    matches!(c, ')' | ']' | ',' | '.') || c.is_whitespace()
+                });
+                (words <= MAX_MARKER_WORDS && rest.is_empty()).then(|| (surname.to_string(), year))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (!citations.is_empty()).then_some(Self::AuthorYear(citations))
+    }
+
+    /// The entries of a reference list that the marker cites.
+    ///
+    /// Numbers are matched against entry labels, or against positions in
+    /// the list if its entries have no labels. Author-year citations are
+    /// matched against the year and the first author's name, or any
+    /// author's name if no first author matches. Citations without a
+    /// matching entry are left out.
+    pub fn find<'a>(&self, references: &'a [Reference]) -> Vec<&'a Reference> {
+        let mut found: Vec<&Reference> = Vec::new();
+        let mut push = |reference: Option<&'a Reference>| {
+            if let Some(reference) = reference {
+                if !found.iter().any(|r| std::ptr::eq(*r, reference)) {
+                    found.push(reference);
+                }
+            }
+        };
+
+        match self {
+            Self::Numbers(numbers) => {
+                let labelled = references.iter().any(|r| r.label.is_some());
+                for &number in numbers {
+                    let label = number.to_string();
+                    push(if labelled {
+                        references
+                            .iter()
+                            .find(|r| r.label.as_deref() == Some(label.as_str()))
+                    } else {
+                        (number as usize)
+                            .checked_sub(1)
+                            .and_then(|index| references.get(index))
+                    });
+                }
+            }
+            Self::AuthorYear(citations) => {
+                for (surname, year) in citations {
+                    let candidates: Vec<&Reference> = references
+                        .iter()
+                        .filter(|r| r.year == Some(*year))
+                        .collect();
+                    let first_author = candidates.iter().find(|r| {
+                        r.authors
+                            .first()
+                            .is_some_and(|author| author.contains(surname.as_str()))
+                    });
+                    let any_author = candidates.iter().find(|r| {
+                        r.authors
+                            .iter()
+                            .any(|author| author.contains(surname.as_str()))
+                    });
+                    push(first_author.or(any_author).copied());
+                }
+            }
+        }
+
+        found
+    }
+}
+
+/// Parse the reference list between a section start and the next section.
+///
+/// `pages` are the pages from the section start to the next section's
+/// start, in order. Running headers and footers are left out.
+pub(super) fn references_from_pages(
+    pages: &[StructuredPage],
+    start: &SectionBound,
+    end: Option<&SectionBound>,
+) -> Vec<Reference> {
+    let lines: Vec<(usize, OrderedLine)> = section_lines(pages, start, end)
+        .into_iter()
+        .flat_map(|(page, lines)| lines.into_iter().map(move |line| (page, line)))
+        .filter(|(_, line)| !line.running && !is_reference_title(&line.text))
+        .collect();
+
+    split_entries(&lines)
+        .into_iter()
+        .map(|(page, text)| Reference {
+            page: Some(page as u32),
+            ..parse_reference(&text)
+        })
+        .collect()
+}
+
+/// Extract and parse the reference list of a PDF.
+///
+/// The References or Bibliography section is found in the outline (see
+/// [`get_or_infer_outline`](super::get_or_infer_outline)) or, failing that,
+/// as the last line titled like one. It ends at the next section of the same
+/// or a higher level, such as an appendix, or at the end of the document.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::extract_references;
+///
+/// for reference in extract_references("/path/to/paper.pdf")? {
+///     println!("{:?} ({:?}) doi: {:?}", reference.title, reference.year, reference.doi);
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn extract_references<P: AsRef<Path>>(path: P) -> Result<Vec<Reference>> {
+    let path = path.as_ref();
+    let outline = get_pdf_outline(path)?;
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    if outline.has_outline {
+        if let Some((start, end)) = reference_bounds(&outline) {
+            // An outline out of document order gives no usable end
+            let end = end.filter(|end| end.page >= start.page);
+            let pages = section_pages(&doc, &start, end.as_ref())?;
+            return Ok(references_from_pages(&pages, &start, end.as_ref()));
+        }
+        return references_after_heading(
+            (0..total_pages)
+                .rev()
+                .map(|page_num| structured_page(&doc, page_num)),
+        );
+    }
+
+    // Without bookmarks every page is read to infer the outline, and the
+    // same pages serve the search for the heading
+    let pages = (0..total_pages)
+        .map(|page_num| structured_page(&doc, page_num))
+        .collect::<Result<Vec<_>>>()?;
+    let outline = infer_outline_from_pages(&pages, total_pages);
+    if let Some((start, end)) = reference_bounds(&outline) {
+        let end = end.filter(|end| end.page >= start.page);
+        let last = end
+            .as_ref()
+            .map_or(total_pages.saturating_sub(1), |end| end.page)
+            .min(total_pages.saturating_sub(1));
+        return Ok(references_from_pages(
+            &pages[start.page..=last],
+            &start,
+            end.as_ref(),
+        ));
+    }
+    references_after_heading(pages.into_iter().rev().map(Ok))
+}
+
+/// Look for the reference heading in `pages`, given from the last page back,
+/// and read the entries after it.
+fn references_after_heading(
+    pages: impl Iterator<Item = Result<StructuredPage>>,
+) -> Result<Vec<Reference>> {
+    let mut read = VecDeque::new();
+    for page in pages {
+        let page = page?;
+        let page_num = page.page as usize;
+        let heading = ordered_lines(&page)
+            .into_iter()
+            .rfind(|line| !line.running && is_reference_title(&line.text));
+        read.push_front(page);
+
+        if let Some(line) = heading {
+            let start = SectionBound {
+                page: page_num,
+                top: Some(line.top),
+                title: line.text,
+            };
+            return Ok(references_from_pages(&Vec::from(read), &start, None));
+        }
+    }
+
+    Err(ZoteroClientError::Pdf(
+        "No References or Bibliography section found".to_string(),
+    ))
+}
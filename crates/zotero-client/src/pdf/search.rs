@@ -0,0 +1,254 @@
+//! Searching the text of a PDF with page, snippet and position results.
+//!
+//! MuPDF's native search ([`search_text`](super::search_text)) is literal,
+//! case-insensitive and cannot match across lines. [`TextMatcher`] instead
+//! runs a regular expression over each page's text, where lines are joined
+//! by line breaks and words hyphenated at a line end are joined, and maps
+//! every match back to the rectangles of its characters.
+
+use std::path::Path;
+
+use mupdf::Document;
+use regex::{Regex, RegexBuilder};
+
+use super::anchor::page_text;
+use super::normalize::is_hyphen;
+use super::PageText;
+use crate::error::{Result, ZoteroClientError};
+
+/// Bytes of page text kept on either side of a match in its snippet.
+const SNIPPET_CONTEXT: usize = 60;
+
+/// How a query is matched against page text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match letter case exactly (default: case-insensitive).
+    pub case_sensitive: bool,
+    /// Only match whole words.
+    pub whole_word: bool,
+    /// Treat the query as a regular expression instead of literal text.
+    pub regex: bool,
+}
+
+/// An occurrence of a query in a PDF.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    /// Zero-based page number.
+    pub page: u32,
+    /// The matched text, with line breaks as spaces.
+    pub text: String,
+    /// The matched text with some surrounding context.
+    pub snippet: String,
+    /// One rectangle per matched line, in PDF/Zotero coordinates.
+    pub rects: Vec<[f64; 4]>,
+}
+
+/// A compiled query.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::pdf::{SearchOptions, TextMatcher};
+///
+/// let options = SearchOptions { whole_word: true, ..Default::default() };
+/// let matcher = TextMatcher::new("attention", options)?;
+/// assert!(matcher.is_match("Attention is all you need"));
+/// assert!(!matcher.is_match("attentional"));
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextMatcher {
+    regex: Regex,
+}
+
+impl TextMatcher {
+    /// Compile a query.
+    ///
+    /// Whitespace in a literal query matches any run of whitespace, including
+    /// line breaks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the query is empty or not a valid regular
+    /// expression.
+    pub fn new(query: &str, options: SearchOptions) -> Result<Self> {
+        if query.trim().is_empty() {
+            return Err(ZoteroClientError::InvalidInput(
+                "Search query is empty".to_string(),
+            ));
+        }
+
+        let pattern = if options.regex {
+            query.to_string()
+        } else {
+            query
+                .split_whitespace()
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\s+")
+        };
+        let pattern = if options.whole_word {
+            format!(r"\b(?:{})\b", pattern)
+        } else {
+            pattern
+        };
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!options.case_sensitive)
+            .build()
+            .map_err(|e| {
+                ZoteroClientError::InvalidInput(format!("Invalid regular expression: {}", e))
+            })?;
+        Ok(Self { regex })
+    }
+
+    /// Whether the query occurs in a string.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// Find all occurrences of the query on a page.
+    pub fn find_in_page(&self, page: &PageText) -> Vec<TextMatch> {
+        let stream = PageStream::new(page);
+
+        self.regex
+            .find_iter(&stream.text)
+            .filter(|m| !m.is_empty())
+            .map(|m| TextMatch {
+                page: page.page,
+                text: m.as_str().split_whitespace().collect::<Vec<_>>().join(" "),
+                snippet: stream.snippet(m.start(), m.end()),
+                rects: stream.rects(m.start(), m.end()),
+            })
+            .collect()
+    }
+}
+
+/// The text of a page as one string, with the source of every character.
+struct PageStream {
+    text: String,
+    /// `(byte offset in text, line, rect)` of each character taken from the page,
+    /// in text order. Line breaks added between lines have no entry.
+    chars: Vec<(usize, usize, [f64; 4])>,
+}
+
+impl PageStream {
+    fn new(page: &PageText) -> Self {
+        let mut text = String::new();
+        let mut chars = Vec::new();
+
+        for (index, line) in page.lines.iter().enumerate() {
+            let continues = page
+                .lines
+                .get(index + 1)
+                .and_then(|next| next.first())
+                .is_some_and(|next| next.ch.is_lowercase());
+            let hyphenated = continues && line.last().is_some_and(|last| is_hyphen(last.ch));
+
+            let kept = if hyphenated {
+                &line[..line.len() - 1]
+            } else {
+                &line[..]
+            };
+            for positioned in kept {
+                chars.push((text.len(), index, positioned.rect));
+                text.push(positioned.ch);
+            }
+            if !hyphenated {
+                text.push('\n');
+            }
+        }
+
+        Self { text, chars }
+    }
+
+    /// One rectangle per line covering the characters in a byte range.
+    fn rects(&self, start: usize, end: usize) -> Vec<[f64; 4]> {
+        let first = self.chars.partition_point(|c| c.0 < start);
+        let mut rects: Vec<(usize, [f64; 4])> = Vec::new();
+
+        for &(_, line, rect) in self.chars[first..].iter().take_while(|c| c.0 < end) {
+            match rects.last_mut() {
+                Some((last_line, union)) if *last_line == line => {
+                    union[0] = union[0].min(rect[0]);
+                    union[1] = union[1].min(rect[1]);
+                    union[2] = union[2].max(rect[2]);
+                    union[3] = union[3].max(rect[3]);
+                }
+                _ => rects.push((line, rect)),
+            }
+        }
+        rects.into_iter().map(|(_, rect)| rect).collect()
+    }
+
+    /// The matched text with surrounding context, whitespace collapsed.
+    fn snippet(&self, start: usize, end: usize) -> String {
+        let mut from = start.saturating_sub(SNIPPET_CONTEXT);
+        while !self.text.is_char_boundary(from) {
+            from -= 1;
+        }
+        let mut to = (end + SNIPPET_CONTEXT).min(self.text.len());
+        while !self.text.is_char_boundary(to) {
+            to += 1;
+        }
+
+        let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        format!(
+            "{}{}{}",
+            if from > 0 { "…" } else { "" },
+            collapse(&self.text[from..to]),
+            if to < self.text.trim_end().len() {
+                "…"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Search pages of a PDF for a query.
+///
+/// Stops after `max_matches` matches.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `pages` - Zero-based page numbers to search, in order
+/// * `matcher` - The compiled query
+/// * `max_matches` - Maximum number of matches to return
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::{get_page_count, search_pages, SearchOptions, TextMatcher};
+///
+/// let path = "/path/to/file.pdf";
+/// let matcher = TextMatcher::new(r"p\s*<\s*0\.05", SearchOptions { regex: true, ..Default::default() })?;
+/// let pages: Vec<usize> = (0..get_page_count(path)?).collect();
+/// for m in search_pages(path, &pages, &matcher, 100)? {
+///     println!("page {}: {}", m.page + 1, m.snippet);
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn search_pages<P: AsRef<Path>>(
+    path: P,
+    pages: &[usize],
+    matcher: &TextMatcher,
+    max_matches: usize,
+) -> Result<Vec<TextMatch>> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    let mut matches = Vec::new();
+    for &page in pages {
+        if matches.len() >= max_matches {
+            break;
+        }
+        matches.extend(matcher.find_in_page(&page_text(&doc, page)?));
+    }
+    matches.truncate(max_matches);
+    Ok(matches)
+}
@@ -0,0 +1,330 @@
+//! Section text cut at the section headings.
+//!
+//! [`resolve_section_to_pages`] maps a section to whole pages, so reading a
+//! section that way also returns the end of the previous section and the
+//! start of the next one. [`extract_section_text`] reads the pages in reading
+//! order instead and cuts the text at the section heading and at the next
+//! heading of the same or a higher level. Headings are located by the
+//! outline destination's position on the page and by their title.
+
+use std::path::Path;
+
+use mupdf::Document;
+
+use super::layout::{LayoutBlock, LayoutOptions, PageLayout};
+use super::resolve_section_to_pages;
+use super::structure::{structured_page, StructuredPage};
+use crate::error::{Result, ZoteroClientError};
+use crate::types::{OutlineItem, PdfOutline};
+
+/// The text of a section on one page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionPage {
+    /// Zero-based page number.
+    pub page: usize,
+    /// The part of the page's text that belongs to the section, in reading order.
+    pub text: String,
+}
+
+/// Where a section starts, or where the next one starts.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SectionBound {
+    /// Zero-based page number.
+    pub(super) page: usize,
+    /// Distance in points from the top of the page, if the outline has it.
+    pub(super) top: Option<f64>,
+    /// Heading title, as in the outline.
+    pub(super) title: String,
+}
+
+impl SectionBound {
+    fn new(item: &OutlineItem) -> Self {
+        Self {
+            page: item.page.map_or(0, |p| p as usize),
+            top: item.top,
+            title: item.title.clone(),
+        }
+    }
+}
+
+/// A line of a page in reading order.
+pub(super) struct OrderedLine {
+    /// Index of the line's block on the page.
+    pub(super) block: usize,
+    /// Distance in points from the left of the page.
+    pub(super) left: f64,
+    /// Distance in points from the top of the page.
+    pub(super) top: f64,
+    pub(super) text: String,
+    /// Whether the line is part of a running header, footer or page number.
+    pub(super) running: bool,
+}
+
+/// Outline items in document order, with their depth.
+pub(super) fn flatten<'a>(
+    items: &'a [OutlineItem],
+    depth: usize,
+    out: &mut Vec<(&'a OutlineItem, usize)>,
+) {
+    for item in items {
+        out.push((item, depth));
+        flatten(&item.children, depth + 1, out);
+    }
+}
+
+/// Find where a section starts and where the next section of the same or a
+/// higher level starts, if there is one.
+///
+/// Sections are matched like in [`resolve_section_to_pages`].
+pub(super) fn section_bounds(
+    outline: &PdfOutline,
+    section: &str,
+) -> Result<(SectionBound, Option<SectionBound>)> {
+    // Reports a missing outline or section with the available sections
+    resolve_section_to_pages(outline, section)?;
+
+    let mut items = Vec::new();
+    flatten(&outline.items, 0, &mut items);
+
+    let wanted = section.to_lowercase();
+    let index = items
+        .iter()
+        .position(|(item, _)| item.title.to_lowercase().contains(&wanted))
+        .ok_or_else(|| ZoteroClientError::Pdf(format!("Section '{}' not found", section)))?;
+    Ok(item_bounds(&items, index))
+}
+
+/// Where the outline item at `index` of the [`flatten`]ed outline starts, and
+/// where the next item of the same or a higher level starts.
+pub(super) fn item_bounds(
+    items: &[(&OutlineItem, usize)],
+    index: usize,
+) -> (SectionBound, Option<SectionBound>) {
+    let (item, depth) = items[index];
+
+    let next = items[index + 1..]
+        .iter()
+        .find(|(next, next_depth)| *next_depth <= depth && next.page.is_some())
+        .map(|(next, _)| SectionBound::new(next));
+
+    (SectionBound::new(item), next)
+}
+
+/// Lowercase words of letters and digits, to compare headings with page text.
+pub(super) fn key(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The lines of a page in reading order.
+pub(super) fn ordered_lines(page: &StructuredPage) -> Vec<OrderedLine> {
+    let layout = PageLayout {
+        page: page.page,
+        width: page.width,
+        height: page.height,
+        blocks: page
+            .blocks
+            .iter()
+            .map(|block| LayoutBlock {
+                rect: block.bbox,
+                lines: block.lines.iter().map(|line| line.text()).collect(),
+            })
+            .collect(),
+    };
+
+    let mut lines = Vec::new();
+    for index in layout.reading_order_indices() {
+        let running = layout.is_running_text(&layout.blocks[index]);
+        for line in &page.blocks[index].lines {
+            lines.push(OrderedLine {
+                block: index,
+                left: line.bbox[0],
+                top: page.height - line.bbox[3],
+                text: line.text(),
+                running,
+            });
+        }
+    }
+    lines
+}
+
+/// Index of the line where a section starts.
+///
+/// This is the line that starts with the heading title, closest to the
+/// outline destination if several do. Without a matching line, it is the
+/// line closest to the destination, or the first line of the page.
+fn locate(lines: &[OrderedLine], bound: &SectionBound) -> usize {
+    let title = key(&bound.title);
+    let distance = |line: &OrderedLine| bound.top.map_or(0.0, |top| (line.top - top).abs());
+    let closest = |candidates: Vec<(usize, &OrderedLine)>| {
+        candidates
+            .into_iter()
+            .min_by(|a, b| distance(a.1).total_cmp(&distance(b.1)))
+            .map(|(index, _)| index)
+    };
+
+    let titled: Vec<(usize, &OrderedLine)> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            let text = key(&line.text);
+            // A long heading may wrap, so the line may hold only its start
+            !line.running
+                && !text.is_empty()
+                && (text.starts_with(&title)
+                    || (title.starts_with(&text) && text.len() * 2 >= title.len()))
+        })
+        .collect();
+
+    if let Some(index) = closest(titled) {
+        return index;
+    }
+    if bound.top.is_some() {
+        return closest(lines.iter().enumerate().collect()).unwrap_or(0);
+    }
+    0
+}
+
+/// Cut the lines of a section from the structured text of its pages.
+///
+/// `pages` are the pages from the section start to the next section's
+/// start, in order. Returns the section's lines of each page with the
+/// zero-based page number.
+pub(super) fn section_lines(
+    pages: &[StructuredPage],
+    start: &SectionBound,
+    end: Option<&SectionBound>,
+) -> Vec<(usize, Vec<OrderedLine>)> {
+    let mut sections = Vec::new();
+
+    for page in pages {
+        let page_num = page.page as usize;
+        let mut lines = ordered_lines(page);
+
+        let from = if page_num == start.page {
+            locate(&lines, start)
+        } else {
+            0
+        };
+        let to = match end {
+            Some(end) if end.page == page_num => {
+                let to = locate(&lines, end);
+                // A next heading before the section's own is misplaced
+                if page_num == start.page && to <= from {
+                    lines.len()
+                } else {
+                    to
+                }
+            }
+            _ => lines.len(),
+        };
+
+        lines.truncate(to.max(from));
+        lines.drain(..from);
+        sections.push((page_num, lines));
+    }
+
+    sections
+}
+
+/// Cut the text of a section from the structured text of its pages.
+///
+/// `pages` are the pages from the section start to the next section's
+/// start, in order.
+pub(super) fn slice_section(
+    pages: &[StructuredPage],
+    start: &SectionBound,
+    end: Option<&SectionBound>,
+    options: LayoutOptions,
+) -> Vec<SectionPage> {
+    let mut sections = Vec::new();
+
+    for (page, lines) in section_lines(pages, start, end) {
+        let mut text = String::new();
+        let mut last_block = None;
+        for line in lines
+            .iter()
+            .filter(|line| !(options.strip_headers_footers && line.running))
+        {
+            if let Some(last) = last_block {
+                text.push_str(if last == line.block { "\n" } else { "\n\n" });
+            }
+            text.push_str(&line.text);
+            last_block = Some(line.block);
+        }
+
+        if !text.trim().is_empty() {
+            sections.push(SectionPage { page, text });
+        }
+    }
+
+    sections
+}
+
+/// Extract the text of a section, from its heading to the next heading.
+///
+/// The section is found in the outline like in [`resolve_section_to_pages`]
+/// and includes its subsections. Pages are read in reading order, so the
+/// section ends where the next heading of the same or a higher level starts,
+/// even in the middle of a column.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `outline` - The PDF outline, embedded or inferred
+/// * `section` - The section name to extract
+/// * `options` - Whether to leave out running headers, footers and page numbers
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::{extract_section_text, get_or_infer_outline, LayoutOptions};
+///
+/// let path = "/path/to/file.pdf";
+/// let outline = get_or_infer_outline(path)?;
+/// for page in extract_section_text(path, &outline, "Methods", LayoutOptions::default())? {
+///     println!("--- Page {} ---\n{}", page.page + 1, page.text);
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn extract_section_text<P: AsRef<Path>>(
+    path: P,
+    outline: &PdfOutline,
+    section: &str,
+    options: LayoutOptions,
+) -> Result<Vec<SectionPage>> {
+    let path = path.as_ref();
+    let (start, end) = section_bounds(outline, section)?;
+    // An outline out of document order gives no usable end
+    let end = end.filter(|end| end.page >= start.page);
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+    let pages = section_pages(&doc, &start, end.as_ref())?;
+
+    Ok(slice_section(&pages, &start, end.as_ref(), options))
+}
+
+/// Read the structured text of the pages from a section start to the next
+/// section's start, or to the end of the document.
+pub(super) fn section_pages(
+    doc: &Document,
+    start: &SectionBound,
+    end: Option<&SectionBound>,
+) -> Result<Vec<StructuredPage>> {
+    let total_pages = doc
+        .page_count()
+        .map(|c| c as usize)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page count: {}", e)))?;
+
+    let last = end
+        .map_or(total_pages.saturating_sub(1), |end| end.page)
+        .min(total_pages.saturating_sub(1));
+    (start.page..=last)
+        .map(|page| structured_page(doc, page))
+        .collect()
+}
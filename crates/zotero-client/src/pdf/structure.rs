@@ -0,0 +1,394 @@
+//! Structured page text: blocks, lines and spans with font information.
+//!
+//! MuPDF's structured text has the block and line structure of a page but
+//! no fonts, while the text objects a page draws have fonts but no layout.
+//! [`extract_structured_text`] runs the page through a device that records
+//! the font of every glyph by its origin, then looks the font up for each
+//! character of the structured text and groups runs of characters with the
+//! same font and size into spans.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use mupdf::{
+    ColorParams, Colorspace, Device, Document, Matrix, NativeDevice, Point, Quad, Rect,
+    StrokeState, Text, TextBlockType, TextPageOptions,
+};
+use serde::{Deserialize, Serialize};
+
+use super::union;
+use crate::error::{Result, ZoteroClientError};
+
+/// Farthest a character's origin may be from a glyph's origin to take its font, in points.
+const ORIGIN_TOLERANCE: f32 = 0.5;
+
+/// A page's text as blocks of lines of styled spans.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StructuredPage {
+    /// Zero-based page number.
+    pub page: u32,
+    /// Page width in points.
+    pub width: f64,
+    /// Page height in points.
+    pub height: f64,
+    /// Text blocks in the order MuPDF extracts them.
+    pub blocks: Vec<StructuredBlock>,
+}
+
+/// A block of lines, usually a paragraph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredBlock {
+    /// Bounding box [x1, y1, x2, y2] in PDF/Zotero coordinates.
+    pub bbox: [f64; 4],
+    /// Lines, top to bottom.
+    pub lines: Vec<StructuredLine>,
+}
+
+/// A line of text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredLine {
+    /// Bounding box [x1, y1, x2, y2] in PDF/Zotero coordinates.
+    pub bbox: [f64; 4],
+    /// Runs of text in the same font, left to right.
+    pub spans: Vec<StructuredSpan>,
+}
+
+/// A run of text in one font and size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredSpan {
+    /// The text of the run.
+    pub text: String,
+    /// Font name without the subset prefix (e.g. "NimbusRomNo9L-Medi").
+    pub font: String,
+    /// Font size in points.
+    pub size: f64,
+    /// Whether the font is bold.
+    pub bold: bool,
+    /// Whether the font is italic or oblique.
+    pub italic: bool,
+    /// Whether the font is monospaced.
+    pub monospaced: bool,
+    /// Bounding box [x1, y1, x2, y2] in PDF/Zotero coordinates.
+    pub bbox: [f64; 4],
+}
+
+impl StructuredBlock {
+    /// The text of the block, with lines separated by line breaks.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(StructuredLine::text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl StructuredLine {
+    /// The text of the line.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+/// The font of a glyph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct FontStyle {
+    pub(super) name: String,
+    pub(super) bold: bool,
+    pub(super) italic: bool,
+    pub(super) monospaced: bool,
+}
+
+impl FontStyle {
+    /// Describe a font from its name and flags.
+    ///
+    /// The subset prefix ("ABCDEF+") is removed, and weight and slant are
+    /// also read from the name, since many embedded fonts have no flags.
+    pub(super) fn new(name: &str, bold: bool, italic: bool, monospaced: bool) -> Self {
+        let name = match name.split_once('+') {
+            Some((prefix, rest))
+                if prefix.len() == 6 && prefix.chars().all(|c| c.is_ascii_uppercase()) =>
+            {
+                rest
+            }
+            _ => name,
+        };
+        let lower = name.to_lowercase();
+        let has = |parts: &[&str]| parts.iter().any(|part| lower.contains(part));
+        // TeX's Computer Modern fonts name their style in the family: CMBX is
+        // bold, CMTI, CMMI and CMSL are slanted, CMTT is typewriter.
+        let tex = |families: &[&str]| families.iter().any(|family| lower.starts_with(family));
+        // URW fonts name their bold weight "Medi" ("NimbusRomNo9L-Medi",
+        // "-MediItal"), unlike the lighter "Medium" of other families.
+        let medi = lower
+            .rsplit_once(['-', ','])
+            .is_some_and(|(_, style)| style.starts_with("medi") && !style.starts_with("medium"));
+
+        Self {
+            name: name.to_string(),
+            bold: bold
+                || has(&["bold", "black", "heavy", "demi"])
+                || medi
+                || tex(&["cmbx", "cmb10"]),
+            italic: italic || has(&["italic", "oblique"]) || tex(&["cmti", "cmmi", "cmsl"]),
+            monospaced: monospaced || has(&["mono", "courier"]) || tex(&["cmtt"]),
+        }
+    }
+}
+
+/// Append a character to a line, extending the last span if the style matches.
+///
+/// A character without a font (such as a space MuPDF inserted between
+/// words) continues the current span.
+pub(super) fn push_char(
+    spans: &mut Vec<StructuredSpan>,
+    ch: char,
+    rect: [f64; 4],
+    size: f64,
+    font: Option<&FontStyle>,
+) {
+    if let Some(last) = spans.last_mut() {
+        let same_style = font.is_none_or(|font| {
+            last.font == font.name
+                && last.bold == font.bold
+                && last.italic == font.italic
+                && (last.size - size).abs() < 0.05
+        });
+        if same_style {
+            last.text.push(ch);
+            last.bbox = union(last.bbox, rect);
+            return;
+        }
+    }
+
+    let font = font.cloned().unwrap_or_default();
+    spans.push(StructuredSpan {
+        text: ch.to_string(),
+        font: font.name,
+        size,
+        bold: font.bold,
+        italic: font.italic,
+        monospaced: font.monospaced,
+        bbox: rect,
+    });
+}
+
+/// Records the font of every glyph a page draws, by glyph origin.
+#[derive(Default)]
+struct FontRecorder {
+    fonts: Vec<FontStyle>,
+    /// Glyph origins, bucketed by whole point, with the index of their font.
+    glyphs: HashMap<(i32, i32), Vec<(Point, usize)>>,
+}
+
+impl FontRecorder {
+    fn record(&mut self, text: &Text, ctm: &Matrix) {
+        for span in text.spans() {
+            let font = span.font();
+            let style = FontStyle::new(
+                font.name(),
+                font.is_bold(),
+                font.is_italic(),
+                font.is_monospaced(),
+            );
+            let index = match self.fonts.iter().position(|f| *f == style) {
+                Some(index) => index,
+                None => {
+                    self.fonts.push(style);
+                    self.fonts.len() - 1
+                }
+            };
+
+            for item in span.items() {
+                let origin = Point::new(item.x(), item.y()).transform(ctm);
+                self.glyphs
+                    .entry((origin.x.round() as i32, origin.y.round() as i32))
+                    .or_default()
+                    .push((origin, index));
+            }
+        }
+    }
+
+    /// The font of the glyph drawn closest to an origin.
+    fn font_at(&self, origin: Point) -> Option<&FontStyle> {
+        let (x, y) = (origin.x.round() as i32, origin.y.round() as i32);
+        let mut best: Option<(f32, usize)> = None;
+        for key in [x - 1, x, x + 1]
+            .into_iter()
+            .flat_map(|x| [(x, y - 1), (x, y), (x, y + 1)])
+        {
+            for &(glyph, index) in self.glyphs.get(&key).into_iter().flatten() {
+                let distance = (glyph.x - origin.x).abs().max((glyph.y - origin.y).abs());
+                if distance <= ORIGIN_TOLERANCE && best.is_none_or(|(d, _)| distance < d) {
+                    best = Some((distance, index));
+                }
+            }
+        }
+        best.map(|(_, index)| &self.fonts[index])
+    }
+}
+
+impl NativeDevice for FontRecorder {
+    fn fill_text(
+        &mut self,
+        text: &Text,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        self.record(text, &cmt);
+    }
+
+    fn stroke_text(
+        &mut self,
+        text: &Text,
+        _stroke_state: &StrokeState,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        self.record(text, &cmt);
+    }
+
+    fn clip_text(&mut self, text: &Text, cmt: Matrix, _scissor: Rect) {
+        self.record(text, &cmt);
+    }
+
+    fn ignore_text(&mut self, text: &Text, cmt: Matrix) {
+        self.record(text, &cmt);
+    }
+}
+
+/// Read the structured text of a page.
+pub(super) fn structured_page(doc: &Document, page_num: usize) -> Result<StructuredPage> {
+    let page = doc
+        .load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+
+    let bounds = page
+        .bounds()
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to get page bounds: {}", e)))?;
+    let width = (bounds.x1 - bounds.x0) as f64;
+    let height = (bounds.y1 - bounds.y0) as f64;
+
+    let mut recorder = FontRecorder::default();
+    {
+        let device = Device::from_native(&mut recorder)
+            .map_err(|e| ZoteroClientError::Pdf(format!("Failed to create font device: {}", e)))?;
+        page.run(&device, &Matrix::IDENTITY).map_err(|e| {
+            ZoteroClientError::Pdf(format!("Failed to read fonts of page {}: {}", page_num, e))
+        })?;
+    }
+
+    let text_page = page.to_text_page(TextPageOptions::empty()).map_err(|e| {
+        ZoteroClientError::Pdf(format!(
+            "Failed to extract text from page {}: {}",
+            page_num, e
+        ))
+    })?;
+
+    // Transform to PDF/Zotero coords (origin bottom-left)
+    let to_pdf = |r: Rect| {
+        [
+            r.x0 as f64,
+            height - r.y1 as f64,
+            r.x1 as f64,
+            height - r.y0 as f64,
+        ]
+    };
+    let quad_to_pdf = |q: Quad| {
+        to_pdf(Rect::new(
+            q.ul.x.min(q.ll.x),
+            q.ul.y.min(q.ur.y),
+            q.ur.x.max(q.lr.x),
+            q.ll.y.max(q.lr.y),
+        ))
+    };
+
+    let mut blocks = Vec::new();
+    for block in text_page.blocks() {
+        if block.r#type() != TextBlockType::Text {
+            continue;
+        }
+
+        let mut lines = Vec::new();
+        for line in block.lines() {
+            let mut spans = Vec::new();
+            for ch in line.chars() {
+                let Some(c) = ch.char() else {
+                    continue;
+                };
+                let font = recorder.font_at(ch.origin());
+                push_char(
+                    &mut spans,
+                    c,
+                    quad_to_pdf(ch.quad()),
+                    ch.size() as f64,
+                    font,
+                );
+            }
+            if !spans.is_empty() {
+                lines.push(StructuredLine {
+                    bbox: to_pdf(line.bounds()),
+                    spans,
+                });
+            }
+        }
+
+        if !lines.is_empty() {
+            blocks.push(StructuredBlock {
+                bbox: to_pdf(block.bounds()),
+                lines,
+            });
+        }
+    }
+
+    Ok(StructuredPage {
+        page: page_num as u32,
+        width,
+        height,
+        blocks,
+    })
+}
+
+/// Extract the text of a page as blocks, lines and spans with font information.
+///
+/// The result serializes to JSON, so tools can find headings (larger or
+/// bold spans), code (monospaced spans) and equations without reading the
+/// PDF again.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_num` - Zero-based page number
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::extract_structured_text;
+///
+/// let page = extract_structured_text("/path/to/file.pdf", 0)?;
+/// for block in &page.blocks {
+///     for line in &block.lines {
+///         for span in &line.spans {
+///             if span.bold {
+///                 println!("{} ({} {}pt)", span.text, span.font, span.size);
+///             }
+///         }
+///     }
+/// }
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn extract_structured_text<P: AsRef<Path>>(path: P, page_num: usize) -> Result<StructuredPage> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    structured_page(&doc, page_num)
+}
@@ -0,0 +1,695 @@
+//! Table structure from text positions and ruling lines.
+//!
+//! A table is read in three steps. The page is run through a device that
+//! records its ruling lines: stroked horizontal and vertical segments and
+//! filled rectangles thin enough to be drawn lines. Without a given area,
+//! the table is taken to be the largest stack of horizontal rules, as drawn
+//! by booktabs or a full grid. The words in the area are then grouped into
+//! rows by their vertical position and into columns by the vertical rules
+//! or, for tables without them, by the gaps that no row has text in.
+
+use std::path::Path;
+
+use mupdf::{
+    ColorParams, Colorspace, Device, Document, Matrix, NativeDevice, PathWalker, Point, StrokeState,
+};
+use serde::{Deserialize, Serialize};
+
+use super::anchor::{page_text, PageText};
+use super::union;
+use crate::error::{Result, ZoteroClientError};
+
+/// Shortest segment taken for a ruling line, in points.
+const MIN_RULE_LENGTH: f64 = 5.0;
+
+/// Thickest filled rectangle taken for a ruling line, in points.
+const MAX_RULE_THICKNESS: f64 = 2.0;
+
+/// Largest deviation from horizontal or vertical of a ruling line, in points.
+const AXIS_TOLERANCE: f64 = 0.5;
+
+/// Closest two vertical rules may be and still bound separate columns, in points.
+const RULE_MERGE_DISTANCE: f64 = 2.0;
+
+/// Least fraction of the table's height a vertical rule must span to separate columns.
+const MIN_COLUMN_RULE_SPAN: f64 = 0.5;
+
+/// Least fraction of the table's width a horizontal rule must span to separate rows.
+const MIN_ROW_RULE_SPAN: f64 = 0.5;
+
+/// Gap between two characters of a line, relative to their height, that splits a word.
+const WORD_GAP: f64 = 0.3;
+
+/// Gap between two words of a line, relative to their height, that splits a cell.
+const CELL_GAP: f64 = 0.5;
+
+/// Margin added around the horizontal rules of a table, in points.
+const AREA_MARGIN: f64 = 1.0;
+
+/// A table read from a PDF page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    /// Zero-based page number.
+    pub page: u32,
+    /// Area of the table, in PDF/Zotero coordinates (origin bottom-left).
+    pub rect: [f64; 4],
+    /// Cell text, row by row; every row has the same number of cells.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Number of columns.
+    pub fn columns(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// Format the table as a Markdown pipe table, with the first row as the header.
+    pub fn to_markdown(&self) -> String {
+        let Some((header, body)) = self.rows.split_first() else {
+            return String::new();
+        };
+
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+
+        let mut out = row(header);
+        out.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+        for cells in body {
+            out.push_str(&row(cells));
+        }
+        out
+    }
+
+    /// Format the table as CSV, quoting cells as RFC 4180 requires.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        for cells in &self.rows {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect();
+            out.push_str(&cells.join(","));
+            out.push_str("\r\n");
+        }
+        out
+    }
+}
+
+/// A horizontal or vertical ruling line, in PDF/Zotero coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Rule {
+    pub(super) horizontal: bool,
+    /// The y of a horizontal rule or the x of a vertical one.
+    pub(super) position: f64,
+    /// Where the rule starts and ends along its direction.
+    pub(super) start: f64,
+    pub(super) end: f64,
+}
+
+impl Rule {
+    /// A rule from one point to another, if the segment is axis-aligned and long enough.
+    pub(super) fn between(a: (f64, f64), b: (f64, f64)) -> Option<Self> {
+        let (dx, dy) = ((b.0 - a.0).abs(), (b.1 - a.1).abs());
+        let rule = if dy <= AXIS_TOLERANCE {
+            Rule {
+                horizontal: true,
+                position: (a.1 + b.1) / 2.0,
+                start: a.0.min(b.0),
+                end: a.0.max(b.0),
+            }
+        } else if dx <= AXIS_TOLERANCE {
+            Rule {
+                horizontal: false,
+                position: (a.0 + b.0) / 2.0,
+                start: a.1.min(b.1),
+                end: a.1.max(b.1),
+            }
+        } else {
+            return None;
+        };
+        (rule.length() >= MIN_RULE_LENGTH).then_some(rule)
+    }
+
+    /// The rule a filled rectangle draws, if it is thin enough to be one.
+    pub(super) fn from_rect(rect: [f64; 4]) -> Option<Self> {
+        let (width, height) = (rect[2] - rect[0], rect[3] - rect[1]);
+        if width.min(height) > MAX_RULE_THICKNESS {
+            return None;
+        }
+        let (x, y) = ((rect[0] + rect[2]) / 2.0, (rect[1] + rect[3]) / 2.0);
+        if width >= height {
+            Rule::between((rect[0], y), (rect[2], y))
+        } else {
+            Rule::between((x, rect[1]), (x, rect[3]))
+        }
+    }
+
+    fn length(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// A word of the page and its bounding box, in PDF/Zotero coordinates.
+#[derive(Debug, Clone)]
+struct Word {
+    text: String,
+    rect: [f64; 4],
+}
+
+impl Word {
+    fn height(&self) -> f64 {
+        self.rect[3] - self.rect[1]
+    }
+
+    fn center_x(&self) -> f64 {
+        (self.rect[0] + self.rect[2]) / 2.0
+    }
+
+    fn center_y(&self) -> f64 {
+        (self.rect[1] + self.rect[3]) / 2.0
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
+
+/// Split the lines of a page into words, at whitespace and at gaps between characters.
+fn page_words(page: &PageText) -> Vec<Word> {
+    let mut words = Vec::new();
+    for line in &page.lines {
+        let mut current: Option<Word> = None;
+        for c in line {
+            if c.ch.is_whitespace() {
+                words.extend(current.take());
+                continue;
+            }
+            if let Some(word) = &current {
+                let height = (c.rect[3] - c.rect[1]).max(word.height());
+                if c.rect[0] - word.rect[2] > WORD_GAP * height {
+                    words.extend(current.take());
+                }
+            }
+            match &mut current {
+                Some(word) => {
+                    word.text.push(c.ch);
+                    word.rect = union(word.rect, c.rect);
+                }
+                None => {
+                    current = Some(Word {
+                        text: c.ch.to_string(),
+                        rect: c.rect,
+                    })
+                }
+            }
+        }
+        words.extend(current);
+    }
+    words
+}
+
+/// Group words into text lines by their vertical position, top to bottom,
+/// with the words of each line left to right.
+fn text_lines(mut words: Vec<Word>) -> Vec<Vec<Word>> {
+    words.sort_by(|a, b| b.rect[3].total_cmp(&a.rect[3]));
+
+    let mut lines: Vec<(f64, f64, Vec<Word>)> = Vec::new();
+    for word in words {
+        let center = word.center_y();
+        match lines
+            .iter_mut()
+            .find(|(bottom, top, _)| center >= *bottom && center <= *top)
+        {
+            Some(line) => line.2.push(word),
+            None => lines.push((word.rect[1], word.rect[3], vec![word])),
+        }
+    }
+
+    lines.sort_by(|a, b| b.1.total_cmp(&a.1));
+    lines
+        .into_iter()
+        .map(|(_, _, mut words)| {
+            words.sort_by(|a, b| a.rect[0].total_cmp(&b.rect[0]));
+            words
+        })
+        .collect()
+}
+
+/// Join the words of a line that are closer than `gap` into cells.
+fn line_chunks(words: &[Word], gap: f64) -> Vec<Word> {
+    let mut chunks: Vec<Word> = Vec::new();
+    for word in words {
+        match chunks.last_mut() {
+            Some(chunk) if word.rect[0] - chunk.rect[2] < gap => {
+                chunk.text.push(' ');
+                chunk.text.push_str(&word.text);
+                chunk.rect = union(chunk.rect, word.rect);
+            }
+            _ => chunks.push(word.clone()),
+        }
+    }
+    chunks
+}
+
+/// Column separators from the vertical rules inside the area.
+fn rule_separators(rules: &[Rule], area: [f64; 4]) -> Vec<f64> {
+    let height = area[3] - area[1];
+    let mut xs: Vec<f64> = rules
+        .iter()
+        .filter(|r| {
+            !r.horizontal
+                && r.position > area[0] + RULE_MERGE_DISTANCE
+                && r.position < area[2] - RULE_MERGE_DISTANCE
+                && r.end.min(area[3]) - r.start.max(area[1]) >= MIN_COLUMN_RULE_SPAN * height
+        })
+        .map(|r| r.position)
+        .collect();
+    xs.sort_by(f64::total_cmp);
+    xs.dedup_by(|b, a| *b - *a < RULE_MERGE_DISTANCE);
+    xs
+}
+
+/// Column separators from the gaps between the cells of the fullest rows.
+fn gap_separators(chunks: &[Vec<Word>]) -> Vec<f64> {
+    let counts: Vec<f64> = chunks
+        .iter()
+        .map(Vec::len)
+        .filter(|&n| n >= 2)
+        .map(|n| n as f64)
+        .collect();
+    if counts.is_empty() {
+        return Vec::new();
+    }
+    let typical = median(counts);
+
+    let mut spans: Vec<(f64, f64)> = chunks
+        .iter()
+        .filter(|row| row.len() as f64 >= typical)
+        .flatten()
+        .map(|chunk| (chunk.rect[0], chunk.rect[2]))
+        .collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut separators = Vec::new();
+    let mut end = f64::NEG_INFINITY;
+    for (start, stop) in spans {
+        if start > end && end.is_finite() {
+            separators.push((start + end) / 2.0);
+        }
+        end = end.max(stop);
+    }
+    separators
+}
+
+/// Index of the column an x position falls in.
+fn column_at(separators: &[f64], x: f64) -> usize {
+    separators.iter().take_while(|&&s| x > s).count()
+}
+
+/// Whether a horizontal rule inside the area lies between two text lines.
+fn ruled_between(rules: &[f64], upper: &[Word], lower: &[Word]) -> bool {
+    let bottom = upper
+        .iter()
+        .map(|w| w.rect[1])
+        .fold(f64::INFINITY, f64::min);
+    let top = lower
+        .iter()
+        .map(|w| w.rect[3])
+        .fold(f64::NEG_INFINITY, f64::max);
+    rules.iter().any(|&y| y <= bottom && y >= top)
+}
+
+/// Read the cells of the table in `area` from the page's text and ruling lines.
+///
+/// Returns `None` if the area has fewer than two rows or two columns of text.
+pub(super) fn build_table(
+    page: &PageText,
+    rules: &[Rule],
+    area: [f64; 4],
+) -> Option<Vec<Vec<String>>> {
+    let words: Vec<Word> = page_words(page)
+        .into_iter()
+        .filter(|w| {
+            let (x, y) = (w.center_x(), w.center_y());
+            x >= area[0] && x <= area[2] && y >= area[1] && y <= area[3]
+        })
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    let gap = CELL_GAP * median(words.iter().map(Word::height).collect());
+    let lines = text_lines(words);
+
+    let separators = rule_separators(rules, area);
+    let grid = !separators.is_empty();
+
+    // In a grid, a cell may wrap over several text lines; only the
+    // horizontal rules separate its rows.
+    let width = area[2] - area[0];
+    let row_rules: Vec<f64> = rules
+        .iter()
+        .filter(|r| {
+            r.horizontal && r.end.min(area[2]) - r.start.max(area[0]) >= MIN_ROW_RULE_SPAN * width
+        })
+        .map(|r| r.position)
+        .collect();
+    let mut rows: Vec<Vec<Vec<Word>>> = Vec::new();
+    for line in lines {
+        let continues = grid
+            && !row_rules.is_empty()
+            && rows
+                .last()
+                .and_then(|row| row.last())
+                .is_some_and(|previous| !ruled_between(&row_rules, previous, &line));
+        if continues {
+            rows.last_mut().unwrap().push(line);
+        } else {
+            rows.push(vec![line]);
+        }
+    }
+
+    // In rows without rules, the cells of the fullest rows line up into
+    // columns; rows with fewer cells span columns or leave cells empty.
+    let (separators, rows) = if grid {
+        (separators, rows)
+    } else {
+        let rows: Vec<Vec<Vec<Word>>> = rows
+            .iter()
+            .map(|row| row.iter().map(|line| line_chunks(line, gap)).collect())
+            .collect();
+        let chunks: Vec<Vec<Word>> = rows.iter().flatten().cloned().collect();
+        (gap_separators(&chunks), rows)
+    };
+    if separators.is_empty() {
+        return None;
+    }
+
+    let columns = separators.len() + 1;
+    let mut table: Vec<Vec<String>> = Vec::new();
+    for row in rows {
+        let mut cells = vec![String::new(); columns];
+        for piece in row.iter().flatten() {
+            // A cell that crosses a separator spans columns and goes in the first.
+            let crosses = separators
+                .iter()
+                .any(|&s| piece.rect[0] < s && piece.rect[2] > s);
+            let x = if crosses {
+                piece.rect[0]
+            } else {
+                piece.center_x()
+            };
+            let cell = &mut cells[column_at(&separators, x)];
+            if !cell.is_empty() {
+                cell.push(' ');
+            }
+            cell.push_str(&piece.text);
+        }
+        table.push(cells);
+    }
+
+    table.retain(|cells| cells.iter().any(|c| !c.is_empty()));
+    let used: Vec<bool> = (0..columns)
+        .map(|i| table.iter().any(|cells| !cells[i].is_empty()))
+        .collect();
+    for cells in &mut table {
+        let mut i = 0;
+        cells.retain(|_| {
+            i += 1;
+            used[i - 1]
+        });
+    }
+
+    let columns = used.iter().filter(|&&u| u).count();
+    (table.len() >= 2 && columns >= 2).then_some(table)
+}
+
+/// The area of the table drawn by the most horizontal rules that overlap
+/// each other horizontally, with the vertical rules that meet them.
+pub(super) fn table_area(rules: &[Rule]) -> Option<[f64; 4]> {
+    let mut horizontal: Vec<&Rule> = rules.iter().filter(|r| r.horizontal).collect();
+    horizontal.sort_by(|a, b| b.position.total_cmp(&a.position));
+
+    // Rules belong to a group if they overlap its extent by half the shorter one.
+    let mut groups: Vec<(f64, f64, Vec<&Rule>)> = Vec::new();
+    for rule in horizontal {
+        let group = groups.iter_mut().find(|(start, end, _)| {
+            let overlap = rule.end.min(*end) - rule.start.max(*start);
+            overlap >= 0.5 * rule.length().min(end - start)
+        });
+        match group {
+            Some((start, end, members)) => {
+                *start = start.min(rule.start);
+                *end = end.max(rule.end);
+                members.push(rule);
+            }
+            None => groups.push((rule.start, rule.end, vec![rule])),
+        }
+    }
+
+    let (start, end, members) = groups
+        .into_iter()
+        .filter(|(_, _, members)| members.len() >= 2)
+        .max_by(|a, b| {
+            a.2.len()
+                .cmp(&b.2.len())
+                .then((a.1 - a.0).total_cmp(&(b.1 - b.0)))
+        })?;
+
+    let bottom = members
+        .iter()
+        .map(|r| r.position)
+        .fold(f64::INFINITY, f64::min);
+    let top = members
+        .iter()
+        .map(|r| r.position)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut area = [start, bottom, end, top];
+    for rule in rules.iter().filter(|r| !r.horizontal) {
+        let within = rule.position >= start - RULE_MERGE_DISTANCE
+            && rule.position <= end + RULE_MERGE_DISTANCE;
+        if within && rule.start <= top && rule.end >= bottom {
+            area = union(area, [rule.position, rule.start, rule.position, rule.end]);
+        }
+    }
+
+    Some([
+        area[0] - AREA_MARGIN,
+        area[1] - AREA_MARGIN,
+        area[2] + AREA_MARGIN,
+        area[3] + AREA_MARGIN,
+    ])
+}
+
+/// Collects the line segments of a path, in device coordinates.
+struct SegmentCollector {
+    ctm: Matrix,
+    start: Option<Point>,
+    current: Option<Point>,
+    segments: Vec<(Point, Point)>,
+}
+
+impl SegmentCollector {
+    fn new(ctm: Matrix) -> Self {
+        Self {
+            ctm,
+            start: None,
+            current: None,
+            segments: Vec::new(),
+        }
+    }
+
+    fn segment_to(&mut self, x: f32, y: f32) {
+        let point = Point::new(x, y).transform(&self.ctm);
+        if let Some(from) = self.current {
+            self.segments.push((from, point));
+        }
+        self.current = Some(point);
+    }
+}
+
+impl PathWalker for SegmentCollector {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = Point::new(x, y).transform(&self.ctm);
+        self.start = Some(point);
+        self.current = Some(point);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.segment_to(x, y);
+    }
+
+    fn curve_to(&mut self, _cx1: f32, _cy1: f32, _cx2: f32, _cy2: f32, ex: f32, ey: f32) {
+        // Curves are not rules; move on without a segment.
+        self.current = Some(Point::new(ex, ey).transform(&self.ctm));
+    }
+
+    fn close(&mut self) {
+        if let (Some(from), Some(to)) = (self.current, self.start) {
+            self.segments.push((from, to));
+        }
+        self.current = self.start;
+    }
+}
+
+/// Records the ruling lines a page draws, in PDF/Zotero coordinates.
+struct RuleRecorder {
+    height: f64,
+    rules: Vec<Rule>,
+}
+
+impl RuleRecorder {
+    fn to_pdf(&self, point: Point) -> (f64, f64) {
+        (point.x as f64, self.height - point.y as f64)
+    }
+}
+
+impl NativeDevice for RuleRecorder {
+    fn fill_path(
+        &mut self,
+        path: &mupdf::Path,
+        _even_odd: bool,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        let mut collector = SegmentCollector::new(cmt);
+        if path.walk(&mut collector).is_err() || collector.segments.is_empty() {
+            return;
+        }
+        // Filled paths are rules only if they are thin rectangles.
+        let axis_aligned = collector.segments.iter().all(|(a, b)| {
+            (a.x - b.x).abs() as f64 <= AXIS_TOLERANCE || (a.y - b.y).abs() as f64 <= AXIS_TOLERANCE
+        });
+        if !axis_aligned {
+            return;
+        }
+        let mut rect: Option<[f64; 4]> = None;
+        for &(a, b) in &collector.segments {
+            let (a, b) = (self.to_pdf(a), self.to_pdf(b));
+            let r = [a.0.min(b.0), a.1.min(b.1), a.0.max(b.0), a.1.max(b.1)];
+            rect = Some(rect.map_or(r, |rect| union(rect, r)));
+        }
+        self.rules.extend(rect.and_then(Rule::from_rect));
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &mupdf::Path,
+        _stroke_state: &StrokeState,
+        cmt: Matrix,
+        _color_space: &Colorspace,
+        _color: &[f32],
+        _alpha: f32,
+        _cp: ColorParams,
+    ) {
+        let mut collector = SegmentCollector::new(cmt);
+        if path.walk(&mut collector).is_err() {
+            return;
+        }
+        for &(a, b) in &collector.segments {
+            self.rules
+                .extend(Rule::between(self.to_pdf(a), self.to_pdf(b)));
+        }
+    }
+}
+
+/// Read the ruling lines of a page from an open document.
+fn page_rules(doc: &Document, page_num: usize, height: f64) -> Result<Vec<Rule>> {
+    let page = doc
+        .load_page(page_num as i32)
+        .map_err(|e| ZoteroClientError::Pdf(format!("Failed to load page {}: {}", page_num, e)))?;
+
+    let mut recorder = RuleRecorder {
+        height,
+        rules: Vec::new(),
+    };
+    {
+        let device = Device::from_native(&mut recorder)
+            .map_err(|e| ZoteroClientError::Pdf(format!("Failed to create rule device: {}", e)))?;
+        page.run(&device, &Matrix::IDENTITY).map_err(|e| {
+            ZoteroClientError::Pdf(format!(
+                "Failed to read ruling lines of page {}: {}",
+                page_num, e
+            ))
+        })?;
+    }
+    Ok(recorder.rules)
+}
+
+/// Extract the rows and columns of a table on a PDF page.
+///
+/// Rows come from the vertical position of the text; columns come from the
+/// vertical rules of a grid or, for tables drawn with horizontal rules only
+/// (booktabs) or none at all, from the gaps between the cells. In a grid, a
+/// cell may wrap over several lines.
+///
+/// # Arguments
+///
+/// * `path` - Path to the PDF file
+/// * `page_num` - Zero-based page number
+/// * `rect` - Area of the table, in PDF/Zotero coordinates (origin bottom-left).
+///   If `None`, the table drawn by the most horizontal rules on the page is read.
+///
+/// # Example
+///
+/// ```no_run
+/// use zotero_client::pdf::extract_table;
+///
+/// let table = extract_table("/path/to/file.pdf", 4, None)?;
+/// println!("{}", table.to_markdown());
+/// # Ok::<(), zotero_client::error::ZoteroClientError>(())
+/// ```
+pub fn extract_table<P: AsRef<Path>>(
+    path: P,
+    page_num: usize,
+    rect: Option<[f64; 4]>,
+) -> Result<Table> {
+    let path = path.as_ref();
+
+    let doc = Document::open(path).map_err(|e| {
+        ZoteroClientError::Pdf(format!("Failed to open PDF '{}': {}", path.display(), e))
+    })?;
+
+    let text = page_text(&doc, page_num)?;
+    let rules = page_rules(&doc, page_num, text.height)?;
+
+    let area = match rect {
+        Some(rect) => rect,
+        None => table_area(&rules).ok_or_else(|| {
+            ZoteroClientError::Pdf(format!(
+                "No ruled table found on page {}; pass the table's rect",
+                page_num
+            ))
+        })?,
+    };
+
+    let rows = build_table(&text, &rules, area).ok_or_else(|| {
+        ZoteroClientError::Pdf(format!(
+            "No rows and columns found in the table area on page {}",
+            page_num
+        ))
+    })?;
+
+    Ok(Table {
+        page: page_num as u32,
+        rect: area,
+        rows,
+    })
+}
@@ -0,0 +1,147 @@
+//! Unit tests for text anchoring.
+
+use crate::pdf::{anchor_in_pages, normalize_text, PageText, PositionedChar};
+
+/// Build a page from lines of text, giving each character a 5pt wide box.
+pub(super) fn page(page: u32, lines: &[&str]) -> PageText {
+    PageText {
+        page,
+        height: 842.0,
+        lines: lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                let top = 800.0 - 12.0 * row as f64;
+                line.chars()
+                    .enumerate()
+                    .map(|(col, ch)| PositionedChar {
+                        ch,
+                        rect: [5.0 * col as f64, top - 10.0, 5.0 * (col + 1) as f64, top],
+                    })
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_normalize_text_collapses_whitespace() {
+    assert_eq!(normalize_text("  one\t two\n\nthree  "), "one two three");
+}
+
+#[test]
+fn test_normalize_text_dehyphenates_line_breaks() {
+    assert_eq!(normalize_text("hyphen-\nated"), "hyphenated");
+    // A hyphen followed by a plain space is kept
+    assert_eq!(
+        normalize_text("pre- and post-processing"),
+        "pre- and post-processing"
+    );
+}
+
+#[test]
+fn test_anchor_single_line() {
+    let pages = [page(0, &["The quick brown fox jumps"])];
+
+    let anchor = anchor_in_pages(&pages, "brown fox").unwrap();
+
+    assert_eq!(anchor.text, "brown fox");
+    assert_eq!(anchor.segments.len(), 1);
+    assert_eq!(anchor.segments[0].page, 0);
+    assert_eq!(anchor.segments[0].rects, vec![[50.0, 790.0, 95.0, 800.0]]);
+}
+
+#[test]
+fn test_anchor_is_case_and_whitespace_insensitive() {
+    let pages = [page(0, &["The quick   brown fox"])];
+
+    let anchor = anchor_in_pages(&pages, "QUICK\n brown").unwrap();
+
+    assert_eq!(anchor.text, "quick brown");
+}
+
+#[test]
+fn test_anchor_across_lines_gives_one_rect_per_line() {
+    let pages = [page(0, &["first line ends", "here and continues"])];
+
+    let anchor = anchor_in_pages(&pages, "line ends here").unwrap();
+
+    assert_eq!(anchor.segments.len(), 1);
+    assert_eq!(anchor.segments[0].text, "line ends here");
+    assert_eq!(anchor.segments[0].rects.len(), 2);
+}
+
+#[test]
+fn test_anchor_across_hyphenated_line_end() {
+    let pages = [page(0, &["a carefully hyphen-", "ated word"])];
+
+    let anchor = anchor_in_pages(&pages, "hyphenated word").unwrap();
+    assert_eq!(anchor.text, "hyphenated word");
+    assert_eq!(anchor.segments[0].rects.len(), 2);
+
+    // Text copied with the line break intact matches too
+    assert!(anchor_in_pages(&pages, "hyphen-\nated").is_some());
+}
+
+#[test]
+fn test_anchor_compound_split_at_line_end() {
+    let pages = [page(0, &["a state-of-the-", "art method"])];
+
+    assert!(anchor_in_pages(&pages, "state-of-the-art method").is_some());
+}
+
+#[test]
+fn test_anchor_across_page_break() {
+    let pages = [
+        page(3, &["intro text", "the passage starts"]),
+        page(4, &["and ends here", "more text"]),
+    ];
+
+    let anchor = anchor_in_pages(&pages, "passage starts and ends here").unwrap();
+
+    assert!(anchor.spans_pages());
+    assert_eq!(anchor.segments.len(), 2);
+    assert_eq!(anchor.segments[0].page, 3);
+    assert_eq!(anchor.segments[0].text, "passage starts");
+    assert_eq!(anchor.segments[1].page, 4);
+    assert_eq!(anchor.segments[1].text, "and ends here");
+}
+
+#[test]
+fn test_anchor_must_start_on_first_page() {
+    let pages = [page(0, &["nothing here"]), page(1, &["target text"])];
+
+    assert!(anchor_in_pages(&pages, "target text").is_none());
+}
+
+#[test]
+fn test_anchor_not_found() {
+    let pages = [page(0, &["some text"])];
+
+    assert!(anchor_in_pages(&pages, "missing").is_none());
+    assert!(anchor_in_pages(&pages, "   ").is_none());
+    assert!(anchor_in_pages(&[], "some").is_none());
+}
+
+#[test]
+fn test_anchor_folds_ligatures_quotes_and_diacritics() {
+    let pages = [page(
+        0,
+        &["an e\u{FB03}cient \u{201C}na\u{EF}ve\u{201D} approach"],
+    )];
+
+    let anchor = anchor_in_pages(&pages, "efficient \"naive\" approach").unwrap();
+
+    // The matched text is reported as it appears in the PDF
+    assert_eq!(
+        anchor.text,
+        "e\u{FB03}cient \u{201C}na\u{EF}ve\u{201D} approach"
+    );
+}
+
+#[test]
+fn test_anchor_ignores_dash_style() {
+    let pages = [page(0, &["from 1990\u{2013}2000 only"])];
+
+    assert!(anchor_in_pages(&pages, "1990 - 2000").is_some());
+}
@@ -0,0 +1,69 @@
+//! Unit tests for fuzzy anchoring.
+
+use super::anchor::page;
+use crate::pdf::fuzzy_anchor_in_pages;
+
+#[test]
+fn test_fuzzy_exact_match_scores_one() {
+    let pages = [page(0, &["The quick brown fox jumps"])];
+
+    let found = fuzzy_anchor_in_pages(&pages, "brown fox").unwrap();
+
+    assert_eq!(found.score, 1.0);
+    assert_eq!(found.anchor.text, "brown fox");
+}
+
+#[test]
+fn test_fuzzy_tolerates_ocr_noise() {
+    let pages = [page(
+        0,
+        &["Results show that the rnodel outperforms all baselines by a wide margin."],
+    )];
+
+    let found = fuzzy_anchor_in_pages(&pages, "the model outperforms all baselines").unwrap();
+
+    assert_eq!(found.anchor.text, "the rnodel outperforms all baselines");
+    assert!(found.score > 0.9 && found.score < 1.0);
+}
+
+#[test]
+fn test_fuzzy_tolerates_changed_word_across_lines() {
+    let pages = [page(
+        0,
+        &["we propose a novel frame-", "work for document analysis"],
+    )];
+
+    let found = fuzzy_anchor_in_pages(&pages, "a new framework for document analysis").unwrap();
+
+    assert_eq!(found.anchor.text, "a novel framework for document analysis");
+    assert_eq!(found.anchor.segments[0].rects.len(), 2);
+    assert!(found.score > 0.8);
+}
+
+#[test]
+fn test_fuzzy_unrelated_text_scores_low() {
+    let pages = [page(0, &["completely different content here"])];
+
+    let found = fuzzy_anchor_in_pages(&pages, "quantum entanglement experiment").unwrap();
+
+    assert!(found.score < 0.5);
+}
+
+#[test]
+fn test_fuzzy_must_start_on_first_page() {
+    let pages = [
+        page(2, &["nothing relevant"]),
+        page(3, &["target sentence"]),
+    ];
+
+    let found = fuzzy_anchor_in_pages(&pages, "target sentence").unwrap();
+
+    assert_eq!(found.anchor.segments[0].page, 2);
+    assert!(found.score < 1.0);
+}
+
+#[test]
+fn test_fuzzy_empty_input() {
+    assert!(fuzzy_anchor_in_pages(&[page(0, &["text"])], "  ").is_none());
+    assert!(fuzzy_anchor_in_pages(&[], "text").is_none());
+}
@@ -0,0 +1,200 @@
+//! Unit tests for outlines inferred from section headings.
+
+use crate::pdf::headings::{find_headings, infer_outline_from_pages};
+use crate::pdf::{resolve_section_to_pages, StructuredPage, StructuredSpan};
+
+use super::{span, text_block, text_line, text_page};
+
+const BODY: &str = "Body text set in the regular font of the paper, long enough to dominate.";
+
+fn styled(text: &str, size: f64, bold: bool) -> StructuredSpan {
+    StructuredSpan {
+        font: if bold { "Times-Bold" } else { "Times-Roman" }.to_string(),
+        size,
+        bold,
+        ..span(text, [0.0; 4])
+    }
+}
+
+fn heading(text: &str) -> Vec<StructuredSpan> {
+    vec![styled(text, 12.0, true)]
+}
+
+fn body(text: &str) -> Vec<StructuredSpan> {
+    vec![styled(text, 10.0, false)]
+}
+
+/// A page with one block per line, with a paragraph of body text after each line.
+fn page(page: u32, lines: Vec<Vec<StructuredSpan>>) -> StructuredPage {
+    let blocks = lines
+        .into_iter()
+        .flat_map(|spans| [spans, body(BODY)])
+        .map(|spans| text_block(vec![text_line(spans)]))
+        .collect();
+    text_page(page, blocks)
+}
+
+fn titles(pages: &[StructuredPage]) -> Vec<String> {
+    find_headings(pages).into_iter().map(|h| h.title).collect()
+}
+
+#[test]
+fn test_infer_outline_nests_numbered_headings() {
+    let pages = vec![
+        page(0, vec![heading("1 Introduction"), heading("2 Method")]),
+        page(
+            1,
+            vec![
+                heading("2.1 Data"),
+                heading("2.2. Model"),
+                heading("3 Results"),
+            ],
+        ),
+    ];
+    let outline = infer_outline_from_pages(&pages, 4);
+
+    assert!(outline.has_outline);
+    assert!(outline.inferred);
+    assert_eq!(outline.total_pages, 4);
+    let top: Vec<&str> = outline.items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(top, vec!["1 Introduction", "2 Method", "3 Results"]);
+    assert_eq!(outline.items[1].page, Some(0));
+    let sub: Vec<&str> = outline.items[1]
+        .children
+        .iter()
+        .map(|i| i.title.as_str())
+        .collect();
+    assert_eq!(sub, vec!["2.1 Data", "2.2. Model"]);
+    assert_eq!(outline.items[1].children[0].page, Some(1));
+}
+
+#[test]
+fn test_numbered_headings_must_be_in_order() {
+    let pages = vec![
+        page(0, vec![heading("1 Introduction"), heading("2 Method")]),
+        // A running header repeating the section and a bold table row
+        page(1, vec![heading("2 Method"), heading("1 Baseline model")]),
+        page(2, vec![heading("3 Results")]),
+    ];
+    assert_eq!(
+        titles(&pages),
+        vec!["1 Introduction", "2 Method", "3 Results"]
+    );
+}
+
+#[test]
+fn test_plain_numbered_lines_are_not_headings() {
+    let pages = vec![page(
+        0,
+        vec![
+            heading("1 Introduction"),
+            body("2 We propose a new method"),
+            // A table of contents entry
+            heading("2 Method 3"),
+        ],
+    )];
+    assert_eq!(titles(&pages), vec!["1 Introduction"]);
+}
+
+#[test]
+fn test_larger_font_counts_as_heading_style() {
+    let pages = vec![page(0, vec![vec![styled("1 Introduction", 14.0, false)]])];
+    assert_eq!(titles(&pages), vec!["1 Introduction"]);
+}
+
+#[test]
+fn test_run_in_heading_is_cut_at_body_text() {
+    let pages = vec![page(
+        0,
+        vec![
+            heading("3 Experiments"),
+            vec![
+                styled("3.1 Training.", 10.0, true),
+                styled(" We train for ten epochs.", 10.0, false),
+            ],
+        ],
+    )];
+    assert_eq!(titles(&pages), vec!["3 Experiments", "3.1 Training"]);
+}
+
+#[test]
+fn test_unnumbered_section_names() {
+    let pages = vec![
+        page(0, vec![heading("Abstract"), heading("Introduction")]),
+        page(
+            1,
+            vec![
+                body("References"),
+                heading("Our Contribution"),
+                heading("References"),
+            ],
+        ),
+        page(2, vec![heading("References")]),
+    ];
+    assert_eq!(
+        titles(&pages),
+        vec!["Abstract", "Introduction", "References"]
+    );
+}
+
+#[test]
+fn test_ieee_roman_headings_in_small_capitals() {
+    let pages = vec![page(
+        0,
+        vec![
+            body("I. INTRODUCTION"),
+            body("II. RELATED WORK"),
+            body("V. Smith and J. Doe"),
+        ],
+    )];
+    let headings = find_headings(&pages);
+    let titles: Vec<&str> = headings.iter().map(|h| h.title.as_str()).collect();
+    assert_eq!(titles, vec!["I. INTRODUCTION", "II. RELATED WORK"]);
+    assert!(headings.iter().all(|h| h.level == 1));
+}
+
+#[test]
+fn test_lettered_appendices_follow_numbered_sections() {
+    let pages = vec![
+        page(
+            0,
+            vec![
+                vec![styled("A Study of Graph Networks", 18.0, true)],
+                heading("1 Introduction"),
+            ],
+        ),
+        page(
+            5,
+            vec![
+                heading("A Proofs"),
+                heading("A.1 Lemma"),
+                heading("B Datasets"),
+            ],
+        ),
+    ];
+    let outline = infer_outline_from_pages(&pages, 8);
+    let top: Vec<&str> = outline.items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(top, vec!["1 Introduction", "A Proofs", "B Datasets"]);
+    assert_eq!(outline.items[1].children[0].title, "A.1 Lemma");
+}
+
+#[test]
+fn test_infer_outline_without_headings() {
+    let outline = infer_outline_from_pages(&[page(0, vec![body("Just text")])], 1);
+    assert!(!outline.has_outline);
+    assert!(outline.inferred);
+    assert!(outline.items.is_empty());
+}
+
+#[test]
+fn test_section_starting_on_the_same_page_as_the_next_keeps_its_page() {
+    let pages = vec![page(
+        2,
+        vec![heading("1 Introduction"), heading("2 Method")],
+    )];
+    let outline = infer_outline_from_pages(&pages, 10);
+    assert_eq!(
+        resolve_section_to_pages(&outline, "Introduction").unwrap(),
+        (2, 3)
+    );
+}
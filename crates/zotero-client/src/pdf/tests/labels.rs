@@ -0,0 +1,81 @@
+//! Unit tests for page labels.
+
+use crate::pdf::labels::decode_text_string;
+use crate::pdf::{label_for_page, PageLabelRange, PageLabelStyle};
+
+fn range(
+    start_page: u32,
+    style: Option<PageLabelStyle>,
+    prefix: &str,
+    first: u32,
+) -> PageLabelRange {
+    PageLabelRange {
+        start_page,
+        style,
+        prefix: prefix.to_string(),
+        first_number: first,
+    }
+}
+
+#[test]
+fn test_label_styles() {
+    assert_eq!(PageLabelStyle::Decimal.format(42), "42");
+    assert_eq!(PageLabelStyle::UpperRoman.format(1994), "MCMXCIV");
+    assert_eq!(PageLabelStyle::LowerRoman.format(4), "iv");
+    assert_eq!(PageLabelStyle::UpperLetters.format(1), "A");
+    assert_eq!(PageLabelStyle::UpperLetters.format(26), "Z");
+    assert_eq!(PageLabelStyle::UpperLetters.format(27), "AA");
+    assert_eq!(PageLabelStyle::LowerLetters.format(54), "bbb");
+}
+
+#[test]
+fn test_label_without_ranges_is_page_number() {
+    assert_eq!(label_for_page(&[], 0), "1");
+    assert_eq!(label_for_page(&[], 9), "10");
+}
+
+#[test]
+fn test_label_front_matter_and_body() {
+    let ranges = [
+        range(0, Some(PageLabelStyle::LowerRoman), "", 1),
+        range(3, Some(PageLabelStyle::Decimal), "", 1),
+    ];
+
+    assert_eq!(label_for_page(&ranges, 0), "i");
+    assert_eq!(label_for_page(&ranges, 2), "iii");
+    assert_eq!(label_for_page(&ranges, 3), "1");
+    assert_eq!(label_for_page(&ranges, 10), "8");
+}
+
+#[test]
+fn test_label_start_number_and_prefix() {
+    let ranges = [
+        range(0, Some(PageLabelStyle::Decimal), "", 215),
+        range(12, Some(PageLabelStyle::Decimal), "S", 1),
+        range(14, None, "Cover", 1),
+    ];
+
+    assert_eq!(label_for_page(&ranges, 0), "215");
+    assert_eq!(label_for_page(&ranges, 11), "226");
+    assert_eq!(label_for_page(&ranges, 13), "S2");
+    assert_eq!(label_for_page(&ranges, 15), "Cover");
+}
+
+#[test]
+fn test_label_before_first_range_is_page_number() {
+    let ranges = [range(2, Some(PageLabelStyle::Decimal), "", 1)];
+
+    assert_eq!(label_for_page(&ranges, 0), "1");
+    assert_eq!(label_for_page(&ranges, 2), "1");
+}
+
+#[test]
+fn test_decode_text_string() {
+    assert_eq!(decode_text_string(b"A-"), "A-");
+    assert_eq!(
+        decode_text_string(&[0xFE, 0xFF, 0x00, 0x53, 0x00, 0xE9]),
+        "Sé"
+    );
+    assert_eq!(decode_text_string(&[0xEF, 0xBB, 0xBF, b'x']), "x");
+    assert_eq!(decode_text_string(&[0xE9]), "é");
+}
@@ -0,0 +1,129 @@
+//! Unit tests for reading order and running text detection.
+
+use crate::pdf::{LayoutBlock, LayoutOptions, PageLayout};
+
+fn block(rect: [f64; 4], text: &str) -> LayoutBlock {
+    LayoutBlock {
+        rect,
+        lines: text.lines().map(str::to_string).collect(),
+    }
+}
+
+/// A US Letter page with blocks in the given (extraction) order.
+fn layout(blocks: Vec<LayoutBlock>) -> PageLayout {
+    PageLayout {
+        page: 0,
+        width: 612.0,
+        height: 792.0,
+        blocks,
+    }
+}
+
+fn order(layout: &PageLayout) -> Vec<&str> {
+    layout
+        .reading_order()
+        .into_iter()
+        .map(|b| b.lines[0].as_str())
+        .collect()
+}
+
+/// Two columns of two paragraphs, extracted row by row, under a title.
+fn two_columns() -> Vec<LayoutBlock> {
+    vec![
+        block([72.0, 700.0, 540.0, 720.0], "Title"),
+        block([72.0, 450.0, 300.0, 680.0], "Left 1"),
+        block([312.0, 450.0, 540.0, 680.0], "Right 1"),
+        block([72.0, 200.0, 300.0, 440.0], "Left 2"),
+        block([312.0, 200.0, 540.0, 440.0], "Right 2"),
+    ]
+}
+
+#[test]
+fn test_gutters_of_two_column_page() {
+    assert_eq!(layout(two_columns()).gutters(), vec![(300.0, 312.0)]);
+}
+
+#[test]
+fn test_reading_order_reads_columns_one_after_the_other() {
+    assert_eq!(
+        order(&layout(two_columns())),
+        vec!["Title", "Left 1", "Left 2", "Right 1", "Right 2"]
+    );
+}
+
+#[test]
+fn test_reading_order_wide_block_separates_column_sections() {
+    let mut blocks = two_columns();
+    // A full-width figure caption between the two rows of paragraphs
+    blocks.push(block([72.0, 442.0, 540.0, 448.0], "Figure 1: Overview."));
+    assert_eq!(
+        order(&layout(blocks)),
+        vec![
+            "Title",
+            "Left 1",
+            "Right 1",
+            "Figure 1: Overview.",
+            "Left 2",
+            "Right 2"
+        ]
+    );
+}
+
+#[test]
+fn test_reading_order_of_single_column_page_is_top_to_bottom() {
+    let layout = layout(vec![
+        block([72.0, 100.0, 540.0, 300.0], "Second"),
+        block([72.0, 400.0, 540.0, 700.0], "First"),
+    ]);
+    assert!(layout.gutters().is_empty());
+    assert_eq!(order(&layout), vec!["First", "Second"]);
+}
+
+#[test]
+fn test_short_blocks_side_by_side_are_not_columns() {
+    let layout = layout(vec![
+        block([72.0, 600.0, 540.0, 700.0], "Paragraph"),
+        block([72.0, 580.0, 200.0, 590.0], "Label"),
+        block([300.0, 580.0, 400.0, 590.0], "Value"),
+        block([72.0, 300.0, 540.0, 570.0], "Next paragraph"),
+    ]);
+    assert!(layout.gutters().is_empty());
+    assert_eq!(
+        order(&layout),
+        vec!["Paragraph", "Label", "Value", "Next paragraph"]
+    );
+}
+
+#[test]
+fn test_text_strips_running_headers_footers_and_page_numbers() {
+    let layout = layout(vec![
+        block(
+            [72.0, 740.0, 540.0, 750.0],
+            "Published as a conference paper",
+        ),
+        block([72.0, 100.0, 540.0, 700.0], "Body text\nsecond line"),
+        block([300.0, 30.0, 312.0, 40.0], "7"),
+    ]);
+
+    let stripped = layout.text(LayoutOptions {
+        strip_headers_footers: true,
+    });
+    assert_eq!(stripped, "Body text\nsecond line");
+
+    let full = layout.text(LayoutOptions::default());
+    assert_eq!(
+        full,
+        "Published as a conference paper\n\nBody text\nsecond line\n\n7"
+    );
+}
+
+#[test]
+fn test_long_or_overlapping_blocks_are_not_running_text() {
+    let layout = layout(vec![]);
+    // Reaches out of the bottom margin into the body
+    assert!(!layout.is_running_text(&block([72.0, 40.0, 540.0, 120.0], "Footnote")));
+    // A paragraph that happens to end in the margin
+    let long = "word ".repeat(40);
+    assert!(!layout.is_running_text(&block([72.0, 740.0, 540.0, 760.0], &long)));
+    assert!(layout.is_running_text(&block([72.0, 740.0, 540.0, 760.0], "Header")));
+}
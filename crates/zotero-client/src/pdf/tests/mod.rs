@@ -0,0 +1,326 @@
+//! Unit tests for the PDF module.
+
+use super::*;
+
+mod anchor;
+mod fuzzy;
+mod headings;
+mod labels;
+mod layout;
+mod position;
+mod references;
+mod search;
+mod sections;
+mod structure;
+mod tables;
+
+/// Height of the fixture pages built by [`text_page`].
+const PAGE_HEIGHT: f64 = 792.0;
+
+/// The rectangle of a 10pt high line from x1 to x2 whose top is `top`
+/// points below the top of a fixture page.
+fn line_rect(x1: f64, x2: f64, top: f64) -> [f64; 4] {
+    [x1, PAGE_HEIGHT - top - 10.0, x2, PAGE_HEIGHT - top]
+}
+
+/// A span of regular 10pt Times text; change the style with struct update syntax.
+fn span(text: &str, bbox: [f64; 4]) -> StructuredSpan {
+    StructuredSpan {
+        text: text.to_string(),
+        font: "Times-Roman".to_string(),
+        size: 10.0,
+        bold: false,
+        italic: false,
+        monospaced: false,
+        bbox,
+    }
+}
+
+/// A line bounded by its spans.
+fn text_line(spans: Vec<StructuredSpan>) -> StructuredLine {
+    let bbox = spans
+        .iter()
+        .map(|s| s.bbox)
+        .reduce(union)
+        .unwrap_or_default();
+    StructuredLine { bbox, spans }
+}
+
+/// A block bounded by its lines.
+fn text_block(lines: Vec<StructuredLine>) -> StructuredBlock {
+    let bbox = lines
+        .iter()
+        .map(|l| l.bbox)
+        .reduce(union)
+        .unwrap_or_default();
+    StructuredBlock { bbox, lines }
+}
+
+/// A US Letter page.
+fn text_page(page: u32, blocks: Vec<StructuredBlock>) -> StructuredPage {
+    StructuredPage {
+        page,
+        width: 612.0,
+        height: PAGE_HEIGHT,
+        blocks,
+    }
+}
+
+#[test]
+fn test_text_quad_to_rect() {
+    let quad = TextQuad {
+        ul: (10.0, 20.0),
+        ur: (50.0, 20.0),
+        ll: (10.0, 35.0),
+        lr: (50.0, 35.0),
+    };
+
+    let rect = quad.to_rect();
+    assert_eq!(rect[0], 10.0); // x1
+    assert_eq!(rect[1], 20.0); // y1
+    assert_eq!(rect[2], 50.0); // x2
+    assert_eq!(rect[3], 35.0); // y2
+}
+
+#[test]
+fn test_text_fragment_creation() {
+    let frag = TextFragment {
+        text: "Hello".to_string(),
+        page: 0,
+        rect: [10.0, 20.0, 50.0, 35.0],
+    };
+
+    assert_eq!(frag.text, "Hello");
+    assert_eq!(frag.page, 0);
+    assert_eq!(frag.rect[0], 10.0); // x1
+    assert_eq!(frag.rect[1], 20.0); // y1
+    assert_eq!(frag.rect[2], 50.0); // x2
+    assert_eq!(frag.rect[3], 35.0); // y2
+}
+
+#[test]
+fn test_outline_item_creation() {
+    let item = OutlineItem {
+        title: "Introduction".to_string(),
+        page: Some(0),
+        top: None,
+        children: vec![OutlineItem {
+            title: "Background".to_string(),
+            page: Some(2),
+            top: None,
+            children: vec![],
+        }],
+    };
+
+    assert_eq!(item.title, "Introduction");
+    assert_eq!(item.page, Some(0));
+    assert_eq!(item.children.len(), 1);
+    assert_eq!(item.children[0].title, "Background");
+}
+
+#[test]
+fn test_resolve_section_exact_match() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![
+            OutlineItem {
+                title: "Introduction".to_string(),
+                page: Some(0),
+                top: None,
+                children: vec![],
+            },
+            OutlineItem {
+                title: "Methods".to_string(),
+                page: Some(5),
+                top: None,
+                children: vec![],
+            },
+            OutlineItem {
+                title: "Results".to_string(),
+                page: Some(10),
+                top: None,
+                children: vec![],
+            },
+        ],
+    };
+
+    let (start, end) = resolve_section_to_pages(&outline, "Methods").unwrap();
+    assert_eq!(start, 5);
+    assert_eq!(end, 10); // Next section starts at 10
+}
+
+#[test]
+fn test_resolve_section_case_insensitive() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![OutlineItem {
+            title: "Introduction".to_string(),
+            page: Some(0),
+            top: None,
+            children: vec![],
+        }],
+    };
+
+    let (start, end) = resolve_section_to_pages(&outline, "introduction").unwrap();
+    assert_eq!(start, 0);
+    assert_eq!(end, 20); // Last section goes to end
+}
+
+#[test]
+fn test_resolve_section_partial_match() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![OutlineItem {
+            title: "1. Introduction and Background".to_string(),
+            page: Some(0),
+            top: None,
+            children: vec![],
+        }],
+    };
+
+    let (start, end) = resolve_section_to_pages(&outline, "Introduction").unwrap();
+    assert_eq!(start, 0);
+    assert_eq!(end, 20);
+}
+
+#[test]
+fn test_resolve_section_not_found() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![OutlineItem {
+            title: "Introduction".to_string(),
+            page: Some(0),
+            top: None,
+            children: vec![],
+        }],
+    };
+
+    let result = resolve_section_to_pages(&outline, "Nonexistent");
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("not found"));
+    assert!(err.contains("Introduction")); // Available sections listed
+}
+
+#[test]
+fn test_resolve_section_no_outline() {
+    let outline = PdfOutline {
+        has_outline: false,
+        total_pages: 20,
+        inferred: false,
+        items: vec![],
+    };
+
+    let result = resolve_section_to_pages(&outline, "Introduction");
+    assert!(result.is_err());
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("no outline"));
+}
+
+#[test]
+fn test_resolve_sections_multiple() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![
+            OutlineItem {
+                title: "Introduction".to_string(),
+                page: Some(0),
+                top: None,
+                children: vec![],
+            },
+            OutlineItem {
+                title: "Methods".to_string(),
+                page: Some(5),
+                top: None,
+                children: vec![],
+            },
+            OutlineItem {
+                title: "Results".to_string(),
+                page: Some(10),
+                top: None,
+                children: vec![],
+            },
+        ],
+    };
+
+    let pages = resolve_sections_to_pages(&outline, "Introduction,Results").unwrap();
+    // Introduction: 0-4, Results: 10-19
+    assert!(pages.contains(&0));
+    assert!(pages.contains(&4));
+    assert!(pages.contains(&10));
+    assert!(pages.contains(&19));
+    // Methods pages should not be included
+    assert!(!pages.contains(&6));
+}
+
+#[test]
+fn test_resolve_nested_section() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 20,
+        inferred: false,
+        items: vec![OutlineItem {
+            title: "Methods".to_string(),
+            page: Some(5),
+            top: None,
+            children: vec![
+                OutlineItem {
+                    title: "Data Collection".to_string(),
+                    page: Some(6),
+                    top: None,
+                    children: vec![],
+                },
+                OutlineItem {
+                    title: "Analysis".to_string(),
+                    page: Some(8),
+                    top: None,
+                    children: vec![],
+                },
+            ],
+        }],
+    };
+
+    // Find nested section - sibling "Analysis" starts at 8, so Data Collection ends at 8
+    let (start, end) = resolve_section_to_pages(&outline, "Data Collection").unwrap();
+    assert_eq!(start, 6);
+    assert_eq!(end, 8); // Next sibling (Analysis) starts at 8
+}
+
+#[test]
+fn test_collect_section_names() {
+    let items = vec![
+        OutlineItem {
+            title: "Introduction".to_string(),
+            page: Some(0),
+            top: None,
+            children: vec![],
+        },
+        OutlineItem {
+            title: "Methods".to_string(),
+            page: Some(5),
+            top: None,
+            children: vec![OutlineItem {
+                title: "Data Collection".to_string(),
+                page: Some(6),
+                top: None,
+                children: vec![],
+            }],
+        },
+    ];
+
+    let names = collect_section_names(&items);
+    assert_eq!(names.len(), 3);
+    assert!(names.contains(&"Introduction".to_string()));
+    assert!(names.contains(&"Methods".to_string()));
+    assert!(names.contains(&"Data Collection".to_string()));
+}
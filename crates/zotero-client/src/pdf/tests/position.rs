@@ -0,0 +1,79 @@
+//! Unit tests for annotation sort indexes.
+
+use super::anchor::page;
+use crate::pdf::{anchor_in_pages, sort_index};
+
+#[test]
+fn test_sort_index_format() {
+    assert_eq!(sort_index(0, 0, 0.0), "00000|000000|00000");
+    assert_eq!(sort_index(12, 345, 678.9), "00012|000345|00678");
+}
+
+#[test]
+fn test_sort_index_clamps_fields() {
+    assert_eq!(sort_index(0, 0, -5.0), "00000|000000|00000");
+    assert_eq!(sort_index(0, 5_000_000, 1e9), "00000|999999|99999");
+}
+
+#[test]
+fn test_anchor_segment_sort_index() {
+    let pages = [page(3, &["The quick brown fox jumps"])];
+
+    let anchor = anchor_in_pages(&pages, "brown fox").unwrap();
+    let segment = &anchor.segments[0];
+
+    assert_eq!(segment.offset, 10);
+    assert_eq!(segment.top, 42.0);
+    assert_eq!(segment.sort_index(), "00003|000010|00042");
+}
+
+#[test]
+fn test_anchor_offset_counts_previous_lines() {
+    let pages = [page(0, &["first line ends", "here and continues"])];
+
+    let anchor = anchor_in_pages(&pages, "here and").unwrap();
+
+    assert_eq!(anchor.segments[0].offset, 15);
+    assert_eq!(anchor.segments[0].top, 54.0);
+}
+
+#[test]
+fn test_anchor_across_pages_restarts_offset() {
+    let pages = [
+        page(0, &["the passage starts here"]),
+        page(1, &["and ends here"]),
+    ];
+
+    let anchor = anchor_in_pages(&pages, "starts here and ends").unwrap();
+
+    assert_eq!(anchor.segments[0].sort_index(), "00000|000012|00042");
+    assert_eq!(anchor.segments[1].sort_index(), "00001|000000|00042");
+}
+
+#[test]
+fn test_later_text_sorts_after_earlier_text() {
+    let pages = [page(0, &["alpha beta", "gamma delta"])];
+
+    let first = anchor_in_pages(&pages, "beta").unwrap().segments[0].sort_index();
+    let second = anchor_in_pages(&pages, "gamma").unwrap().segments[0].sort_index();
+
+    assert!(first < second);
+}
+
+#[test]
+fn test_area_sort_index_uses_closest_char() {
+    let page = page(2, &["alpha beta", "gamma delta"]);
+
+    // Top-left corner just right of "delta" on the second line
+    let rect = [60.0, 700.0, 200.0, 786.0];
+
+    assert_eq!(page.offset_near(rect), 10 + 10);
+    assert_eq!(page.area_sort_index(rect), "00002|000020|00056");
+}
+
+#[test]
+fn test_area_sort_index_on_empty_page() {
+    let page = page(0, &[]);
+
+    assert_eq!(page.offset_near([0.0, 0.0, 10.0, 10.0]), 0);
+}
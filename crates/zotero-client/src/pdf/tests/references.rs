@@ -0,0 +1,365 @@
+//! Unit tests for reference list parsing.
+
+use crate::pdf::references::{is_reference_title, reference_bounds, references_from_pages};
+use crate::pdf::sections::SectionBound;
+use crate::pdf::{
+    parse_reference, CitationMarker, Reference, StructuredBlock, StructuredLine, StructuredSpan,
+};
+use crate::types::{OutlineItem, PdfOutline};
+
+use super::{line_rect, span, text_block, text_line, text_page as page};
+
+fn line(text: &str, x: f64, top: f64) -> StructuredLine {
+    text_line(vec![StructuredSpan {
+        size: 9.0,
+        ..span(text, line_rect(x, x + 200.0, top))
+    }])
+}
+
+/// A block of lines, 12pt apart; `(x, text)` pairs so that lines can be indented.
+fn block(top: f64, lines: &[(f64, &str)]) -> StructuredBlock {
+    text_block(
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, text))| line(text, x, top + 12.0 * i as f64))
+            .collect(),
+    )
+}
+
+fn start(page: usize) -> SectionBound {
+    SectionBound {
+        page,
+        top: Some(90.0),
+        title: "References".to_string(),
+    }
+}
+
+fn item(title: &str, page: u32) -> OutlineItem {
+    OutlineItem {
+        title: title.to_string(),
+        page: Some(page),
+        top: None,
+        children: vec![],
+    }
+}
+
+#[test]
+fn test_reference_titles() {
+    assert!(is_reference_title("References"));
+    assert!(is_reference_title("7 References"));
+    assert!(is_reference_title("VII. REFERENCES"));
+    assert!(is_reference_title("Bibliography"));
+    assert!(!is_reference_title("Related Work"));
+    assert!(!is_reference_title(
+        "References to prior work are given below"
+    ));
+}
+
+#[test]
+fn test_reference_bounds_end_at_appendix() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 12,
+        items: vec![
+            item("1 Introduction", 0),
+            item("6 Conclusion", 8),
+            item("References", 9),
+            item("A Proofs", 11),
+        ],
+        inferred: false,
+    };
+    let (start, end) = reference_bounds(&outline).unwrap();
+    assert_eq!(start.page, 9);
+    assert_eq!(end.unwrap().title, "A Proofs");
+
+    let outline = PdfOutline {
+        items: vec![item("1 Introduction", 0)],
+        ..outline
+    };
+    assert!(reference_bounds(&outline).is_none());
+}
+
+#[test]
+fn test_parse_ieee_reference() {
+    let reference = parse_reference(
+        "[12] K. He, X. Zhang, S. Ren, and J. Sun, “Deep residual learning for image \
+         recognition,” in Proc. IEEE Conf. Comput. Vis. Pattern Recognit., 2016, pp. 770–778, \
+         doi: 10.1109/CVPR.2016.90.",
+    );
+    assert_eq!(reference.label.as_deref(), Some("12"));
+    assert_eq!(
+        reference.authors,
+        vec!["K. He", "X. Zhang", "S. Ren", "J. Sun"]
+    );
+    assert_eq!(
+        reference.title.as_deref(),
+        Some("Deep residual learning for image recognition")
+    );
+    assert_eq!(reference.year, Some(2016));
+    assert_eq!(
+        reference.venue.as_deref(),
+        Some("Proc. IEEE Conf. Comput. Vis. Pattern Recognit")
+    );
+    assert_eq!(reference.doi.as_deref(), Some("10.1109/CVPR.2016.90"));
+}
+
+#[test]
+fn test_parse_acl_reference() {
+    let reference = parse_reference(
+        "Jacob Devlin, Ming-Wei Chang, Kenton Lee, and Kristina Toutanova. 2019. BERT: \
+         Pre-training of deep bidirectional transformers for language understanding. In \
+         Proceedings of the 2019 Conference of the North American Chapter of the Association \
+         for Computational Linguistics, pages 4171–4186.",
+    );
+    assert_eq!(reference.label, None);
+    assert_eq!(
+        reference.authors,
+        vec![
+            "Jacob Devlin",
+            "Ming-Wei Chang",
+            "Kenton Lee",
+            "Kristina Toutanova"
+        ]
+    );
+    assert_eq!(reference.year, Some(2019));
+    assert_eq!(
+        reference.title.as_deref(),
+        Some("BERT: Pre-training of deep bidirectional transformers for language understanding")
+    );
+    assert_eq!(
+        reference.venue.as_deref(),
+        Some(
+            "Proceedings of the 2019 Conference of the North American Chapter of the \
+             Association for Computational Linguistics"
+        )
+    );
+}
+
+#[test]
+fn test_parse_apa_reference() {
+    let reference = parse_reference(
+        "LeCun, Y., Bengio, Y., & Hinton, G. (2015). Deep learning. Nature, 521(7553), \
+         436–444. https://doi.org/10.1038/nature14539",
+    );
+    assert_eq!(
+        reference.authors,
+        vec!["LeCun, Y.", "Bengio, Y.", "Hinton, G."]
+    );
+    assert_eq!(reference.year, Some(2015));
+    assert_eq!(reference.title.as_deref(), Some("Deep learning"));
+    assert_eq!(reference.venue.as_deref(), Some("Nature"));
+    assert_eq!(reference.doi.as_deref(), Some("10.1038/nature14539"));
+}
+
+#[test]
+fn test_parse_springer_and_vancouver_references() {
+    let lncs = parse_reference(
+        "3. Smith, J., Doe, A.: Graph networks for proofs. In: Advances in Logic, pp. 1–10. \
+         Springer (2020)",
+    );
+    assert_eq!(lncs.label.as_deref(), Some("3"));
+    assert_eq!(lncs.authors, vec!["Smith, J.", "Doe, A."]);
+    assert_eq!(lncs.title.as_deref(), Some("Graph networks for proofs"));
+    assert_eq!(lncs.venue.as_deref(), Some("Advances in Logic"));
+    assert_eq!(lncs.year, Some(2020));
+
+    let vancouver = parse_reference(
+        "Smith JA, Doe B, et al. Aspirin and stroke. N Engl J Med. 2001;344(3):1–10.",
+    );
+    assert_eq!(vancouver.authors, vec!["Smith JA", "Doe B"]);
+    assert_eq!(vancouver.title.as_deref(), Some("Aspirin and stroke"));
+    assert_eq!(vancouver.venue.as_deref(), Some("N Engl J Med"));
+    assert_eq!(vancouver.year, Some(2001));
+}
+
+#[test]
+fn test_parse_arxiv_reference() {
+    let reference = parse_reference(
+        "Jared Kaplan, Sam McCandlish, et al. Scaling laws for neural language models. arXiv \
+         preprint arXiv:2001.08361, 2020.",
+    );
+    assert_eq!(reference.authors, vec!["Jared Kaplan", "Sam McCandlish"]);
+    assert_eq!(
+        reference.title.as_deref(),
+        Some("Scaling laws for neural language models")
+    );
+    assert_eq!(
+        reference.venue.as_deref(),
+        Some("arXiv preprint arXiv:2001.08361")
+    );
+    assert_eq!(reference.year, Some(2020));
+}
+
+#[test]
+fn test_numbered_entries_continue_across_pages() {
+    let pages = vec![
+        page(
+            4,
+            vec![
+                block(80.0, &[(72.0, "References")]),
+                block(
+                    100.0,
+                    &[
+                        (72.0, "[1] A. Smith, “A first title,” in Proc. ICML,"),
+                        (84.0, "2019."),
+                        (72.0, "[2] B. Jones, “A second title that is hyphen-"),
+                    ],
+                ),
+                block(760.0, &[(300.0, "5")]),
+            ],
+        ),
+        page(
+            5,
+            vec![block(
+                100.0,
+                &[
+                    (84.0, "ated,” Nature, 2020, doi: 10.1038/"),
+                    (84.0, "s41586-020-1234-5."),
+                ],
+            )],
+        ),
+    ];
+
+    let references = references_from_pages(&pages, &start(4), None);
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[0].title.as_deref(), Some("A first title"));
+    assert_eq!(references[0].year, Some(2019));
+    assert_eq!(references[0].page, Some(4));
+    assert_eq!(
+        references[1].title.as_deref(),
+        Some("A second title that is hyphenated")
+    );
+    assert_eq!(
+        references[1].doi.as_deref(),
+        Some("10.1038/s41586-020-1234-5")
+    );
+}
+
+#[test]
+fn test_author_year_entries_split_at_hanging_indent() {
+    // Two columns; the entry at the bottom of the left column continues at
+    // the top of the right one
+    let pages = vec![page(
+        0,
+        vec![
+            block(80.0, &[(72.0, "References")]),
+            block(
+                100.0,
+                &[
+                    (72.0, "Ada Lovelace. 1843. Notes on the analytical"),
+                    (82.0, "engine. Scientific Memoirs."),
+                    (72.0, "Alan Turing. 1950. Computing machinery and"),
+                ],
+            ),
+            block(
+                100.0,
+                &[
+                    (322.0, "intelligence. Mind."),
+                    (312.0, "Claude Shannon. 1948. A mathematical theory"),
+                    (322.0, "of communication. Bell System Technical Journal."),
+                ],
+            ),
+        ],
+    )];
+
+    let references = references_from_pages(&pages, &start(0), None);
+    let titles: Vec<_> = references
+        .iter()
+        .map(|r| r.title.as_deref().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Notes on the analytical engine",
+            "Computing machinery and intelligence",
+            "A mathematical theory of communication"
+        ]
+    );
+    assert_eq!(references[1].venue.as_deref(), Some("Mind"));
+    assert_eq!(references[2].authors, vec!["Claude Shannon"]);
+}
+
+#[test]
+fn test_unindented_entries_split_at_blocks() {
+    let pages = vec![page(
+        0,
+        vec![
+            block(80.0, &[(72.0, "Bibliography")]),
+            block(
+                100.0,
+                &[
+                    (72.0, "Ada Lovelace. 1843. Notes on the analytical"),
+                    (72.0, "engine. Scientific Memoirs."),
+                ],
+            ),
+            block(140.0, &[(72.0, "Alan Turing. 1950. Computing machinery.")]),
+        ],
+    )];
+
+    let references = references_from_pages(&pages, &start(0), None);
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[1].year, Some(1950));
+}
+
+fn entry(label: Option<&str>, authors: &[&str], year: u32) -> Reference {
+    Reference {
+        label: label.map(str::to_string),
+        authors: authors.iter().map(|a| a.to_string()).collect(),
+        year: Some(year),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_citation_markers() {
+    assert_eq!(
+        CitationMarker::parse("[12]"),
+        Some(CitationMarker::Numbers(vec![12]))
+    );
+    assert_eq!(
+        CitationMarker::parse("[3, 5–7]"),
+        Some(CitationMarker::Numbers(vec![3, 5, 6, 7]))
+    );
+    assert_eq!(
+        CitationMarker::parse("(Smith and Doe 2020a; van der Berg, 2019)"),
+        Some(CitationMarker::AuthorYear(vec![
+            ("Smith".to_string(), 2020),
+            ("Berg".to_string(), 2019)
+        ]))
+    );
+    // Full entries are not markers
+    assert_eq!(
+        CitationMarker::parse("Smith, J. (2020). Deep learning. Nature."),
+        None
+    );
+    assert_eq!(CitationMarker::parse("[1-5000]"), None);
+}
+
+#[test]
+fn test_citation_marker_finds_entries() {
+    let numbered = vec![
+        entry(Some("1"), &["A. Smith"], 2019),
+        entry(Some("2"), &["B. Jones"], 2020),
+    ];
+    let found = CitationMarker::Numbers(vec![2, 9]).find(&numbered);
+    assert_eq!(found, vec![&numbered[1]]);
+
+    let unlabelled = vec![
+        entry(None, &["Doe, A.", "Smith, J."], 2020),
+        entry(None, &["Smith, J.", "Lee, K."], 2020),
+        entry(None, &["Smith, J."], 2018),
+    ];
+    let found = CitationMarker::parse("Smith et al., 2020")
+        .unwrap()
+        .find(&unlabelled);
+    assert_eq!(found, vec![&unlabelled[1]]);
+    let found = CitationMarker::parse("Doe and Smith (2020)")
+        .unwrap()
+        .find(&unlabelled);
+    assert_eq!(found, vec![&unlabelled[0]]);
+    assert_eq!(
+        CitationMarker::Numbers(vec![3]).find(&unlabelled),
+        vec![&unlabelled[2]]
+    );
+}
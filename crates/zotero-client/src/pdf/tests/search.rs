@@ -0,0 +1,123 @@
+//! Unit tests for searching page text.
+
+use super::anchor::page;
+use crate::error::ZoteroClientError;
+use crate::pdf::{SearchOptions, TextMatcher};
+
+fn matcher(query: &str, options: SearchOptions) -> TextMatcher {
+    TextMatcher::new(query, options).unwrap()
+}
+
+#[test]
+fn test_find_is_case_insensitive_by_default() {
+    let page = page(2, &["Attention and attention"]);
+    let matches = matcher("ATTENTION", SearchOptions::default()).find_in_page(&page);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].page, 2);
+    assert_eq!(matches[0].text, "Attention");
+    assert_eq!(matches[1].rects, vec![[70.0, 790.0, 115.0, 800.0]]);
+}
+
+#[test]
+fn test_find_case_sensitive() {
+    let page = page(0, &["Attention and attention"]);
+    let options = SearchOptions {
+        case_sensitive: true,
+        ..Default::default()
+    };
+    let matches = matcher("attention", options).find_in_page(&page);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].rects[0][0], 70.0);
+}
+
+#[test]
+fn test_find_whole_word() {
+    let page = page(0, &["attentional attention"]);
+    let options = SearchOptions {
+        whole_word: true,
+        ..Default::default()
+    };
+    let matches = matcher("attention", options).find_in_page(&page);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].rects[0][0], 60.0);
+}
+
+#[test]
+fn test_find_regex() {
+    let page = page(0, &["p < 0.05 and p<0.001"]);
+    let options = SearchOptions {
+        regex: true,
+        ..Default::default()
+    };
+    let matches = matcher(r"p\s*<\s*0\.\d+", options).find_in_page(&page);
+    let texts: Vec<&str> = matches.iter().map(|m| m.text.as_str()).collect();
+    assert_eq!(texts, vec!["p < 0.05", "p<0.001"]);
+}
+
+#[test]
+fn test_literal_query_escapes_regex_syntax() {
+    let page = page(0, &["costs $5.00 (approx.)"]);
+    let matches = matcher("$5.00 (approx.)", SearchOptions::default()).find_in_page(&page);
+    assert_eq!(matches.len(), 1);
+}
+
+#[test]
+fn test_find_across_lines_with_one_rect_per_line() {
+    let page = page(0, &["graph neural", "networks are"]);
+    let matches = matcher("neural networks", SearchOptions::default()).find_in_page(&page);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].text, "neural networks");
+    assert_eq!(
+        matches[0].rects,
+        vec![[30.0, 790.0, 60.0, 800.0], [0.0, 778.0, 40.0, 788.0]]
+    );
+}
+
+#[test]
+fn test_find_joins_hyphenated_words() {
+    let page = page(0, &["the compu-", "tation of"]);
+    let matches = matcher("computation", SearchOptions::default()).find_in_page(&page);
+    assert_eq!(matches.len(), 1);
+    // The line-end hyphen is not part of the rectangles
+    assert_eq!(matches[0].rects[0], [20.0, 790.0, 45.0, 800.0]);
+}
+
+#[test]
+fn test_snippet_adds_context_and_ellipses() {
+    let long = format!("{} target {}", "a".repeat(100), "b".repeat(100));
+    let page = page(0, &[&long, "next line"]);
+    let matches = matcher("target", SearchOptions::default()).find_in_page(&page);
+    let snippet = &matches[0].snippet;
+    assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+    assert!(snippet.contains(" target "));
+
+    let page = super::anchor::page(0, &["short target"]);
+    let matches = matcher("target", SearchOptions::default()).find_in_page(&page);
+    assert_eq!(matches[0].snippet, "short target");
+}
+
+#[test]
+fn test_invalid_queries_are_rejected() {
+    let options = SearchOptions {
+        regex: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        TextMatcher::new("(unclosed", options),
+        Err(ZoteroClientError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        TextMatcher::new("  ", SearchOptions::default()),
+        Err(ZoteroClientError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_empty_regex_matches_are_skipped() {
+    let page = page(0, &["abc"]);
+    let options = SearchOptions {
+        regex: true,
+        ..Default::default()
+    };
+    assert!(matcher("x*", options).find_in_page(&page).is_empty());
+}
@@ -0,0 +1,219 @@
+//! Unit tests for cutting section text at headings.
+
+use crate::pdf::layout::LayoutOptions;
+use crate::pdf::sections::{section_bounds, slice_section, SectionBound};
+use crate::pdf::{SectionPage, StructuredBlock};
+use crate::types::{OutlineItem, PdfOutline};
+
+use super::{line_rect, span, text_block, text_line, text_page as page};
+
+/// A one-line block spanning x1..x2 whose top is `top` points from the top of the page.
+fn block(text: &str, x1: f64, x2: f64, top: f64) -> StructuredBlock {
+    text_block(vec![text_line(vec![span(text, line_rect(x1, x2, top))])])
+}
+
+/// A full-width block.
+fn wide(text: &str, top: f64) -> StructuredBlock {
+    block(text, 72.0, 540.0, top)
+}
+
+fn bound(title: &str, page: usize, top: Option<f64>) -> SectionBound {
+    SectionBound {
+        page,
+        top,
+        title: title.to_string(),
+    }
+}
+
+fn item(title: &str, page: u32, children: Vec<OutlineItem>) -> OutlineItem {
+    OutlineItem {
+        title: title.to_string(),
+        page: Some(page),
+        top: Some(100.0),
+        children,
+    }
+}
+
+fn texts(pages: &[SectionPage]) -> Vec<(usize, &str)> {
+    pages.iter().map(|p| (p.page, p.text.as_str())).collect()
+}
+
+#[test]
+fn test_section_bounds_end_at_next_heading_of_same_or_higher_level() {
+    let outline = PdfOutline {
+        has_outline: true,
+        total_pages: 10,
+        items: vec![
+            item(
+                "2 Methods",
+                2,
+                vec![item("2.1 Data", 2, vec![]), item("2.2 Model", 3, vec![])],
+            ),
+            item("3 Results", 5, vec![]),
+        ],
+        inferred: false,
+    };
+
+    let (start, end) = section_bounds(&outline, "methods").unwrap();
+    assert_eq!(start, bound("2 Methods", 2, Some(100.0)));
+    assert_eq!(end.unwrap().title, "3 Results");
+
+    let (_, end) = section_bounds(&outline, "Data").unwrap();
+    assert_eq!(end.unwrap().title, "2.2 Model");
+
+    let (_, end) = section_bounds(&outline, "Model").unwrap();
+    assert_eq!(end.unwrap().title, "3 Results");
+
+    let (_, end) = section_bounds(&outline, "Results").unwrap();
+    assert!(end.is_none());
+
+    assert!(section_bounds(&outline, "Discussion")
+        .unwrap_err()
+        .to_string()
+        .contains("2.1 Data"));
+}
+
+#[test]
+fn test_slice_section_within_one_page() {
+    let pages = vec![page(
+        3,
+        vec![
+            wide("1 Introduction", 100.0),
+            wide("Intro text.", 120.0),
+            wide("2 Methods", 300.0),
+            wide("Method text.", 320.0),
+            wide("3 Results", 500.0),
+            wide("Results text.", 520.0),
+        ],
+    )];
+    let sections = slice_section(
+        &pages,
+        &bound("2 Methods", 3, Some(295.0)),
+        Some(&bound("3 Results", 3, Some(495.0))),
+        LayoutOptions::default(),
+    );
+    assert_eq!(texts(&sections), vec![(3, "2 Methods\n\nMethod text.")]);
+}
+
+#[test]
+fn test_slice_section_across_pages_strips_running_headers() {
+    let pages = vec![
+        page(
+            0,
+            vec![
+                wide("Intro text.", 100.0),
+                wide("2 Methods", 600.0),
+                wide("Method text.", 620.0),
+            ],
+        ),
+        page(
+            1,
+            vec![
+                wide("Preprint. Under review.", 20.0),
+                wide("More method text.", 100.0),
+                wide("3 Results", 300.0),
+            ],
+        ),
+    ];
+    let start = bound("2 Methods", 0, None);
+    let end = bound("3 Results", 1, None);
+
+    let stripped = slice_section(
+        &pages,
+        &start,
+        Some(&end),
+        LayoutOptions {
+            strip_headers_footers: true,
+        },
+    );
+    assert_eq!(
+        texts(&stripped),
+        vec![(0, "2 Methods\n\nMethod text."), (1, "More method text.")]
+    );
+
+    let full = slice_section(&pages, &start, Some(&end), LayoutOptions::default());
+    assert_eq!(full[1].text, "Preprint. Under review.\n\nMore method text.");
+}
+
+#[test]
+fn test_slice_section_follows_columns() {
+    // Paragraphs 100pt tall, so that the page has columns
+    let column = |text: &str, x1: f64, top: f64| {
+        let mut block = block(text, x1, x1 + 228.0, top);
+        block.bbox[1] -= 90.0;
+        block
+    };
+    let left = |text: &str, top: f64| column(text, 72.0, top);
+    let right = |text: &str, top: f64| column(text, 312.0, top);
+
+    // The section starts low in the left column and ends high in the right one
+    let pages = vec![page(
+        0,
+        vec![
+            left("Intro text.", 100.0),
+            right("Method text, continued.", 100.0),
+            left("More intro text.", 300.0),
+            right("3 Results", 300.0),
+            left("2 Methods", 500.0),
+            right("Results text.", 500.0),
+        ],
+    )];
+
+    let sections = slice_section(
+        &pages,
+        &bound("2 Methods", 0, Some(500.0)),
+        Some(&bound("3 Results", 0, Some(300.0))),
+        LayoutOptions::default(),
+    );
+    assert_eq!(
+        texts(&sections),
+        vec![(0, "2 Methods\n\nMethod text, continued.")]
+    );
+}
+
+#[test]
+fn test_slice_section_prefers_title_then_position() {
+    let pages = vec![page(
+        0,
+        vec![
+            wide("Methods are described below.", 100.0),
+            wide("METHODS", 200.0),
+            wide("Method text.", 220.0),
+            wide("Unmatched heading", 400.0),
+            wide("After.", 420.0),
+        ],
+    )];
+    // Both the heading and the sentence start with the title, and the one
+    // closest to the destination wins; the next heading is found by its
+    // position alone
+    let sections = slice_section(
+        &pages,
+        &bound("Methods", 0, Some(190.0)),
+        Some(&bound("Results", 0, Some(398.0))),
+        LayoutOptions::default(),
+    );
+    assert_eq!(texts(&sections), vec![(0, "METHODS\n\nMethod text.")]);
+}
+
+#[test]
+fn test_slice_section_matches_wrapped_headings() {
+    let pages = vec![page(
+        0,
+        vec![
+            wide("Intro text.", 100.0),
+            wide("4 Scaling Laws for Sparse", 200.0),
+            wide("Mixture of Experts", 212.0),
+            wide("Body.", 240.0),
+        ],
+    )];
+    let sections = slice_section(
+        &pages,
+        &bound("4 Scaling Laws for Sparse Mixture of Experts", 0, None),
+        None,
+        LayoutOptions::default(),
+    );
+    assert_eq!(
+        sections[0].text.lines().next(),
+        Some("4 Scaling Laws for Sparse")
+    );
+}
@@ -0,0 +1,111 @@
+//! Unit tests for structured page text.
+
+use crate::pdf::structure::{push_char, FontStyle};
+use crate::pdf::{StructuredBlock, StructuredLine, StructuredPage};
+
+fn line(text: &str, fonts: &[Option<&FontStyle>], size: f64) -> StructuredLine {
+    let mut spans = Vec::new();
+    for (i, (ch, font)) in text.chars().zip(fonts).enumerate() {
+        let x = 5.0 * i as f64;
+        push_char(&mut spans, ch, [x, 790.0, x + 5.0, 800.0], size, *font);
+    }
+    StructuredLine {
+        bbox: [0.0, 790.0, 5.0 * text.len() as f64, 800.0],
+        spans,
+    }
+}
+
+#[test]
+fn test_font_style_strips_subset_prefix() {
+    assert_eq!(
+        FontStyle::new("ABCDEF+Times-Roman", false, false, false).name,
+        "Times-Roman"
+    );
+    assert_eq!(
+        FontStyle::new("Foo+Bar", false, false, false).name,
+        "Foo+Bar"
+    );
+}
+
+#[test]
+fn test_font_style_reads_style_from_name() {
+    let bold = FontStyle::new("XYZABC+NimbusRomNo9L-Medi", false, false, false);
+    assert!(bold.bold && !bold.italic);
+    let bold_italic = FontStyle::new("NimbusRomNo9L-MediItal", false, false, false);
+    assert!(bold_italic.bold);
+    assert!(!FontStyle::new("Roboto-Medium", false, false, false).bold);
+    assert!(!FontStyle::new("Inter-MediumItalic", false, false, false).bold);
+
+    let italic = FontStyle::new("Helvetica-Oblique", false, false, false);
+    assert!(italic.italic && !italic.bold);
+
+    assert!(FontStyle::new("CMBX12", false, false, false).bold);
+    assert!(FontStyle::new("CMTI10", false, false, false).italic);
+    assert!(FontStyle::new("CMTT10", false, false, false).monospaced);
+    assert!(FontStyle::new("Inconsolata", false, false, true).monospaced);
+
+    let plain = FontStyle::new("CMR10", false, false, false);
+    assert!(!plain.bold && !plain.italic && !plain.monospaced);
+}
+
+#[test]
+fn test_push_char_groups_runs_of_the_same_style() {
+    let bold = FontStyle::new("Times-Bold", false, false, false);
+    let roman = FontStyle::new("Times-Roman", false, false, false);
+    let fonts = [Some(&bold), Some(&bold), None, Some(&roman), Some(&roman)];
+    let line = line("1. It", &fonts, 10.0);
+
+    assert_eq!(line.spans.len(), 2);
+    assert_eq!(line.spans[0].text, "1. ");
+    assert!(line.spans[0].bold);
+    assert_eq!(line.spans[0].bbox, [0.0, 790.0, 15.0, 800.0]);
+    assert_eq!(line.spans[1].text, "It");
+    assert_eq!(line.spans[1].font, "Times-Roman");
+    assert_eq!(line.text(), "1. It");
+}
+
+#[test]
+fn test_push_char_splits_on_size_change() {
+    let roman = FontStyle::new("Times-Roman", false, false, false);
+    let mut spans = Vec::new();
+    push_char(&mut spans, 'x', [0.0, 0.0, 5.0, 10.0], 10.0, Some(&roman));
+    push_char(&mut spans, '2', [5.0, 4.0, 8.0, 10.0], 7.0, Some(&roman));
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[1].size, 7.0);
+}
+
+#[test]
+fn test_push_char_without_font_starts_unnamed_span() {
+    let mut spans = Vec::new();
+    push_char(&mut spans, 'a', [0.0, 0.0, 5.0, 10.0], 10.0, None);
+    assert_eq!(spans[0].font, "");
+    assert!(!spans[0].bold);
+}
+
+#[test]
+fn test_structured_page_serializes_to_json() {
+    let roman = FontStyle::new("Times-Roman", false, false, false);
+    let page = StructuredPage {
+        page: 0,
+        width: 612.0,
+        height: 792.0,
+        blocks: vec![StructuredBlock {
+            bbox: [0.0, 778.0, 20.0, 800.0],
+            lines: vec![
+                line("Two", &[Some(&roman); 3], 10.0),
+                line("rows", &[Some(&roman); 4], 10.0),
+            ],
+        }],
+    };
+    assert_eq!(page.blocks[0].text(), "Two\nrows");
+
+    let json = serde_json::to_value(&page).unwrap();
+    let span = &json["blocks"][0]["lines"][0]["spans"][0];
+    assert_eq!(span["text"], "Two");
+    assert_eq!(span["font"], "Times-Roman");
+    assert_eq!(span["size"], 10.0);
+    assert_eq!(span["italic"], false);
+
+    let parsed: StructuredPage = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, page);
+}
@@ -0,0 +1,197 @@
+//! Unit tests for table structure extraction.
+
+use crate::pdf::tables::{build_table, table_area, Rule};
+use crate::pdf::{PageText, PositionedChar, Table};
+
+/// A MuPDF line of 10pt high characters starting at `x`, with its bottom at
+/// `y`; letters are 5pt wide and spaces 2.5pt.
+fn line(x: f64, y: f64, text: &str) -> Vec<PositionedChar> {
+    let mut x = x;
+    text.chars()
+        .map(|ch| {
+            let width = if ch == ' ' { 2.5 } else { 5.0 };
+            x += width;
+            PositionedChar {
+                ch,
+                rect: [x - width, y, x, y + 10.0],
+            }
+        })
+        .collect()
+}
+
+fn page(lines: Vec<Vec<PositionedChar>>) -> PageText {
+    PageText {
+        page: 0,
+        height: 792.0,
+        lines,
+    }
+}
+
+fn horizontal(y: f64, start: f64, end: f64) -> Rule {
+    Rule::between((start, y), (end, y)).unwrap()
+}
+
+fn vertical(x: f64, bottom: f64, top: f64) -> Rule {
+    Rule::between((x, bottom), (x, top)).unwrap()
+}
+
+fn rows(cells: &[&[&str]]) -> Vec<Vec<String>> {
+    cells
+        .iter()
+        .map(|row| row.iter().map(|c| c.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_booktabs_table() {
+    let text = page(vec![
+        line(100.0, 700.0, "Model"),
+        line(200.0, 700.0, "Accuracy"),
+        line(300.0, 700.0, "F1"),
+        line(100.0, 685.0, "BERT base"),
+        line(200.0, 685.0, "84.5"),
+        line(300.0, 685.0, "0.81"),
+        line(100.0, 670.0, "RoBERTa"),
+        line(200.0, 670.0, "87.2"),
+        line(300.0, 670.0, "0.85"),
+        line(100.0, 640.0, "Table 1: Results on the test set."),
+    ]);
+    let rules = vec![
+        horizontal(713.0, 95.0, 350.0),
+        horizontal(697.0, 95.0, 350.0),
+        horizontal(666.0, 95.0, 350.0),
+    ];
+
+    let area = table_area(&rules).unwrap();
+    assert_eq!(area, [94.0, 665.0, 351.0, 714.0]);
+    assert_eq!(
+        build_table(&text, &rules, area).unwrap(),
+        rows(&[
+            &["Model", "Accuracy", "F1"],
+            &["BERT base", "84.5", "0.81"],
+            &["RoBERTa", "87.2", "0.85"],
+        ])
+    );
+}
+
+#[test]
+fn test_cells_on_one_line_are_split_at_gaps() {
+    // MuPDF may read a whole row as one line when the cells are close.
+    let mut header = line(100.0, 700.0, "Name");
+    header.extend(line(140.0, 700.0, "Value"));
+    let mut row = line(100.0, 685.0, "alpha");
+    row.extend(line(140.0, 685.0, "1"));
+    let text = page(vec![header, row]);
+
+    assert_eq!(
+        build_table(&text, &[], [90.0, 680.0, 200.0, 715.0]).unwrap(),
+        rows(&[&["Name", "Value"], &["alpha", "1"]])
+    );
+}
+
+#[test]
+fn test_grid_table_joins_wrapped_cells() {
+    let text = page(vec![
+        line(105.0, 700.0, "Term"),
+        line(205.0, 700.0, "Meaning"),
+        line(105.0, 680.0, "DOI"),
+        line(205.0, 680.0, "Digital object"),
+        line(205.0, 668.0, "identifier"),
+        line(105.0, 650.0, "ISBN"),
+        line(205.0, 650.0, "Book number"),
+    ]);
+    let rules = vec![
+        horizontal(715.0, 100.0, 320.0),
+        horizontal(695.0, 100.0, 320.0),
+        horizontal(664.0, 100.0, 320.0),
+        horizontal(645.0, 100.0, 320.0),
+        vertical(100.0, 645.0, 715.0),
+        vertical(200.0, 645.0, 715.0),
+        vertical(320.0, 645.0, 715.0),
+    ];
+
+    let area = table_area(&rules).unwrap();
+    assert_eq!(
+        build_table(&text, &rules, area).unwrap(),
+        rows(&[
+            &["Term", "Meaning"],
+            &["DOI", "Digital object identifier"],
+            &["ISBN", "Book number"],
+        ])
+    );
+}
+
+#[test]
+fn test_spanning_header_goes_in_first_column() {
+    let text = page(vec![
+        line(200.0, 715.0, "Scores (%)"),
+        line(100.0, 700.0, "Method"),
+        line(200.0, 700.0, "P"),
+        line(260.0, 700.0, "R"),
+        line(100.0, 685.0, "Ours"),
+        line(200.0, 685.0, "91.0"),
+        line(260.0, 685.0, "88.3"),
+    ]);
+
+    assert_eq!(
+        build_table(&text, &[], [90.0, 680.0, 320.0, 730.0]).unwrap(),
+        rows(&[
+            &["", "Scores (%)", ""],
+            &["Method", "P", "R"],
+            &["Ours", "91.0", "88.3"],
+        ])
+    );
+}
+
+#[test]
+fn test_text_without_columns_is_not_a_table() {
+    let text = page(vec![
+        line(100.0, 700.0, "Just a paragraph of text"),
+        line(100.0, 688.0, "that wraps over two lines."),
+    ]);
+    assert!(build_table(&text, &[], [90.0, 680.0, 400.0, 720.0]).is_none());
+}
+
+#[test]
+fn test_table_area_needs_two_overlapping_rules() {
+    assert!(table_area(&[horizontal(700.0, 100.0, 300.0)]).is_none());
+    // Rules in different columns of the page are not one table.
+    assert!(table_area(&[
+        horizontal(700.0, 50.0, 290.0),
+        horizontal(650.0, 320.0, 560.0),
+    ])
+    .is_none());
+}
+
+#[test]
+fn test_rules_from_segments_and_rects() {
+    assert!(Rule::between((10.0, 10.0), (50.0, 30.0)).is_none());
+    assert!(Rule::between((10.0, 10.0), (12.0, 10.0)).is_none());
+
+    let rule = Rule::from_rect([100.0, 699.6, 300.0, 700.4]).unwrap();
+    assert!(rule.horizontal);
+    assert!((rule.position - 700.0).abs() < 1e-9);
+    assert_eq!((rule.start, rule.end), (100.0, 300.0));
+
+    let rule = Rule::from_rect([199.5, 600.0, 200.5, 700.0]).unwrap();
+    assert!(!rule.horizontal);
+    assert!(Rule::from_rect([100.0, 600.0, 300.0, 700.0]).is_none());
+}
+
+#[test]
+fn test_table_to_markdown_and_csv() {
+    let table = Table {
+        page: 0,
+        rect: [0.0, 0.0, 100.0, 100.0],
+        rows: rows(&[&["Name", "Note"], &["a|b", "say \"hi\", twice"]]),
+    };
+    assert_eq!(table.columns(), 2);
+    assert_eq!(
+        table.to_markdown(),
+        "| Name | Note |\n| --- | --- |\n| a\\|b | say \"hi\", twice |\n"
+    );
+    assert_eq!(
+        table.to_csv(),
+        "Name,Note\r\na|b,\"say \"\"hi\"\", twice\"\r\n"
+    );
+}
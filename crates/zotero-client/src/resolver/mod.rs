@@ -0,0 +1,96 @@
+//! Attachment key to file path resolution.
+//!
+//! Tools address PDFs by their Zotero attachment key, but every PDF operation
+//! needs a local file path. This module provides [`AttachmentIndex`], a shared,
+//! invalidatable cache of key → path mappings that [`ZoteroClient`] consults
+//! before falling back to a direct attachment-by-key lookup.
+//!
+//! [`ZoteroClient`]: crate::ZoteroClient
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// A thread-safe cache of attachment keys to local file paths.
+///
+/// Cloning an `AttachmentIndex` is cheap and all clones share the same
+/// underlying map, so a single index can be shared between every clone of a
+/// [`ZoteroClient`](crate::ZoteroClient).
+///
+/// Entries whose file no longer exists on disk are treated as stale: they are
+/// evicted on lookup so the caller can re-resolve the key from Zotero.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::resolver::AttachmentIndex;
+///
+/// let index = AttachmentIndex::new();
+/// index.insert("ABCD1234", "/tmp");
+/// assert!(index.get("ABCD1234").is_some());
+///
+/// index.invalidate("ABCD1234");
+/// assert!(index.get("ABCD1234").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentIndex {
+    entries: Arc<RwLock<HashMap<String, PathBuf>>>,
+}
+
+impl AttachmentIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up the cached path for an attachment key.
+    ///
+    /// Returns `None` if the key is not cached or if the cached file no longer
+    /// exists (in which case the stale entry is removed).
+    pub fn get(&self, key: &str) -> Option<PathBuf> {
+        let path = {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            entries.get(key).cloned()
+        }?;
+
+        if path.exists() {
+            Some(path)
+        } else {
+            self.invalidate(key);
+            None
+        }
+    }
+
+    /// Record the path for an attachment key, replacing any previous entry.
+    pub fn insert(&self, key: impl Into<String>, path: impl AsRef<Path>) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key.into(), path.as_ref().to_path_buf());
+    }
+
+    /// Remove the cached path for an attachment key.
+    ///
+    /// Call this when an attachment has been moved, renamed or deleted in Zotero.
+    pub fn invalidate(&self, key: &str) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.remove(key);
+    }
+
+    /// Remove all cached entries.
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.clear();
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Whether the index has no cached entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests;
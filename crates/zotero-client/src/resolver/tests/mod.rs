@@ -0,0 +1,62 @@
+//! Unit tests for the attachment resolver module.
+
+use super::*;
+
+/// A path that is guaranteed to exist while the tests run.
+fn existing_path() -> PathBuf {
+    std::env::temp_dir()
+}
+
+#[test]
+fn test_attachment_index_insert_and_get() {
+    let index = AttachmentIndex::new();
+    assert!(index.is_empty());
+
+    index.insert("PDF12345", existing_path());
+
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.get("PDF12345"), Some(existing_path()));
+    assert_eq!(index.get("MISSING1"), None);
+}
+
+#[test]
+fn test_attachment_index_invalidate() {
+    let index = AttachmentIndex::new();
+    index.insert("PDF12345", existing_path());
+    index.insert("PDF67890", existing_path());
+
+    index.invalidate("PDF12345");
+
+    assert_eq!(index.get("PDF12345"), None);
+    assert!(index.get("PDF67890").is_some());
+}
+
+#[test]
+fn test_attachment_index_clear() {
+    let index = AttachmentIndex::new();
+    index.insert("PDF12345", existing_path());
+    index.insert("PDF67890", existing_path());
+
+    index.clear();
+
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_attachment_index_evicts_missing_files() {
+    let index = AttachmentIndex::new();
+    index.insert("PDF12345", "/nonexistent/zotero-mcp/missing.pdf");
+
+    assert_eq!(index.get("PDF12345"), None);
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_attachment_index_clones_share_entries() {
+    let index = AttachmentIndex::new();
+    let clone = index.clone();
+
+    clone.insert("PDF12345", existing_path());
+
+    assert!(index.get("PDF12345").is_some());
+}
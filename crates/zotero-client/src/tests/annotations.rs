@@ -0,0 +1,205 @@
+//! Unit tests for annotation types: kinds, positions, filters and updates.
+
+use crate::types::{
+    AnnotationFilter, AnnotationPosition, CreateAnnotationRequest, HighlightColor,
+    UpdateAnnotationRequest, ZoteroAnnotation, NOTE_ANNOTATION_SIZE,
+};
+
+// ============================================
+// Annotation Kind Tests
+// ============================================
+
+#[test]
+fn test_create_annotation_request_underline() {
+    let request = CreateAnnotationRequest::underline("PDF_KEY", "Test text", 2, vec![]);
+
+    assert_eq!(request.annotation_type, Some("underline".to_string()));
+    assert_eq!(request.text, Some("Test text".to_string()));
+    assert_eq!(request.page_label, Some("3".to_string()));
+}
+
+#[test]
+fn test_create_annotation_request_note() {
+    let request = CreateAnnotationRequest::note("PDF_KEY", "A note", 1, [30.0, 500.0]);
+
+    assert_eq!(request.annotation_type, Some("note".to_string()));
+    assert_eq!(request.text, None);
+    assert_eq!(request.comment, Some("A note".to_string()));
+
+    let position = request.position.unwrap();
+    assert_eq!(position.page_index, 1);
+    assert_eq!(
+        position.rects,
+        vec![vec![
+            30.0,
+            500.0 - NOTE_ANNOTATION_SIZE,
+            30.0 + NOTE_ANNOTATION_SIZE,
+            500.0
+        ]]
+    );
+}
+
+#[test]
+fn test_create_annotation_request_ink() {
+    let paths = vec![vec![10.0, 20.0, 30.0, 40.0], vec![5.0, 50.0, 15.0, 45.0]];
+    let request = CreateAnnotationRequest::ink("PDF_KEY", 0, paths.clone(), 2.5);
+
+    assert_eq!(request.annotation_type, Some("ink".to_string()));
+    assert_eq!(request.text, None);
+
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["position"]["width"], 2.5);
+    assert_eq!(json["position"]["paths"][1][1], 50.0);
+    assert!(json["position"].get("rects").is_none());
+
+    let position = request.position.unwrap();
+    assert_eq!(position.paths, paths);
+    assert_eq!(position.bounding_rect(), Some([5.0, 20.0, 30.0, 50.0]));
+}
+
+#[test]
+fn test_annotation_position_bounding_rect() {
+    let position = AnnotationPosition {
+        rects: vec![vec![10.0, 20.0, 100.0, 35.0], vec![12.0, 5.0, 80.0, 20.0]],
+        ..Default::default()
+    };
+    assert_eq!(position.bounding_rect(), Some([10.0, 5.0, 100.0, 35.0]));
+    assert_eq!(AnnotationPosition::default().bounding_rect(), None);
+}
+
+#[test]
+fn test_ink_position_deserialization() {
+    let json = r#"{"pageIndex": 3, "width": 2, "paths": [[1, 2, 3, 4]]}"#;
+    let position: AnnotationPosition = serde_json::from_str(json).unwrap();
+
+    assert_eq!(position.page_index, 3);
+    assert!(position.rects.is_empty());
+    assert_eq!(position.paths, vec![vec![1.0, 2.0, 3.0, 4.0]]);
+    assert_eq!(position.width, Some(2.0));
+}
+
+// ============================================
+// Annotation Listing Tests
+// ============================================
+
+fn annotation(annotation_type: &str, color: &str, page_index: u32) -> ZoteroAnnotation {
+    ZoteroAnnotation {
+        id: None,
+        key: Some("ANN12345".to_string()),
+        parent_item_key: Some("PDF12345".to_string()),
+        annotation_type: Some(annotation_type.to_string()),
+        text: None,
+        comment: None,
+        color: Some(color.to_string()),
+        page_label: None,
+        sort_index: None,
+        position: Some(AnnotationPosition {
+            page_index,
+            ..Default::default()
+        }),
+    }
+}
+
+#[test]
+fn test_annotation_position_from_json_string() {
+    // The plugin returns Zotero's raw annotationPosition, which is a JSON string
+    let json = r#"{
+        "key": "ANN12345",
+        "annotationType": "highlight",
+        "position": "{\"pageIndex\":2,\"rects\":[[10.0,20.0,30.0,40.0]]}"
+    }"#;
+
+    let annotation: ZoteroAnnotation = serde_json::from_str(json).unwrap();
+
+    assert_eq!(annotation.page_index(), Some(2));
+    assert_eq!(annotation.position.unwrap().rects.len(), 1);
+}
+
+#[test]
+fn test_annotation_position_missing_or_null() {
+    let missing: ZoteroAnnotation = serde_json::from_str(r#"{"key": "ANN1"}"#).unwrap();
+    let null: ZoteroAnnotation =
+        serde_json::from_str(r#"{"key": "ANN1", "position": null}"#).unwrap();
+
+    assert_eq!(missing.position, None);
+    assert_eq!(null.position, None);
+}
+
+#[test]
+fn test_annotation_semantic_color() {
+    let ann = annotation("highlight", "#FF6666", 0);
+    assert_eq!(ann.semantic_color(), Some(HighlightColor::Negative));
+}
+
+#[test]
+fn test_annotation_filter_empty_matches_everything() {
+    let filter = AnnotationFilter::new();
+    assert!(filter.matches(&annotation("highlight", "#ffd400", 0)));
+    assert!(filter.matches(&annotation("image", "#2ea8e5", 7)));
+}
+
+#[test]
+fn test_annotation_filter_criteria() {
+    let filter = AnnotationFilter::new()
+        .with_type("highlight")
+        .with_semantic_color(HighlightColor::Question)
+        .on_page(3);
+
+    assert!(filter.matches(&annotation("highlight", "#FFD400", 3)));
+    assert!(!filter.matches(&annotation("image", "#ffd400", 3)));
+    assert!(!filter.matches(&annotation("highlight", "#ff6666", 3)));
+    assert!(!filter.matches(&annotation("highlight", "#ffd400", 4)));
+}
+
+// ============================================
+// Annotation Update Tests
+// ============================================
+
+#[test]
+fn test_update_annotation_request_only_serializes_set_fields() {
+    let request = UpdateAnnotationRequest::new("ANN12345")
+        .with_comment("")
+        .with_semantic_color(HighlightColor::Negative);
+
+    let json = serde_json::to_string(&request).unwrap();
+
+    assert!(json.contains("\"key\":\"ANN12345\""));
+    assert!(json.contains("\"comment\":\"\""));
+    assert!(json.contains("\"color\":\"#ff6666\""));
+    assert!(!json.contains("\"text\""));
+    assert!(!json.contains("\"position\""));
+}
+
+#[test]
+fn test_update_annotation_request_with_position() {
+    let request =
+        UpdateAnnotationRequest::new("ANN12345").with_position(4, vec![vec![1.0, 2.0, 3.0, 4.0]]);
+
+    assert_eq!(request.page_label, Some("5".to_string()));
+    assert_eq!(request.sort_index, None);
+    assert_eq!(request.position.unwrap().page_index, 4);
+
+    let request = UpdateAnnotationRequest::new("ANN12345")
+        .with_position(4, vec![vec![1.0, 2.0, 3.0, 4.0]])
+        .with_page_label("iii")
+        .with_sort_index("00004|000120|00388");
+    let json = serde_json::to_string(&request).unwrap();
+    assert!(json.contains("\"pageLabel\":\"iii\""));
+    assert!(json.contains("\"sortIndex\":\"00004|000120|00388\""));
+}
+
+#[test]
+fn test_update_annotation_request_is_empty() {
+    assert!(UpdateAnnotationRequest::new("ANN12345").is_empty());
+    assert!(!UpdateAnnotationRequest::new("ANN12345")
+        .with_text("x")
+        .is_empty());
+}
+
+#[test]
+fn test_annotation_response_type_alias() {
+    // The plugin reports the annotation type as "type" in create/update responses
+    let annotation: ZoteroAnnotation =
+        serde_json::from_str(r#"{"key": "ANN1", "type": "highlight"}"#).unwrap();
+    assert_eq!(annotation.annotation_type, Some("highlight".to_string()));
+}
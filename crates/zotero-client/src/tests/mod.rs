@@ -6,6 +6,9 @@ use crate::types::{
     ZoteroItem,
 };
 
+mod annotations;
+mod notes;
+
 #[test]
 fn test_zotero_item_deserialization() {
     let json = r#"{
@@ -38,6 +41,7 @@ fn test_zotero_item_deserialization() {
     assert_eq!(item.creators[0].last_name, Some("Doe".to_string()));
     assert_eq!(item.date, Some("2024-01-01".to_string()));
     assert_eq!(item.extra, Some("Citation Key: doe2024test".to_string()));
+    assert_eq!(item.citation_key(), Some("doe2024test"));
     assert_eq!(item.tags.len(), 1);
     assert_eq!(item.tags[0].tag, "machine-learning");
 }
@@ -55,6 +59,7 @@ fn test_zotero_item_minimal() {
     assert_eq!(item.key, "XYZ99999");
     assert_eq!(item.item_type, "book");
     assert_eq!(item.title, None);
+    assert_eq!(item.citation_key(), None);
     assert!(item.creators.is_empty());
     assert!(item.tags.is_empty());
 }
@@ -134,12 +139,15 @@ fn test_annotation_position_serialization() {
     let position = AnnotationPosition {
         page_index: 0,
         rects: vec![vec![10.0, 20.0, 100.0, 35.0], vec![10.0, 5.0, 100.0, 20.0]],
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&position).unwrap();
 
     assert!(json.contains("\"pageIndex\":0"));
     assert!(json.contains("\"rects\":"));
+    assert!(!json.contains("paths"));
+    assert!(!json.contains("width"));
 }
 
 #[test]
@@ -319,6 +327,26 @@ fn test_create_area_annotation_request_with_options() {
     assert_eq!(request.page_label, Some("6".to_string())); // page_index 5 + 1
 }
 
+#[test]
+fn test_annotation_requests_with_page_label_and_sort_index() {
+    let request = CreateAnnotationRequest::highlight("PDF_KEY", "text", 4, vec![])
+        .with_page_label("iv")
+        .with_sort_index("00004|000120|00300");
+
+    assert_eq!(request.page_label, Some("iv".to_string()));
+    assert_eq!(request.sort_index, Some("00004|000120|00300".to_string()));
+
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["sortIndex"], "00004|000120|00300");
+
+    let request = CreateAreaAnnotationRequest::new("PDF_KEY", 0, [0.0, 0.0, 10.0, 10.0])
+        .with_page_label("S1")
+        .with_sort_index("00000|000000|00832");
+
+    assert_eq!(request.page_label, Some("S1".to_string()));
+    assert_eq!(request.sort_index, Some("00000|000000|00832".to_string()));
+}
+
 #[test]
 fn test_create_area_annotation_request_with_semantic_color() {
     let request = CreateAreaAnnotationRequest::new("PDF_KEY", 0, [0.0, 0.0, 100.0, 100.0])
@@ -363,3 +391,15 @@ fn test_create_area_annotation_request_with_question_color() {
 
     assert_eq!(request.color, Some("#ffd400".to_string()));
 }
+
+#[test]
+fn test_highlight_color_from_hex() {
+    for color in HighlightColor::all() {
+        assert_eq!(HighlightColor::from_hex(color.to_hex()), Some(color));
+    }
+    assert_eq!(
+        HighlightColor::from_hex("#FFD400"),
+        Some(HighlightColor::Question)
+    );
+    assert_eq!(HighlightColor::from_hex("#123456"), None);
+}
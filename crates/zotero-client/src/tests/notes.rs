@@ -0,0 +1,46 @@
+//! Unit tests for note types.
+
+use crate::types::NoteResponse;
+
+#[test]
+fn test_note_response_deserialization() {
+    let json = r#"{
+        "success": true,
+        "note": {
+            "id": 789,
+            "key": "NOTE5678",
+            "parentItemKey": "ABCD1234",
+            "title": "Summary",
+            "note": "<div data-schema-version=\"9\"><h1>Summary</h1></div>"
+        }
+    }"#;
+
+    let response: NoteResponse = serde_json::from_str(json).unwrap();
+    let note = response.note.unwrap();
+
+    assert!(response.success);
+    assert_eq!(note.key.as_deref(), Some("NOTE5678"));
+    assert_eq!(note.parent_item_key.as_deref(), Some("ABCD1234"));
+    assert_eq!(note.title.as_deref(), Some("Summary"));
+}
+
+#[test]
+fn test_standalone_note_response_deserialization() {
+    let json = r#"{"success": true, "note": {"id": 1, "key": "NOTE0001", "parentItemKey": null, "title": "Ideas", "note": "<p>Ideas</p>"}}"#;
+
+    let response: NoteResponse = serde_json::from_str(json).unwrap();
+
+    assert_eq!(response.note.unwrap().parent_item_key, None);
+    assert_eq!(response.error, None);
+}
+
+#[test]
+fn test_note_error_response_deserialization() {
+    let json = r#"{"success": false, "error": "Note not found"}"#;
+
+    let response: NoteResponse = serde_json::from_str(json).unwrap();
+
+    assert!(!response.success);
+    assert!(response.note.is_none());
+    assert_eq!(response.error.as_deref(), Some("Note not found"));
+}
@@ -0,0 +1,192 @@
+//! Annotation types: existing annotations, filters and positions.
+
+use serde::{Deserialize, Serialize};
+
+use super::HighlightColor;
+
+/// An annotation on a PDF attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoteroAnnotation {
+    /// Internal database ID.
+    pub id: Option<i64>,
+    /// Unique item key.
+    pub key: Option<String>,
+    /// Parent item key (the PDF attachment).
+    pub parent_item_key: Option<String>,
+    /// Type of annotation (e.g., "highlight", "note").
+    #[serde(alias = "type")]
+    pub annotation_type: Option<String>,
+    /// Highlighted or selected text.
+    pub text: Option<String>,
+    /// User comment on the annotation.
+    pub comment: Option<String>,
+    /// Highlight color (hex code, e.g., "#ffd400").
+    pub color: Option<String>,
+    /// Page label (human-readable page number).
+    pub page_label: Option<String>,
+    /// Sort index for ordering annotations.
+    pub sort_index: Option<String>,
+    /// Position information for the annotation.
+    ///
+    /// Zotero stores positions as JSON strings; both the string and the
+    /// object form are accepted when deserializing.
+    #[serde(default, deserialize_with = "deserialize_position")]
+    pub position: Option<AnnotationPosition>,
+}
+
+impl ZoteroAnnotation {
+    /// Zero-based page index of this annotation, if it has a position.
+    pub fn page_index(&self) -> Option<u32> {
+        self.position.as_ref().map(|p| p.page_index)
+    }
+
+    /// The semantic color of this annotation, if its color is part of the scheme.
+    pub fn semantic_color(&self) -> Option<HighlightColor> {
+        self.color.as_deref().and_then(HighlightColor::from_hex)
+    }
+}
+
+/// Deserialize an annotation position given either as an object or as a JSON string.
+fn deserialize_position<'de, D>(deserializer: D) -> Result<Option<AnnotationPosition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawPosition {
+        Object(AnnotationPosition),
+        Json(String),
+    }
+
+    match Option::<RawPosition>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(RawPosition::Object(position)) => Ok(Some(position)),
+        Some(RawPosition::Json(json)) if json.trim().is_empty() => Ok(None),
+        Some(RawPosition::Json(json)) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Filter for selecting annotations returned by
+/// [`ZoteroClient::list_annotations_matching`](crate::ZoteroClient::list_annotations_matching).
+///
+/// All criteria are optional; an empty filter matches every annotation.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::types::{AnnotationFilter, HighlightColor};
+///
+/// let filter = AnnotationFilter::new()
+///     .with_type("highlight")
+///     .with_semantic_color(HighlightColor::Negative)
+///     .on_page(3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnnotationFilter {
+    /// Only include annotations of this type (e.g., "highlight", "note").
+    pub annotation_type: Option<String>,
+    /// Only include annotations with this color (hex code, case-insensitive).
+    pub color: Option<String>,
+    /// Only include annotations on this zero-based page index.
+    pub page_index: Option<u32>,
+}
+
+impl AnnotationFilter {
+    /// Create an empty filter that matches every annotation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to annotations of the given type.
+    pub fn with_type(mut self, annotation_type: impl Into<String>) -> Self {
+        self.annotation_type = Some(annotation_type.into());
+        self
+    }
+
+    /// Restrict to annotations with the given hex color.
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Restrict to annotations with the given semantic color.
+    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
+        self.color = Some(color.to_hex().to_string());
+        self
+    }
+
+    /// Restrict to annotations on the given zero-based page index.
+    pub fn on_page(mut self, page_index: u32) -> Self {
+        self.page_index = Some(page_index);
+        self
+    }
+
+    /// Check whether an annotation satisfies every criterion of this filter.
+    pub fn matches(&self, annotation: &ZoteroAnnotation) -> bool {
+        if let Some(wanted) = &self.annotation_type {
+            if annotation.annotation_type.as_deref() != Some(wanted.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(wanted) = &self.color {
+            match &annotation.color {
+                Some(color) if color.eq_ignore_ascii_case(wanted) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(wanted) = self.page_index {
+            if annotation.page_index() != Some(wanted) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Position information for a PDF annotation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationPosition {
+    /// Zero-based page index.
+    pub page_index: u32,
+    /// List of rectangles defining the highlight area.
+    /// Each rect is [x1, y1, x2, y2] in PDF coordinates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rects: Vec<Vec<f64>>,
+    /// Strokes of an ink annotation, each a flat list of points
+    /// `[x1, y1, x2, y2, ...]` in PDF coordinates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<Vec<f64>>,
+    /// Stroke width of an ink annotation, in points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+}
+
+impl AnnotationPosition {
+    /// The smallest rect [x1, y1, x2, y2] enclosing all rects and ink paths.
+    ///
+    /// Returns `None` if the position has no coordinates.
+    pub fn bounding_rect(&self) -> Option<[f64; 4]> {
+        let points = self
+            .rects
+            .iter()
+            .chain(&self.paths)
+            .flat_map(|coords| coords.chunks_exact(2));
+
+        points.fold(None, |bounds, point| {
+            let [x1, y1, x2, y2] = bounds.unwrap_or([point[0], point[1], point[0], point[1]]);
+            Some([
+                x1.min(point[0]),
+                y1.min(point[1]),
+                x2.max(point[0]),
+                y2.max(point[1]),
+            ])
+        })
+    }
+}
@@ -0,0 +1,458 @@
+//! Requests to create, update and delete annotations, and their responses.
+
+use serde::{Deserialize, Serialize};
+
+use super::{AnnotationPosition, HighlightColor, ZoteroAnnotation};
+
+/// Side length in points of the icon of a note annotation, as drawn by Zotero.
+pub const NOTE_ANNOTATION_SIZE: f64 = 22.0;
+
+/// Request to create a new annotation.
+///
+/// Text annotations (`highlight`, `underline`) carry the annotated text and
+/// one rect per line; `note` annotations a single icon rect; `ink`
+/// annotations stroke paths and a width.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAnnotationRequest {
+    /// Key of the parent PDF attachment.
+    pub parent_item_key: String,
+    /// Type of annotation (default: "highlight").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation_type: Option<String>,
+    /// Text content of the annotation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// User comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Highlight color (hex code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Page label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_label: Option<String>,
+    /// Sort index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<String>,
+    /// Position information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<AnnotationPosition>,
+}
+
+impl CreateAnnotationRequest {
+    /// Create a new highlight annotation request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zotero_client::types::{CreateAnnotationRequest, AnnotationPosition};
+    ///
+    /// let request = CreateAnnotationRequest::highlight(
+    ///     "ABCD1234",
+    ///     "Important text",
+    ///     0,
+    ///     vec![[100.0, 200.0, 300.0, 220.0].to_vec()],
+    /// );
+    /// ```
+    pub fn highlight(
+        parent_item_key: impl Into<String>,
+        text: impl Into<String>,
+        page_index: u32,
+        rects: Vec<Vec<f64>>,
+    ) -> Self {
+        Self::new(
+            parent_item_key,
+            "highlight",
+            Some(text.into()),
+            AnnotationPosition {
+                page_index,
+                rects,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a new underline annotation request.
+    ///
+    /// Takes the same arguments as [`highlight`](Self::highlight).
+    pub fn underline(
+        parent_item_key: impl Into<String>,
+        text: impl Into<String>,
+        page_index: u32,
+        rects: Vec<Vec<f64>>,
+    ) -> Self {
+        Self::new(
+            parent_item_key,
+            "underline",
+            Some(text.into()),
+            AnnotationPosition {
+                page_index,
+                rects,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a new note (sticky note) annotation request.
+    ///
+    /// The note icon is [`NOTE_ANNOTATION_SIZE`] points square, with its
+    /// top-left corner at `point` ([x, y] in PDF coordinates). The note's
+    /// content is its comment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zotero_client::types::CreateAnnotationRequest;
+    ///
+    /// let request = CreateAnnotationRequest::note("ABCD1234", "Compare with Table 2", 0, [40.0, 700.0]);
+    /// assert_eq!(request.position.unwrap().rects[0], vec![40.0, 678.0, 62.0, 700.0]);
+    /// ```
+    pub fn note(
+        parent_item_key: impl Into<String>,
+        comment: impl Into<String>,
+        page_index: u32,
+        point: [f64; 2],
+    ) -> Self {
+        let [x, y] = point;
+        Self::new(
+            parent_item_key,
+            "note",
+            None,
+            AnnotationPosition {
+                page_index,
+                rects: vec![vec![
+                    x,
+                    y - NOTE_ANNOTATION_SIZE,
+                    x + NOTE_ANNOTATION_SIZE,
+                    y,
+                ]],
+                ..Default::default()
+            },
+        )
+        .with_comment(comment)
+    }
+
+    /// Create a new ink (freehand drawing) annotation request.
+    ///
+    /// Each path is one stroke, given as a flat list of points
+    /// `[x1, y1, x2, y2, ...]` in PDF coordinates; `width` is the stroke width
+    /// in points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zotero_client::types::CreateAnnotationRequest;
+    ///
+    /// // Circle a region roughly with one closed stroke
+    /// let request = CreateAnnotationRequest::ink(
+    ///     "ABCD1234",
+    ///     2,
+    ///     vec![vec![100.0, 500.0, 200.0, 520.0, 210.0, 440.0, 100.0, 500.0]],
+    ///     2.0,
+    /// );
+    /// ```
+    pub fn ink(
+        parent_item_key: impl Into<String>,
+        page_index: u32,
+        paths: Vec<Vec<f64>>,
+        width: f64,
+    ) -> Self {
+        Self::new(
+            parent_item_key,
+            "ink",
+            None,
+            AnnotationPosition {
+                page_index,
+                paths,
+                width: Some(width),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Request of the given type with the default color and page label.
+    fn new(
+        parent_item_key: impl Into<String>,
+        annotation_type: &str,
+        text: Option<String>,
+        position: AnnotationPosition,
+    ) -> Self {
+        Self {
+            parent_item_key: parent_item_key.into(),
+            annotation_type: Some(annotation_type.to_string()),
+            text,
+            comment: None,
+            color: Some("#ffd400".to_string()), // Default yellow
+            page_label: Some((position.page_index + 1).to_string()),
+            sort_index: None,
+            position: Some(position),
+        }
+    }
+
+    /// Set the comment on this annotation.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Set the color of this annotation.
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the color using a semantic `HighlightColor`.
+    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
+        self.color = Some(color.to_hex().to_string());
+        self
+    }
+
+    /// Set the page label shown in Zotero (defaults to the one-based page number).
+    pub fn with_page_label(mut self, page_label: impl Into<String>) -> Self {
+        self.page_label = Some(page_label.into());
+        self
+    }
+
+    /// Set the sort index that orders this annotation in Zotero's sidebar.
+    ///
+    /// The format is `PPPPP|OOOOOO|TTTTT` (page index, character offset and
+    /// distance from the top of the page); `pdf::sort_index` computes it.
+    pub fn with_sort_index(mut self, sort_index: impl Into<String>) -> Self {
+        self.sort_index = Some(sort_index.into());
+        self
+    }
+}
+
+/// Request to create an area/image annotation (for figures, diagrams, etc.).
+///
+/// Area annotations use `annotationType: "image"` and only require position
+/// coordinates (no text content).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAreaAnnotationRequest {
+    /// Key of the parent PDF attachment.
+    pub parent_item_key: String,
+    /// Type of annotation (always "image" for area annotations).
+    pub annotation_type: String,
+    /// User comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Highlight color (hex code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Page label (human-readable page number).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_label: Option<String>,
+    /// Sort index for ordering annotations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<String>,
+    /// Position information (page index and rectangular region).
+    pub position: AnnotationPosition,
+}
+
+impl CreateAreaAnnotationRequest {
+    /// Create a new area annotation request.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_item_key` - The key of the parent PDF attachment
+    /// * `page_index` - Zero-based page index
+    /// * `rect` - Bounding rectangle [x1, y1, x2, y2] in PDF coordinates
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zotero_client::types::CreateAreaAnnotationRequest;
+    ///
+    /// let request = CreateAreaAnnotationRequest::new(
+    ///     "ABCD1234",
+    ///     0, // page index
+    ///     [100.0, 200.0, 300.0, 400.0], // rect
+    /// );
+    /// ```
+    pub fn new(parent_item_key: impl Into<String>, page_index: u32, rect: [f64; 4]) -> Self {
+        Self {
+            parent_item_key: parent_item_key.into(),
+            annotation_type: "image".to_string(),
+            comment: None,
+            color: Some("#ffd400".to_string()), // Default yellow
+            page_label: Some((page_index + 1).to_string()),
+            sort_index: None,
+            position: AnnotationPosition {
+                page_index,
+                rects: vec![rect.to_vec()],
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Set the comment on this annotation.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Set the color of this annotation (hex code).
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the color using a semantic `HighlightColor`.
+    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
+        self.color = Some(color.to_hex().to_string());
+        self
+    }
+
+    /// Set the page label shown in Zotero (defaults to the one-based page number).
+    pub fn with_page_label(mut self, page_label: impl Into<String>) -> Self {
+        self.page_label = Some(page_label.into());
+        self
+    }
+
+    /// Set the sort index (see [`CreateAnnotationRequest::with_sort_index`]).
+    pub fn with_sort_index(mut self, sort_index: impl Into<String>) -> Self {
+        self.sort_index = Some(sort_index.into());
+        self
+    }
+}
+
+/// Request to update an existing annotation.
+///
+/// Only the fields that are set are changed; everything else is left as is.
+/// Setting an empty comment clears it.
+///
+/// # Example
+///
+/// ```
+/// use zotero_client::types::{HighlightColor, UpdateAnnotationRequest};
+///
+/// let request = UpdateAnnotationRequest::new("ANN12345")
+///     .with_comment("Revised note")
+///     .with_semantic_color(HighlightColor::Negative);
+/// assert!(!request.is_empty());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAnnotationRequest {
+    /// Key of the annotation to update.
+    pub key: String,
+    /// New text content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// New user comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// New color (hex code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// New page label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_label: Option<String>,
+    /// New sort index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<String>,
+    /// New position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<AnnotationPosition>,
+}
+
+impl UpdateAnnotationRequest {
+    /// Create an update request for an annotation that changes nothing yet.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            text: None,
+            comment: None,
+            color: None,
+            page_label: None,
+            sort_index: None,
+            position: None,
+        }
+    }
+
+    /// Replace the text of the annotation.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Replace the comment of the annotation (an empty string clears it).
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Replace the color of the annotation (hex code).
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Replace the color using a semantic `HighlightColor`.
+    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
+        self.color = Some(color.to_hex().to_string());
+        self
+    }
+
+    /// Move the annotation to new rectangles on a page.
+    ///
+    /// The page label defaults to the one-based page number. Set the PDF's
+    /// own label and a sort index with [`with_page_label`](Self::with_page_label)
+    /// and [`with_sort_index`](Self::with_sort_index), or Zotero sorts the
+    /// annotation to the top of its new page.
+    pub fn with_position(mut self, page_index: u32, rects: Vec<Vec<f64>>) -> Self {
+        self.page_label = Some((page_index + 1).to_string());
+        self.position = Some(AnnotationPosition {
+            page_index,
+            rects,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Set the page label shown in Zotero.
+    pub fn with_page_label(mut self, page_label: impl Into<String>) -> Self {
+        self.page_label = Some(page_label.into());
+        self
+    }
+
+    /// Set the sort index (see [`CreateAnnotationRequest::with_sort_index`]).
+    pub fn with_sort_index(mut self, sort_index: impl Into<String>) -> Self {
+        self.sort_index = Some(sort_index.into());
+        self
+    }
+
+    /// Whether this request would leave the annotation unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none()
+            && self.comment.is_none()
+            && self.color.is_none()
+            && self.page_label.is_none()
+            && self.sort_index.is_none()
+            && self.position.is_none()
+    }
+}
+
+/// Response from annotation creation or update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAnnotationResponse {
+    /// Whether the operation succeeded.
+    pub success: bool,
+    /// The created or updated annotation.
+    pub annotation: Option<ZoteroAnnotation>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
+
+/// Response from annotation deletion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAnnotationResponse {
+    /// Whether the operation succeeded.
+    pub success: bool,
+    /// Key of the deleted annotation.
+    pub key: Option<String>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
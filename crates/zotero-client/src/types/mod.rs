@@ -3,6 +3,14 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+mod annotation;
+mod annotation_request;
+mod note;
+
+pub use annotation::*;
+pub use annotation_request::*;
+pub use note::*;
+
 /// Semantic highlight colors for annotations.
 ///
 /// These colors follow a predefined scheme for consistent meaning:
@@ -46,6 +54,31 @@ impl HighlightColor {
         }
     }
 
+    /// Look up the semantic color for a hex code (case-insensitive).
+    ///
+    /// Returns `None` for colors outside the semantic scheme.
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|color| color.to_hex().eq_ignore_ascii_case(hex.trim()))
+    }
+
+    /// All semantic colors, in scheme order.
+    #[must_use]
+    pub fn all() -> [Self; 8] {
+        [
+            Self::Section1,
+            Self::Section2,
+            Self::Section3,
+            Self::Positive,
+            Self::Detail,
+            Self::Negative,
+            Self::Code,
+            Self::Question,
+        ]
+    }
+
     /// Get a human-readable description of this color's semantic meaning.
     #[must_use]
     pub fn description(&self) -> &'static str {
@@ -120,6 +153,22 @@ pub struct ZoteroItem {
     pub tags: Vec<Tag>,
 }
 
+impl ZoteroItem {
+    /// The BetterBibTeX citation key stored in the extra field, if any
+    /// (a `Citation Key: ...` or `citekey: ...` line).
+    #[must_use]
+    pub fn citation_key(&self) -> Option<&str> {
+        self.extra.as_deref()?.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            let name = name.trim();
+            let value = value.trim();
+            ((name.eq_ignore_ascii_case("citation key") || name.eq_ignore_ascii_case("citekey"))
+                && !value.is_empty())
+            .then_some(value)
+        })
+    }
+}
+
 /// A tag attached to an item.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Tag {
@@ -148,206 +197,6 @@ pub struct ZoteroAttachment {
     pub item_type: String,
 }
 
-/// An annotation on a PDF attachment.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct ZoteroAnnotation {
-    /// Internal database ID.
-    pub id: Option<i64>,
-    /// Unique item key.
-    pub key: Option<String>,
-    /// Parent item key (the PDF attachment).
-    pub parent_item_key: Option<String>,
-    /// Type of annotation (e.g., "highlight", "note").
-    pub annotation_type: Option<String>,
-    /// Highlighted or selected text.
-    pub text: Option<String>,
-    /// User comment on the annotation.
-    pub comment: Option<String>,
-    /// Highlight color (hex code, e.g., "#ffd400").
-    pub color: Option<String>,
-    /// Page label (human-readable page number).
-    pub page_label: Option<String>,
-    /// Sort index for ordering annotations.
-    pub sort_index: Option<String>,
-    /// Position information for the annotation.
-    pub position: Option<AnnotationPosition>,
-}
-
-/// Position information for a PDF annotation.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct AnnotationPosition {
-    /// Zero-based page index.
-    pub page_index: u32,
-    /// List of rectangles defining the highlight area.
-    /// Each rect is [x1, y1, x2, y2] in PDF coordinates.
-    #[serde(default)]
-    pub rects: Vec<Vec<f64>>,
-}
-
-/// Request to create a new annotation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CreateAnnotationRequest {
-    /// Key of the parent PDF attachment.
-    pub parent_item_key: String,
-    /// Type of annotation (default: "highlight").
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub annotation_type: Option<String>,
-    /// Text content of the annotation.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<String>,
-    /// User comment.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub comment: Option<String>,
-    /// Highlight color (hex code).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
-    /// Page label.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub page_label: Option<String>,
-    /// Sort index.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub sort_index: Option<String>,
-    /// Position information.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub position: Option<AnnotationPosition>,
-}
-
-impl CreateAnnotationRequest {
-    /// Create a new highlight annotation request.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use zotero_client::types::{CreateAnnotationRequest, AnnotationPosition};
-    ///
-    /// let request = CreateAnnotationRequest::highlight(
-    ///     "ABCD1234",
-    ///     "Important text",
-    ///     0,
-    ///     vec![[100.0, 200.0, 300.0, 220.0].to_vec()],
-    /// );
-    /// ```
-    pub fn highlight(
-        parent_item_key: impl Into<String>,
-        text: impl Into<String>,
-        page_index: u32,
-        rects: Vec<Vec<f64>>,
-    ) -> Self {
-        Self {
-            parent_item_key: parent_item_key.into(),
-            annotation_type: Some("highlight".to_string()),
-            text: Some(text.into()),
-            comment: None,
-            color: Some("#ffd400".to_string()), // Default yellow
-            page_label: Some((page_index + 1).to_string()),
-            sort_index: None,
-            position: Some(AnnotationPosition { page_index, rects }),
-        }
-    }
-
-    /// Set the comment on this annotation.
-    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
-        self.comment = Some(comment.into());
-        self
-    }
-
-    /// Set the color of this annotation.
-    pub fn with_color(mut self, color: impl Into<String>) -> Self {
-        self.color = Some(color.into());
-        self
-    }
-
-    /// Set the color using a semantic `HighlightColor`.
-    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
-        self.color = Some(color.to_hex().to_string());
-        self
-    }
-}
-
-/// Request to create an area/image annotation (for figures, diagrams, etc.).
-///
-/// Area annotations use `annotationType: "image"` and only require position
-/// coordinates (no text content).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CreateAreaAnnotationRequest {
-    /// Key of the parent PDF attachment.
-    pub parent_item_key: String,
-    /// Type of annotation (always "image" for area annotations).
-    pub annotation_type: String,
-    /// User comment.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub comment: Option<String>,
-    /// Highlight color (hex code).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
-    /// Page label (human-readable page number).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub page_label: Option<String>,
-    /// Sort index for ordering annotations.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub sort_index: Option<String>,
-    /// Position information (page index and rectangular region).
-    pub position: AnnotationPosition,
-}
-
-impl CreateAreaAnnotationRequest {
-    /// Create a new area annotation request.
-    ///
-    /// # Arguments
-    ///
-    /// * `parent_item_key` - The key of the parent PDF attachment
-    /// * `page_index` - Zero-based page index
-    /// * `rect` - Bounding rectangle [x1, y1, x2, y2] in PDF coordinates
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use zotero_client::types::CreateAreaAnnotationRequest;
-    ///
-    /// let request = CreateAreaAnnotationRequest::new(
-    ///     "ABCD1234",
-    ///     0, // page index
-    ///     [100.0, 200.0, 300.0, 400.0], // rect
-    /// );
-    /// ```
-    pub fn new(parent_item_key: impl Into<String>, page_index: u32, rect: [f64; 4]) -> Self {
-        Self {
-            parent_item_key: parent_item_key.into(),
-            annotation_type: "image".to_string(),
-            comment: None,
-            color: Some("#ffd400".to_string()), // Default yellow
-            page_label: Some((page_index + 1).to_string()),
-            sort_index: None,
-            position: AnnotationPosition {
-                page_index,
-                rects: vec![rect.to_vec()],
-            },
-        }
-    }
-
-    /// Set the comment on this annotation.
-    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
-        self.comment = Some(comment.into());
-        self
-    }
-
-    /// Set the color of this annotation (hex code).
-    pub fn with_color(mut self, color: impl Into<String>) -> Self {
-        self.color = Some(color.into());
-        self
-    }
-
-    /// Set the color using a semantic `HighlightColor`.
-    pub fn with_semantic_color(mut self, color: HighlightColor) -> Self {
-        self.color = Some(color.to_hex().to_string());
-        self
-    }
-}
-
 /// Response from the ping endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -388,17 +237,6 @@ pub struct ChildrenResponse {
     pub children: Vec<serde_json::Value>,
 }
 
-/// Response from annotation creation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateAnnotationResponse {
-    /// Whether the operation succeeded.
-    pub success: bool,
-    /// The created annotation.
-    pub annotation: Option<ZoteroAnnotation>,
-    /// Error message if failed.
-    pub error: Option<String>,
-}
-
 /// A text fragment from a PDF with position information.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextFragment {
@@ -418,6 +256,10 @@ pub struct OutlineItem {
     /// Zero-based page number where this section starts.
     /// None if the outline item doesn't link to a specific page.
     pub page: Option<u32>,
+    /// Distance in points from the top of the page to where the section
+    /// starts. None if the outline item only links to the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top: Option<f64>,
     /// Nested child outline items (subsections).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<OutlineItem>,
@@ -426,11 +268,15 @@ pub struct OutlineItem {
 /// Response from PDF outline extraction.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PdfOutline {
-    /// Whether the PDF has an outline (table of contents).
+    /// Whether the PDF has an outline (table of contents), embedded or inferred.
     pub has_outline: bool,
     /// Total number of pages in the PDF.
     pub total_pages: usize,
     /// Top-level outline items.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub items: Vec<OutlineItem>,
+    /// Whether the outline was inferred from section headings because the
+    /// PDF has no embedded one.
+    #[serde(default)]
+    pub inferred: bool,
 }
@@ -0,0 +1,30 @@
+//! Note types: Zotero notes and responses from note operations.
+
+use serde::{Deserialize, Serialize};
+
+/// A Zotero note, either standalone or a child of a regular item.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoteroNote {
+    /// Internal database ID.
+    pub id: Option<i64>,
+    /// Unique item key.
+    pub key: Option<String>,
+    /// Key of the parent item, or `None` for a standalone note.
+    pub parent_item_key: Option<String>,
+    /// Title Zotero derives from the first line of the note.
+    pub title: Option<String>,
+    /// Note content as HTML.
+    pub note: Option<String>,
+}
+
+/// Response from note creation or update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteResponse {
+    /// Whether the operation succeeded.
+    pub success: bool,
+    /// The created or updated note.
+    pub note: Option<ZoteroNote>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
@@ -0,0 +1,131 @@
+//! Helpers shared by the PDF, image and full-text modules.
+//!
+//! [`FileCache`] keeps a value computed from a file, such as the figure index
+//! or the inferred outline of a PDF, until the file's modification time
+//! changes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
+
+use crate::error::{Result, ZoteroClientError};
+
+/// Modification time of a file, in seconds since the Unix epoch.
+///
+/// # Errors
+///
+/// Returns `FileNotFound` if the file does not exist or cannot be inspected.
+pub fn file_modified(path: impl AsRef<Path>) -> Result<u64> {
+    let path = path.as_ref();
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|time| {
+            time.duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs())
+        })
+        .map_err(|_| ZoteroClientError::FileNotFound {
+            path: path.display().to_string(),
+        })
+}
+
+/// A thread-safe cache of values computed from files, by file path.
+///
+/// Clones share their entries, so one cache can serve every session of a
+/// server. A value is computed again once its file's modification time
+/// differs from the one it was computed at.
+#[derive(Debug)]
+pub struct FileCache<T> {
+    entries: Arc<RwLock<HashMap<PathBuf, Cached<T>>>>,
+}
+
+/// A cached value and the modification time of the file it was computed from.
+#[derive(Debug)]
+struct Cached<T> {
+    modified: u64,
+    value: Arc<T>,
+}
+
+impl<T> Default for FileCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl<T> Clone for FileCache<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: Arc::clone(&self.entries),
+        }
+    }
+}
+
+impl<T> FileCache<T> {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of `path`, computed by `build` on first use and after the file changes.
+    pub fn get_or_insert_with(
+        &self,
+        path: impl AsRef<Path>,
+        build: impl FnOnce(&Path) -> Result<T>,
+    ) -> Result<Arc<T>> {
+        let path = path.as_ref();
+        self.get_or_build(path, file_modified(path)?, build)
+    }
+
+    /// The cached value of `path` if it was computed at `modified`, else a new one from `build`.
+    pub(crate) fn get_or_build(
+        &self,
+        path: &Path,
+        modified: u64,
+        build: impl FnOnce(&Path) -> Result<T>,
+    ) -> Result<Arc<T>> {
+        {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            if let Some(cached) = entries.get(path).filter(|c| c.modified == modified) {
+                return Ok(Arc::clone(&cached.value));
+            }
+        }
+
+        let value = Arc::new(build(path)?);
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            path.to_path_buf(),
+            Cached {
+                modified,
+                value: Arc::clone(&value),
+            },
+        );
+        Ok(value)
+    }
+
+    /// Remove the cached value of a file.
+    pub fn invalidate(&self, path: impl AsRef<Path>) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.remove(path.as_ref());
+    }
+
+    /// Remove all cached values.
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.clear();
+    }
+
+    /// Number of cached values.
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+//! Unit tests for the shared helpers.
+
+use super::*;
+
+#[test]
+fn test_file_modified() {
+    assert!(file_modified(std::env::temp_dir()).unwrap() > 0);
+    assert!(matches!(
+        file_modified("/nonexistent/file.pdf"),
+        Err(ZoteroClientError::FileNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_file_cache_rebuilds_when_file_changes() {
+    let cache = FileCache::new();
+    let path = Path::new("/papers/a.pdf");
+    let mut builds = 0;
+    let mut build = |value: &str, modified: u64| {
+        cache
+            .get_or_build(path, modified, |_| {
+                builds += 1;
+                Ok(value.to_string())
+            })
+            .unwrap()
+    };
+
+    assert_eq!(*build("first", 10), "first");
+    assert_eq!(*build("second", 10), "first");
+    assert_eq!(*build("third", 20), "third");
+    assert_eq!(builds, 2);
+    assert_eq!(cache.len(), 1);
+
+    // Clones share the entries
+    cache.clone().invalidate(path);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_file_cache_does_not_keep_failures() {
+    let cache: FileCache<String> = FileCache::new();
+    let path = Path::new("/papers/a.pdf");
+    assert!(cache
+        .get_or_build(path, 10, |_| Err(ZoteroClientError::Pdf("damaged".into())))
+        .is_err());
+    assert!(cache.is_empty());
+    assert!(cache
+        .get_or_insert_with("/nonexistent/file.pdf", |_| Ok(String::new()))
+        .is_err());
+}
@@ -231,6 +231,76 @@ async fn test_get_pdf_attachments() {
     }
 }
 
+/// Test resolving a PDF attachment key directly to its file path.
+#[tokio::test]
+async fn test_resolve_pdf_path() {
+    let client = ZoteroClient::new();
+
+    // Skip if Zotero is not running
+    if client.ping().await.is_err() {
+        println!("Skipping test: Zotero not running");
+        return;
+    }
+
+    let items = client.list_items(20).await.unwrap();
+
+    for item in items {
+        let pdfs = client.get_pdf_attachments(&item.key).await.unwrap();
+
+        if let Some(pdf) = pdfs.first() {
+            // Start from an empty index so the direct lookup is exercised
+            client.attachment_index().clear();
+
+            match client.resolve_pdf_path(&pdf.key).await {
+                Ok(path) => {
+                    println!("Resolved {} to {}", pdf.key, path.display());
+                    assert!(client.attachment_index().get(&pdf.key).is_some());
+                }
+                Err(e) => println!("Resolve failed: {}", e),
+            }
+            return;
+        }
+    }
+
+    println!("No PDF attachments found to resolve");
+}
+
+/// Test listing annotations on a PDF attachment.
+#[tokio::test]
+async fn test_list_annotations() {
+    let client = ZoteroClient::new();
+
+    // Skip if Zotero is not running
+    if client.ping().await.is_err() {
+        println!("Skipping test: Zotero not running");
+        return;
+    }
+
+    let items = client.list_items(20).await.unwrap();
+
+    for item in items {
+        let pdfs = client.get_pdf_attachments(&item.key).await.unwrap();
+
+        if let Some(pdf) = pdfs.first() {
+            let annotations = client.list_annotations(&pdf.key).await.unwrap();
+            println!("Found {} annotations on {}", annotations.len(), pdf.key);
+
+            for annotation in &annotations {
+                assert_eq!(
+                    annotation.parent_item_key.as_deref(),
+                    Some(pdf.key.as_str())
+                );
+            }
+
+            let sort_indexes: Vec<_> = annotations.iter().map(|a| &a.sort_index).collect();
+            assert!(sort_indexes.windows(2).all(|w| w[0] <= w[1]));
+            return;
+        }
+    }
+
+    println!("No PDF attachments found to list annotations");
+}
+
 /// Test creating an annotation (commented out by default to avoid modifying library).
 #[tokio::test]
 #[ignore = "This test modifies the library - run manually with --include-ignored"]
@@ -283,6 +353,46 @@ async fn test_create_annotation() {
     println!("No PDF attachments found to annotate");
 }
 
+/// Test creating and updating a child note (ignored by default to avoid modifying library).
+#[tokio::test]
+#[ignore = "This test modifies the library - run manually with --include-ignored"]
+async fn test_create_and_update_note() {
+    let client = ZoteroClient::new();
+
+    // Skip if Zotero is not running
+    if client.ping().await.is_err() {
+        println!("Skipping test: Zotero not running");
+        return;
+    }
+
+    let items = client.list_items(1).await.unwrap();
+    let Some(item) = items.first() else {
+        println!("No items found to add a note to");
+        return;
+    };
+
+    let response = client
+        .create_note(
+            Some(&item.key),
+            "<h1>Integration test</h1><p>Created by zotero-client</p>",
+        )
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let note = response.note.unwrap();
+    assert_eq!(note.parent_item_key.as_deref(), Some(item.key.as_str()));
+    assert_eq!(note.title.as_deref(), Some("Integration test"));
+
+    let key = note.key.unwrap();
+    let response = client
+        .update_note(&key, "<h1>Integration test (updated)</h1>")
+        .await
+        .unwrap();
+    assert!(response.success);
+    println!("Created and updated note: {}", key);
+}
+
 /// Test finding by citation key.
 #[tokio::test]
 async fn test_find_by_citation_key() {
@@ -0,0 +1,103 @@
+//! HTTP transports.
+//!
+//! Serves streamable HTTP at `/mcp` so several clients can share one server.
+//! Responses are streamed as server-sent events, and each client gets its
+//! own session. Clients that only speak the legacy HTTP+SSE transport
+//! connect at `/sse` instead (see [`crate::sse`]). When a token is
+//! configured, every request must carry it as `Authorization: Bearer <token>`.
+
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use zotero_mcp::{
+    LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService, ZoteroMcpServer,
+};
+
+use crate::sse;
+
+/// Path the MCP endpoint is served at.
+const MCP_PATH: &str = "/mcp";
+
+/// Serve MCP requests over streamable HTTP and SSE until interrupted.
+pub async fn serve(
+    zotero_url: &str,
+    index: PathBuf,
+    bind: SocketAddr,
+    token: Option<String>,
+) -> Result<()> {
+    // One server for all sessions, so they share its client, attachment and
+    // figure caches and the full-text index; clones share its state.
+    let server = ZoteroMcpServer::new(zotero_url).with_index_path(index);
+    let sse = sse::router(server.clone());
+    let service = StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    let mut router = Router::new().nest_service(MCP_PATH, service).merge(sse);
+    match token {
+        Some(token) => {
+            router = router.layer(middleware::from_fn_with_state(Arc::new(token), authorize));
+        }
+        None if !bind.ip().is_loopback() => {
+            tracing::warn!("Serving on {} without a bearer token", bind.ip());
+        }
+        None => {}
+    }
+
+    let listener = tokio::net::TcpListener::bind(bind)
+        .await
+        .with_context(|| format!("Failed to bind {}", bind))?;
+    tracing::info!(
+        "Listening on http://{}{} (legacy SSE at {})",
+        bind,
+        MCP_PATH,
+        sse::SSE_PATH
+    );
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    tracing::info!("Server shutting down");
+    Ok(())
+}
+
+/// Reject requests that do not carry the configured bearer token.
+async fn authorize(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(provided) if tokens_match(provided, &token) => next.run(request).await,
+        _ => (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid bearer token",
+        )
+            .into_response(),
+    }
+}
+
+/// Compare tokens in time independent of where they first differ.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
@@ -2,9 +2,88 @@
 //!
 //! Command-line interface for the Zotero MCP server.
 
-use anyhow::Result;
+mod http;
+mod sse;
+
+use std::{
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
-use zotero_mcp::{stdio, ServiceExt, ZoteroMcpServer};
+use zotero_client::{
+    fulltext::{default_index_path, FulltextIndex},
+    ZoteroClient,
+};
+use zotero_mcp::{export_annotations, stdio, update_index, ServiceExt, ZoteroMcpServer};
+
+#[derive(Parser)]
+#[command(
+    name = "zotero-mcp",
+    version,
+    about = "MCP server for Zotero",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    /// URL of the Zotero MCP plugin API
+    #[arg(
+        long,
+        env = "ZOTERO_URL",
+        default_value = "http://localhost:23119/mcp",
+        global = true
+    )]
+    zotero_url: String,
+
+    /// Location of the full-text search index [default: zotero-mcp/fulltext.json
+    /// in the user's cache directory]
+    #[arg(long, env = "ZOTERO_MCP_INDEX", global = true)]
+    index: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    serve: ServeArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the MCP server (the default)
+    Serve(ServeArgs),
+    /// Export an item's annotations as a Markdown note for Obsidian
+    Export {
+        /// BetterBibTeX citation key of the item
+        citekey: String,
+        /// File to write, or a directory (such as an Obsidian vault folder) to
+        /// write `<citekey>.md` into. Prints to stdout if omitted.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Build or update the full-text search index of the library's PDFs
+    Index,
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// Serve HTTP instead of stdio, so several clients can share one server:
+    /// streamable HTTP at /mcp and the legacy SSE transport at /sse
+    #[arg(long)]
+    http: bool,
+
+    /// Address to bind the HTTP server to
+    #[arg(long, env = "ZOTERO_MCP_HOST", default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// Port to bind the HTTP server to
+    #[arg(long, env = "ZOTERO_MCP_PORT", default_value_t = 8000)]
+    port: u16,
+
+    /// Bearer token HTTP clients must send in the Authorization header
+    #[arg(long, env = "ZOTERO_MCP_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -14,15 +93,27 @@ async fn main() -> Result<()> {
         .with(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
         .init();
 
-    tracing::info!("Starting Zotero MCP Server");
+    let cli = Cli::parse();
+    let index = cli.index.unwrap_or_else(default_index_path);
+
+    match cli.command.unwrap_or(Command::Serve(cli.serve)) {
+        Command::Serve(args) if args.http => {
+            let bind = (args.host, args.port).into();
+            http::serve(&cli.zotero_url, index, bind, args.token).await
+        }
+        Command::Serve(_) => serve(&cli.zotero_url, index).await,
+        Command::Export { citekey, output } => export(&cli.zotero_url, &citekey, output).await,
+        Command::Index => build_index(&cli.zotero_url, &index).await,
+    }
+}
 
-    // Get Zotero URL from environment or use default
-    let zotero_url =
-        std::env::var("ZOTERO_URL").unwrap_or_else(|_| "http://localhost:23119/mcp".to_string());
+/// Serve MCP requests over stdio until the client disconnects.
+async fn serve(zotero_url: &str, index: PathBuf) -> Result<()> {
+    tracing::info!("Starting Zotero MCP Server");
     tracing::info!("Connecting to Zotero at {}", zotero_url);
 
     // Create the server
-    let server = ZoteroMcpServer::new(&zotero_url);
+    let server = ZoteroMcpServer::new(zotero_url).with_index_path(index);
 
     // Serve over stdio
     let (stdin, stdout) = stdio();
@@ -36,3 +127,43 @@ async fn main() -> Result<()> {
     tracing::info!("Server shutting down");
     Ok(())
 }
+
+/// Export annotations to a file, a directory or stdout.
+async fn export(zotero_url: &str, citekey: &str, output: Option<PathBuf>) -> Result<()> {
+    let client = ZoteroClient::with_base_url(zotero_url);
+    let markdown = export_annotations(&client, citekey).await?;
+
+    let Some(mut path) = output else {
+        print!("{}", markdown);
+        return Ok(());
+    };
+    if path.is_dir() {
+        path.push(format!("{}.md", citekey));
+    }
+
+    std::fs::write(&path, markdown)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    tracing::info!("Exported annotations of {} to {}", citekey, path.display());
+    Ok(())
+}
+
+/// Synchronize the full-text search index with the library.
+async fn build_index(zotero_url: &str, path: &Path) -> Result<()> {
+    let client = ZoteroClient::with_base_url(zotero_url);
+    let mut index = FulltextIndex::load(path)?;
+
+    tracing::info!("Updating full-text index at {}", path.display());
+    let update = update_index(&client, &mut index, path).await?;
+
+    for failure in &update.failed {
+        tracing::warn!("Could not index {}", failure);
+    }
+    tracing::info!(
+        "Indexed {} attachments, removed {}; the index holds {} attachments ({} pages)",
+        update.indexed,
+        update.removed,
+        index.len(),
+        index.page_count()
+    );
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Legacy HTTP+SSE transport, from the 2024-11-05 protocol version.
+//!
+//! A client opens an event stream with `GET /sse`. Its first `endpoint`
+//! event names the URL to `POST` JSON-RPC messages to,
+//! `/message?sessionId=<id>`, and the server's responses and notifications
+//! arrive as `message` events on the stream. Each stream is its own
+//! session, which ends when the client closes the stream.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Json, Router,
+};
+use futures::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::PollSender;
+use zotero_mcp::{
+    session_id, ClientJsonRpcMessage, ServerJsonRpcMessage, ServiceExt, SessionId, ZoteroMcpServer,
+};
+
+/// Path of the event stream.
+pub const SSE_PATH: &str = "/sse";
+
+/// Path clients post their messages to.
+const MESSAGE_PATH: &str = "/message";
+
+/// Messages buffered per session and direction.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Open sessions, by ID, with the channel to their server.
+type Sessions = Arc<Mutex<HashMap<SessionId, mpsc::Sender<ClientJsonRpcMessage>>>>;
+
+#[derive(Clone)]
+struct SseState {
+    server: ZoteroMcpServer,
+    sessions: Sessions,
+}
+
+#[derive(Deserialize)]
+struct MessageQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+/// Routes of the legacy transport, serving sessions of `server`.
+pub fn router(server: ZoteroMcpServer) -> Router {
+    Router::new()
+        .route(SSE_PATH, get(connect))
+        .route(MESSAGE_PATH, post(message))
+        .with_state(SseState {
+            server,
+            sessions: Sessions::default(),
+        })
+}
+
+/// Removes a session when its event stream is dropped, which closes the
+/// server's input and ends the session.
+struct SessionGuard {
+    id: SessionId,
+    sessions: Sessions,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.remove(&self.id);
+    }
+}
+
+/// Start a session and stream its endpoint and the server's messages.
+async fn connect(
+    State(state): State<SseState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let id = session_id();
+    let (to_server, from_client) = mpsc::channel::<ClientJsonRpcMessage>(CHANNEL_CAPACITY);
+    let (to_client, from_server) = mpsc::channel::<ServerJsonRpcMessage>(CHANNEL_CAPACITY);
+    state
+        .sessions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(id.clone(), to_server);
+
+    let server = state.server.clone();
+    let session = id.clone();
+    tokio::spawn(async move {
+        let transport = (PollSender::new(to_client), ReceiverStream::new(from_client));
+        match server.serve(transport).await {
+            Ok(running) => {
+                let _ = running.waiting().await;
+            }
+            Err(e) => tracing::warn!("SSE session {} failed: {}", session, e),
+        }
+    });
+
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("{}?sessionId={}", MESSAGE_PATH, id));
+    let guard = SessionGuard {
+        id,
+        sessions: state.sessions,
+    };
+    let messages = ReceiverStream::new(from_server).filter_map(move |message| {
+        let _session = &guard;
+        let event = match serde_json::to_string(&message) {
+            Ok(json) => Some(Ok(Event::default().event("message").data(json))),
+            Err(e) => {
+                tracing::warn!("Failed to serialize SSE message: {}", e);
+                None
+            }
+        };
+        async move { event }
+    });
+
+    Sse::new(stream::once(async { Ok(endpoint) }).chain(messages)).keep_alive(KeepAlive::default())
+}
+
+/// Hand a client message to the server of its session.
+async fn message(
+    State(state): State<SseState>,
+    Query(query): Query<MessageQuery>,
+    Json(message): Json<ClientJsonRpcMessage>,
+) -> StatusCode {
+    let sender = state
+        .sessions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(query.session_id.as_str())
+        .cloned();
+    let Some(sender) = sender else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    match sender.send(message).await {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(_) => StatusCode::GONE,
+    }
+}
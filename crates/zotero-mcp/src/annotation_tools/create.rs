@@ -0,0 +1,589 @@
+//! Annotation creation tool implementations.
+//!
+//! Every created annotation gets the PDF's page label and a sort index
+//! computed from its position, so it appears in reading order in Zotero.
+
+use zotero_client::{
+    pdf::{
+        anchor_text, extract_page_text, fuzzy_anchor_text, label_for_page, page_label,
+        page_label_ranges, TextAnchor,
+    },
+    types::{
+        CreateAnnotationRequest, CreateAnnotationResponse, CreateAreaAnnotationRequest,
+        HighlightColor, NOTE_ANNOTATION_SIZE,
+    },
+    ZoteroClient,
+};
+
+use crate::tools::{find_pdf_path, HighlightColorParam, ToolError};
+
+/// Gap in points between a note annotation and the text it annotates.
+const NOTE_GAP: f64 = 2.0;
+
+/// Stroke width in points of ink annotations when none is given.
+const DEFAULT_INK_WIDTH: f64 = 2.0;
+
+/// Text markup annotations, which cover a quoted passage.
+#[derive(Debug, Clone, Copy)]
+enum Markup {
+    Highlight,
+    Underline,
+}
+
+impl Markup {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Highlight => "highlight",
+            Self::Underline => "underline",
+        }
+    }
+
+    fn request(
+        self,
+        attachment_key: &str,
+        text: &str,
+        page: u32,
+        rects: Vec<Vec<f64>>,
+    ) -> CreateAnnotationRequest {
+        match self {
+            Self::Highlight => {
+                CreateAnnotationRequest::highlight(attachment_key, text, page, rects)
+            }
+            Self::Underline => {
+                CreateAnnotationRequest::underline(attachment_key, text, page, rects)
+            }
+        }
+    }
+}
+
+/// Check that a minimum similarity score can be reached and does not accept
+/// every passage.
+fn check_min_score(min_score: Option<f64>) -> Result<(), ToolError> {
+    match min_score {
+        Some(score) if !(score > 0.0 && score <= 1.0) => Err(ToolError::InvalidParameter(format!(
+            "min_score must be above 0.0 and at most 1.0, got {}",
+            score
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Locate a quoted passage starting on a 1-based page.
+///
+/// Tries an exact anchor first; with `min_score`, falls back to the most
+/// similar passage if its score reaches the threshold. Returns the anchor
+/// and, for approximate matches, the score.
+fn locate_passage(
+    path: &str,
+    text: &str,
+    page: u32,
+    min_score: Option<f64>,
+) -> Result<(TextAnchor, Option<f64>), ToolError> {
+    // Convert 1-based page to 0-based index
+    let page_index = page.saturating_sub(1) as usize;
+
+    let exact =
+        anchor_text(path, page_index, text).map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    match (exact, min_score) {
+        (Some(anchor), _) => Ok((anchor, None)),
+        (None, None) => Err(ToolError::TextNotFound(page, text.to_string())),
+        (None, Some(min_score)) => {
+            let found = fuzzy_anchor_text(path, page_index, text)
+                .map_err(|e| ToolError::PdfError(e.to_string()))?
+                .ok_or_else(|| ToolError::TextNotFound(page, text.to_string()))?;
+
+            if found.score < min_score {
+                return Err(ToolError::MatchBelowThreshold(
+                    page,
+                    found.score,
+                    min_score,
+                    found.anchor.text,
+                ));
+            }
+            Ok((found.anchor, Some(found.score)))
+        }
+    }
+}
+
+/// Describe the passage an annotation was anchored to.
+fn describe_match(anchor: &TextAnchor, score: Option<f64>) -> String {
+    match score {
+        Some(score) => format!(
+            "Approximate match (score {:.2})\nMatched text: {}",
+            score, anchor.text
+        ),
+        None => format!("Text: {}", anchor.text),
+    }
+}
+
+/// The key of a created annotation, or the error the plugin reported.
+fn created_key(response: CreateAnnotationResponse) -> Result<String, ToolError> {
+    if !response.success {
+        return Err(ToolError::AnnotationFailed(
+            response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string()),
+        ));
+    }
+
+    Ok(response
+        .annotation
+        .and_then(|a| a.key)
+        .unwrap_or_else(|| "(unknown)".to_string()))
+}
+
+/// Create a text highlight annotation.
+///
+/// The text is anchored starting on `page`, ignoring whitespace differences and
+/// hyphenated line ends. A passage that continues onto the following pages is
+/// highlighted with one annotation per page; the comment goes on the first.
+/// If a later page fails, the annotations already created are deleted again.
+///
+/// With `min_score`, a quote that cannot be anchored exactly falls back to the
+/// most similar passage, provided its similarity score reaches the threshold.
+pub async fn create_highlight(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    text: &str,
+    page: u32,
+    color: HighlightColorParam,
+    comment: Option<&str>,
+    min_score: Option<f64>,
+) -> Result<String, ToolError> {
+    create_markup(
+        client,
+        Markup::Highlight,
+        attachment_key,
+        text,
+        page,
+        color,
+        comment,
+        min_score,
+    )
+    .await
+}
+
+/// Create a text underline annotation.
+///
+/// Anchors the text exactly like [`create_highlight`].
+pub async fn create_underline(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    text: &str,
+    page: u32,
+    color: HighlightColorParam,
+    comment: Option<&str>,
+    min_score: Option<f64>,
+) -> Result<String, ToolError> {
+    create_markup(
+        client,
+        Markup::Underline,
+        attachment_key,
+        text,
+        page,
+        color,
+        comment,
+        min_score,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn create_markup(
+    client: &ZoteroClient,
+    markup: Markup,
+    attachment_key: &str,
+    text: &str,
+    page: u32,
+    color: HighlightColorParam,
+    comment: Option<&str>,
+    min_score: Option<f64>,
+) -> Result<String, ToolError> {
+    check_min_score(min_score)?;
+    let path = find_pdf_path(client, attachment_key).await?;
+    let (anchor, score) = locate_passage(&path, text, page, min_score)?;
+
+    let labels = page_label_ranges(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+    let semantic_color: HighlightColor = color.into();
+    let mut created = Vec::new();
+
+    for (i, segment) in anchor.segments.iter().enumerate() {
+        // Convert to the format expected by Zotero
+        let rects_vec: Vec<Vec<f64>> = segment.rects.iter().map(|r| r.to_vec()).collect();
+
+        let mut request = markup
+            .request(attachment_key, &segment.text, segment.page, rects_vec)
+            .with_semantic_color(semantic_color)
+            .with_page_label(label_for_page(&labels, segment.page))
+            .with_sort_index(segment.sort_index());
+
+        if let (0, Some(c)) = (i, comment) {
+            request = request.with_comment(c);
+        }
+
+        let result = match client.create_annotation(request).await {
+            Ok(response) => created_key(response),
+            Err(e) => Err(e.into()),
+        };
+        match result {
+            Ok(ann_key) => created.push((ann_key, segment.page + 1)),
+            Err(e) => return Err(roll_back(client, &created, e).await),
+        }
+    }
+
+    let annotations = if let [(ann_key, _)] = created.as_slice() {
+        format!(
+            "Created {} annotation: {}\nPage: {}",
+            markup.name(),
+            ann_key,
+            page
+        )
+    } else {
+        let lines: Vec<String> = created
+            .iter()
+            .map(|(ann_key, p)| format!("  - {} (page {})", ann_key, p))
+            .collect();
+        format!(
+            "Created {} {} annotations (one per page):\n{}",
+            created.len(),
+            markup.name(),
+            lines.join("\n")
+        )
+    };
+
+    Ok(format!(
+        "{}\n\
+         {}\n\
+         Color: {} ({})",
+        annotations,
+        describe_match(&anchor, score),
+        semantic_color.to_hex(),
+        semantic_color.description()
+    ))
+}
+
+/// Delete the annotations created for the earlier pages of a passage after a
+/// later page failed, so no partial markup is left behind. Keys that could not
+/// be deleted are added to the error for the caller to clean up.
+async fn roll_back(
+    client: &ZoteroClient,
+    created: &[(String, u32)],
+    error: ToolError,
+) -> ToolError {
+    let mut remaining = Vec::new();
+    for (ann_key, _) in created {
+        let deleted = client
+            .delete_annotation(ann_key)
+            .await
+            .is_ok_and(|response| response.success);
+        if !deleted {
+            remaining.push(ann_key.as_str());
+        }
+    }
+
+    if remaining.is_empty() {
+        return error;
+    }
+    ToolError::AnnotationFailed(format!(
+        "{}; annotations already created on earlier pages could not be deleted: {}",
+        error,
+        remaining.join(", ")
+    ))
+}
+
+/// Create an area/image annotation.
+///
+/// Like highlights, the annotation is labelled with the PDF's page label and
+/// sorted by the text closest to the top-left corner of its rect.
+pub async fn create_area_annotation(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    page: u32,
+    rect: [f64; 4],
+    color: HighlightColorParam,
+    comment: Option<&str>,
+) -> Result<String, ToolError> {
+    let path = find_pdf_path(client, attachment_key).await?;
+
+    // Convert 1-based page to 0-based index
+    let page_index = page.saturating_sub(1);
+
+    let page_text = extract_page_text(&path, page_index as usize)
+        .map_err(|e| ToolError::PdfError(e.to_string()))?;
+    let label = page_label(&path, page_index).map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    // Create the annotation request
+    let semantic_color: HighlightColor = color.into();
+    let mut request = CreateAreaAnnotationRequest::new(attachment_key, page_index, rect)
+        .with_semantic_color(semantic_color)
+        .with_page_label(label)
+        .with_sort_index(page_text.area_sort_index(rect));
+
+    if let Some(c) = comment {
+        request = request.with_comment(c);
+    }
+
+    // Create the annotation
+    let ann_key = created_key(client.create_area_annotation(request).await?)?;
+
+    Ok(format!(
+        "Created area annotation: {}\n\
+         Page: {}\n\
+         Rect: [{:.1}, {:.1}, {:.1}, {:.1}]\n\
+         Color: {} ({})\n\
+         Comment: {}",
+        ann_key,
+        page,
+        rect[0],
+        rect[1],
+        rect[2],
+        rect[3],
+        semantic_color.to_hex(),
+        semantic_color.description(),
+        comment.unwrap_or("(none)")
+    ))
+}
+
+/// Where to pin a note beside a passage: level with the top of its first
+/// line, just left of where the line starts (in the margin for a left
+/// column, in the gutter for a right one).
+fn note_point(anchor: &TextAnchor) -> Option<(u32, [f64; 2])> {
+    let segment = anchor.segments.first()?;
+    let rect = segment.rects.first()?;
+    let x = (rect[0] - NOTE_ANNOTATION_SIZE - NOTE_GAP).max(0.0);
+    Some((segment.page, [x, rect[3]]))
+}
+
+/// Create a note (sticky note) annotation pinned beside a quoted passage.
+///
+/// The passage is anchored like a highlight but left unmarked; the note is
+/// placed next to its first line and sorted with it.
+pub async fn create_note_annotation(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    text: &str,
+    page: u32,
+    comment: &str,
+    color: HighlightColorParam,
+    min_score: Option<f64>,
+) -> Result<String, ToolError> {
+    check_min_score(min_score)?;
+    let path = find_pdf_path(client, attachment_key).await?;
+    let (anchor, score) = locate_passage(&path, text, page, min_score)?;
+
+    let (page_index, point) =
+        note_point(&anchor).ok_or_else(|| ToolError::TextNotFound(page, text.to_string()))?;
+    let labels = page_label_ranges(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    let semantic_color: HighlightColor = color.into();
+    let request = CreateAnnotationRequest::note(attachment_key, comment, page_index, point)
+        .with_semantic_color(semantic_color)
+        .with_page_label(label_for_page(&labels, page_index))
+        .with_sort_index(anchor.segments[0].sort_index());
+
+    let ann_key = created_key(client.create_annotation(request).await?)?;
+
+    Ok(format!(
+        "Created note annotation: {}\n\
+         Page: {}\n\
+         {}\n\
+         Color: {} ({})\n\
+         Comment: {}",
+        ann_key,
+        page_index + 1,
+        describe_match(&anchor, score),
+        semantic_color.to_hex(),
+        semantic_color.description(),
+        comment
+    ))
+}
+
+/// Check that ink paths are non-empty lists of at least two [x, y] points.
+fn validate_ink_paths(paths: &[Vec<f64>]) -> Result<(), ToolError> {
+    if paths.is_empty() {
+        return Err(ToolError::InvalidInkPath(
+            "at least one path is required".to_string(),
+        ));
+    }
+
+    for (i, path) in paths.iter().enumerate() {
+        if path.len() < 4 || path.len() % 2 != 0 {
+            return Err(ToolError::InvalidInkPath(format!(
+                "path {} must be a flat list of at least two [x, y] points, got {} numbers",
+                i + 1,
+                path.len()
+            )));
+        }
+        if path.iter().any(|v| !v.is_finite()) {
+            return Err(ToolError::InvalidInkPath(format!(
+                "path {} contains a non-finite coordinate",
+                i + 1
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Create an ink (freehand drawing) annotation.
+///
+/// Each path is a stroke given as `[x1, y1, x2, y2, ...]` in PDF coordinates;
+/// `width` defaults to 2 points.
+pub async fn create_ink_annotation(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    page: u32,
+    paths: Vec<Vec<f64>>,
+    width: Option<f64>,
+    color: HighlightColorParam,
+    comment: Option<&str>,
+) -> Result<String, ToolError> {
+    validate_ink_paths(&paths)?;
+
+    let path = find_pdf_path(client, attachment_key).await?;
+
+    // Convert 1-based page to 0-based index
+    let page_index = page.saturating_sub(1);
+
+    let page_text = extract_page_text(&path, page_index as usize)
+        .map_err(|e| ToolError::PdfError(e.to_string()))?;
+    let label = page_label(&path, page_index).map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    let strokes = paths.len();
+    let semantic_color: HighlightColor = color.into();
+    let mut request = CreateAnnotationRequest::ink(
+        attachment_key,
+        page_index,
+        paths,
+        width.unwrap_or(DEFAULT_INK_WIDTH),
+    )
+    .with_semantic_color(semantic_color)
+    .with_page_label(label);
+
+    if let Some(bounds) = request.position.as_ref().and_then(|p| p.bounding_rect()) {
+        request = request.with_sort_index(page_text.area_sort_index(bounds));
+    }
+    if let Some(c) = comment {
+        request = request.with_comment(c);
+    }
+
+    let ann_key = created_key(client.create_annotation(request).await?)?;
+
+    Ok(format!(
+        "Created ink annotation: {}\n\
+         Page: {}\n\
+         Strokes: {}\n\
+         Color: {} ({})\n\
+         Comment: {}",
+        ann_key,
+        page,
+        strokes,
+        semantic_color.to_hex(),
+        semantic_color.description(),
+        comment.unwrap_or("(none)")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zotero_client::pdf::{AnchorSegment, TextAnchor};
+
+    #[test]
+    fn test_note_point_left_of_first_line() {
+        let anchor = TextAnchor {
+            text: "quoted text".to_string(),
+            segments: vec![AnchorSegment {
+                page: 4,
+                text: "quoted text".to_string(),
+                rects: vec![[72.0, 690.0, 300.0, 700.0], [72.0, 678.0, 120.0, 688.0]],
+                offset: 0,
+                top: 92.0,
+            }],
+        };
+
+        let (page, [x, y]) = note_point(&anchor).unwrap();
+
+        assert_eq!(page, 4);
+        assert_eq!(x, 72.0 - NOTE_ANNOTATION_SIZE - NOTE_GAP);
+        assert_eq!(y, 700.0);
+    }
+
+    #[test]
+    fn test_note_point_clamped_to_page_edge() {
+        let anchor = TextAnchor {
+            text: "x".to_string(),
+            segments: vec![AnchorSegment {
+                page: 0,
+                text: "x".to_string(),
+                rects: vec![[5.0, 10.0, 10.0, 20.0]],
+                offset: 0,
+                top: 0.0,
+            }],
+        };
+
+        assert_eq!(note_point(&anchor).unwrap().1, [0.0, 20.0]);
+    }
+
+    #[test]
+    fn test_validate_ink_paths() {
+        assert!(validate_ink_paths(&[vec![0.0, 0.0, 10.0, 10.0]]).is_ok());
+        assert!(matches!(
+            validate_ink_paths(&[]),
+            Err(ToolError::InvalidInkPath(_))
+        ));
+        assert!(matches!(
+            validate_ink_paths(&[vec![0.0, 0.0, 10.0]]),
+            Err(ToolError::InvalidInkPath(_))
+        ));
+        assert!(matches!(
+            validate_ink_paths(&[vec![0.0, 0.0]]),
+            Err(ToolError::InvalidInkPath(_))
+        ));
+        assert!(matches!(
+            validate_ink_paths(&[vec![0.0, f64::NAN, 1.0, 1.0]]),
+            Err(ToolError::InvalidInkPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_created_key_reports_failure() {
+        let response = CreateAnnotationResponse {
+            success: false,
+            annotation: None,
+            error: Some("Parent item not found".to_string()),
+        };
+
+        assert!(matches!(
+            created_key(response),
+            Err(ToolError::AnnotationFailed(message)) if message == "Parent item not found"
+        ));
+    }
+
+    #[test]
+    fn test_min_score_must_be_a_reachable_threshold() {
+        assert!(check_min_score(None).is_ok());
+        assert!(check_min_score(Some(0.85)).is_ok());
+        assert!(check_min_score(Some(1.0)).is_ok());
+        for score in [0.0, -0.5, 1.01, 85.0, f64::NAN] {
+            assert!(matches!(
+                check_min_score(Some(score)),
+                Err(ToolError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roll_back_without_created_annotations_keeps_error() {
+        let client = ZoteroClient::new();
+        let error = ToolError::AnnotationFailed("page 2 rejected".to_string());
+
+        let error = roll_back(&client, &[], error).await;
+
+        assert_eq!(
+            error.to_string(),
+            "Annotation operation failed: page 2 rejected"
+        );
+    }
+}
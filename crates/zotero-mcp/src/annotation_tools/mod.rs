@@ -0,0 +1,347 @@
+//! Annotation management tool implementations for the Zotero MCP server.
+
+use serde::{Deserialize, Serialize};
+use zotero_client::{
+    pdf::{extract_page_text, get_page_count, page_label},
+    types::{AnnotationFilter, HighlightColor, UpdateAnnotationRequest, ZoteroAnnotation},
+    ZoteroClient, ZoteroClientError,
+};
+
+use super::tools::{find_pdf_path, HighlightColorParam, ToolError};
+
+mod create;
+
+pub use create::{
+    create_area_annotation, create_highlight, create_ink_annotation, create_note_annotation,
+    create_underline,
+};
+
+/// An annotation as reported by `zotero_list_annotations`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationInfo {
+    /// Annotation item key
+    pub key: Option<String>,
+    /// Annotation type (e.g., "highlight", "image", "note")
+    pub annotation_type: Option<String>,
+    /// Highlighted text, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// User comment, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Color hex code
+    pub color: Option<String>,
+    /// Semantic meaning of the color, if it belongs to the color scheme
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_meaning: Option<String>,
+    /// 1-based page number
+    pub page: Option<u32>,
+    /// Page label as displayed in Zotero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_label: Option<String>,
+}
+
+impl From<ZoteroAnnotation> for AnnotationInfo {
+    fn from(annotation: ZoteroAnnotation) -> Self {
+        Self {
+            color_meaning: annotation
+                .semantic_color()
+                .map(|c| c.description().to_string()),
+            page: annotation.page_index().map(|i| i + 1),
+            key: annotation.key,
+            annotation_type: annotation.annotation_type,
+            text: annotation.text.filter(|t| !t.is_empty()),
+            comment: annotation.comment.filter(|c| !c.is_empty()),
+            color: annotation.color,
+            page_label: annotation.page_label,
+        }
+    }
+}
+
+/// List annotations on a PDF attachment, optionally filtered.
+///
+/// `page` is 1-based; filters that are `None` are not applied.
+pub async fn list_annotations(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    annotation_type: Option<&str>,
+    color: Option<HighlightColorParam>,
+    page: Option<u32>,
+) -> Result<Vec<AnnotationInfo>, ToolError> {
+    let mut filter = AnnotationFilter::new();
+    if let Some(annotation_type) = annotation_type {
+        filter = filter.with_type(annotation_type);
+    }
+    if let Some(color) = color {
+        filter = filter.with_semantic_color(HighlightColor::from(color));
+    }
+    if let Some(page) = page {
+        filter = filter.on_page(page.saturating_sub(1));
+    }
+
+    let annotations = client
+        .list_annotations_matching(attachment_key, &filter)
+        .await
+        .map_err(|e| match e {
+            ZoteroClientError::NotFound { key } => ToolError::PdfNotFound(key),
+            other => ToolError::ClientError(other),
+        })?;
+
+    Ok(annotations.into_iter().map(AnnotationInfo::from).collect())
+}
+
+/// A new place for an annotation given to `zotero_update_annotation`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPosition {
+    /// Key of the PDF attachment the annotation belongs to
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+    /// Rectangles [x1, y1, x2, y2] in PDF coordinates, in reading order
+    pub rects: Vec<[f64; 4]>,
+}
+
+impl NewPosition {
+    /// Build a position from the optional tool parameters. `page` and `rects`
+    /// move the annotation only together, and need the attachment to read the
+    /// page label and text from.
+    pub fn new(
+        attachment_key: Option<String>,
+        page: Option<u32>,
+        rects: Option<Vec<[f64; 4]>>,
+    ) -> Result<Option<Self>, ToolError> {
+        match (attachment_key, page, rects) {
+            (_, None, None) => Ok(None),
+            (_, Some(0), _) => Err(ToolError::InvalidPosition(
+                "page numbers start at 1".to_string(),
+            )),
+            (_, _, Some(rects)) if rects.is_empty() => Err(ToolError::InvalidPosition(
+                "at least one rect is required".to_string(),
+            )),
+            (Some(attachment_key), Some(page), Some(rects)) => Ok(Some(Self {
+                attachment_key,
+                page,
+                rects,
+            })),
+            (None, _, _) => Err(ToolError::InvalidPosition(
+                "attachment_key is required to move an annotation".to_string(),
+            )),
+            _ => Err(ToolError::InvalidPosition(
+                "page and rects must be given together".to_string(),
+            )),
+        }
+    }
+}
+
+/// Update the comment, color, text and/or position of an existing annotation.
+///
+/// A new position gets the page label and a sort index from the PDF, as
+/// created annotations do, so the annotation keeps its place in reading order.
+/// It must be on a page of the annotation's own attachment.
+pub async fn update_annotation(
+    client: &ZoteroClient,
+    annotation_key: &str,
+    comment: Option<&str>,
+    color: Option<HighlightColorParam>,
+    text: Option<&str>,
+    position: Option<&NewPosition>,
+) -> Result<String, ToolError> {
+    let mut request = UpdateAnnotationRequest::new(annotation_key);
+    if let Some(comment) = comment {
+        request = request.with_comment(comment);
+    }
+    if let Some(color) = color {
+        request = request.with_semantic_color(HighlightColor::from(color));
+    }
+    if let Some(text) = text {
+        request = request.with_text(text);
+    }
+    let mut label = None;
+    if let Some(position) = position {
+        let path = find_pdf_path(client, &position.attachment_key).await?;
+        let siblings = client.list_annotations(&position.attachment_key).await?;
+        if !siblings
+            .iter()
+            .any(|annotation| annotation.key.as_deref() == Some(annotation_key))
+        {
+            return Err(ToolError::InvalidPosition(format!(
+                "annotation {} does not belong to attachment {}",
+                annotation_key, position.attachment_key
+            )));
+        }
+        let total_pages = get_page_count(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+        if position.page as usize > total_pages {
+            return Err(ToolError::PageOutOfRange(position.page, total_pages));
+        }
+        let page_index = position.page - 1;
+        let page_text = extract_page_text(&path, page_index as usize)
+            .map_err(|e| ToolError::PdfError(e.to_string()))?;
+        let page_label =
+            page_label(&path, page_index).map_err(|e| ToolError::PdfError(e.to_string()))?;
+        request = request
+            .with_position(
+                page_index,
+                position.rects.iter().map(|rect| rect.to_vec()).collect(),
+            )
+            .with_page_label(page_label.clone())
+            .with_sort_index(page_text.area_sort_index(position.rects[0]));
+        label = Some(page_label);
+    }
+
+    let response = client
+        .update_annotation(request)
+        .await
+        .map_err(|e| annotation_error(annotation_key, e))?;
+
+    if !response.success {
+        return Err(ToolError::AnnotationFailed(
+            response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string()),
+        ));
+    }
+
+    let mut changes = Vec::new();
+    if let Some(comment) = comment {
+        changes.push(format!(
+            "Comment: {}",
+            if comment.is_empty() {
+                "(cleared)"
+            } else {
+                comment
+            }
+        ));
+    }
+    if let Some(color) = color {
+        let color = HighlightColor::from(color);
+        changes.push(format!(
+            "Color: {} ({})",
+            color.to_hex(),
+            color.description()
+        ));
+    }
+    if let Some(text) = text {
+        changes.push(format!("Text: {}", text));
+    }
+    if let (Some(position), Some(label)) = (position, label) {
+        changes.push(format!(
+            "Position: page {} (label {}), {} rect(s)",
+            position.page,
+            label,
+            position.rects.len()
+        ));
+    }
+
+    Ok(format!(
+        "Updated annotation: {}\n{}",
+        annotation_key,
+        changes.join("\n")
+    ))
+}
+
+/// Permanently delete an annotation.
+pub async fn delete_annotation(
+    client: &ZoteroClient,
+    annotation_key: &str,
+) -> Result<String, ToolError> {
+    let response = client
+        .delete_annotation(annotation_key)
+        .await
+        .map_err(|e| annotation_error(annotation_key, e))?;
+
+    if response.success {
+        Ok(format!("Deleted annotation: {}", annotation_key))
+    } else {
+        Err(ToolError::AnnotationFailed(
+            response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string()),
+        ))
+    }
+}
+
+/// Map a client error for an annotation key, reporting unknown keys as such.
+fn annotation_error(annotation_key: &str, error: ZoteroClientError) -> ToolError {
+    match error {
+        ZoteroClientError::NotFound { .. } => {
+            ToolError::AnnotationNotFound(annotation_key.to_string())
+        }
+        other => ToolError::ClientError(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zotero_client::types::AnnotationPosition;
+
+    #[test]
+    fn test_annotation_info_from_annotation() {
+        let annotation = ZoteroAnnotation {
+            id: Some(1),
+            key: Some("ANN12345".to_string()),
+            parent_item_key: Some("PDF12345".to_string()),
+            annotation_type: Some("highlight".to_string()),
+            text: Some("Important text".to_string()),
+            comment: Some(String::new()),
+            color: Some("#ff6666".to_string()),
+            page_label: Some("iv".to_string()),
+            sort_index: None,
+            position: Some(AnnotationPosition {
+                page_index: 3,
+                ..Default::default()
+            }),
+        };
+
+        let info = AnnotationInfo::from(annotation);
+
+        assert_eq!(info.page, Some(4));
+        assert_eq!(info.page_label.as_deref(), Some("iv"));
+        assert_eq!(info.comment, None);
+        assert_eq!(
+            info.color_meaning.as_deref(),
+            Some(HighlightColor::Negative.description())
+        );
+    }
+
+    #[test]
+    fn test_annotation_error_maps_not_found() {
+        let error = annotation_error(
+            "ANN12345",
+            ZoteroClientError::NotFound {
+                key: "ANN12345".to_string(),
+            },
+        );
+        assert!(matches!(error, ToolError::AnnotationNotFound(key) if key == "ANN12345"));
+
+        let error = annotation_error("ANN12345", ZoteroClientError::InvalidInput("x".into()));
+        assert!(matches!(error, ToolError::ClientError(_)));
+    }
+
+    #[test]
+    fn test_new_position_needs_page_rects_and_attachment() {
+        let rects = Some(vec![[72.0, 600.0, 300.0, 700.0]]);
+        assert_eq!(NewPosition::new(None, None, None).unwrap(), None);
+        assert_eq!(
+            NewPosition::new(Some("PDF12345".into()), Some(3), rects.clone()).unwrap(),
+            Some(NewPosition {
+                attachment_key: "PDF12345".into(),
+                page: 3,
+                rects: rects.clone().unwrap(),
+            })
+        );
+
+        for (attachment_key, page, rects) in [
+            (Some("PDF12345".to_string()), Some(3), None),
+            (Some("PDF12345".to_string()), None, rects.clone()),
+            (Some("PDF12345".to_string()), Some(0), rects.clone()),
+            (Some("PDF12345".to_string()), Some(3), Some(vec![])),
+            (None, Some(3), rects.clone()),
+        ] {
+            assert!(matches!(
+                NewPosition::new(attachment_key, page, rects),
+                Err(ToolError::InvalidPosition(_))
+            ));
+        }
+    }
+}
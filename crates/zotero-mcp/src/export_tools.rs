@@ -0,0 +1,37 @@
+//! Annotation export tool implementation for the Zotero MCP server.
+
+use zotero_client::{
+    export::{annotations_to_markdown, today},
+    ZoteroClient,
+};
+
+use super::tools::ToolError;
+
+/// Export the annotations of every PDF attached to an item as a Markdown note.
+///
+/// The item is looked up by its BetterBibTeX citation key, which is also used
+/// for the note's page links. Annotations of each PDF are kept in reading
+/// order, PDFs in the order Zotero lists them.
+pub async fn export_annotations(client: &ZoteroClient, citekey: &str) -> Result<String, ToolError> {
+    let item = client
+        .find_by_citation_key(citekey, 1000)
+        .await?
+        .ok_or_else(|| ToolError::ItemNotFound(citekey.to_string()))?;
+
+    let pdfs = client.get_pdf_attachments(&item.key).await?;
+    if pdfs.is_empty() {
+        return Err(ToolError::PdfNotFound(item.key));
+    }
+
+    let mut annotations = Vec::new();
+    for pdf in &pdfs {
+        annotations.extend(client.list_annotations(&pdf.key).await?);
+    }
+
+    Ok(annotations_to_markdown(
+        &item,
+        citekey,
+        &annotations,
+        &today(),
+    ))
+}
@@ -0,0 +1,267 @@
+//! Full-text search tool implementation for the Zotero MCP server.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+use zotero_client::{
+    fulltext::{default_index_path, extract_pages, now, DocumentInfo, FulltextIndex, SearchHit},
+    util::file_modified,
+    ZoteroClient,
+};
+
+use super::tools::ToolError;
+
+/// Maximum number of library items synchronized into the index.
+const LIBRARY_LIMIT: u32 = 100_000;
+
+/// Number of newly indexed attachments after which a synchronization saves
+/// the index, so that an interrupted one keeps its work.
+const SAVE_INTERVAL: usize = 50;
+
+/// The full-text index of a server, loaded from disk on first use and
+/// again whenever the file changes, e.g. after `zotero-mcp index`.
+#[derive(Debug)]
+pub struct FulltextState {
+    path: PathBuf,
+    index: Mutex<Option<LoadedIndex>>,
+}
+
+/// An index and the modification time of the file it was loaded from or saved to.
+#[derive(Debug)]
+struct LoadedIndex {
+    index: FulltextIndex,
+    modified: Option<SystemTime>,
+}
+
+impl FulltextState {
+    /// Use the index stored at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            index: Mutex::new(None),
+        }
+    }
+}
+
+/// Modification time of the index file, or `None` if there is none.
+///
+/// Unlike [`file_modified`], this keeps sub-second precision, so that a
+/// write in the same second as the last load is still noticed.
+fn index_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Default for FulltextState {
+    fn default() -> Self {
+        Self::new(default_index_path())
+    }
+}
+
+/// Changes made to the index by a synchronization.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexUpdate {
+    /// Attachments indexed for the first time or re-indexed after a change
+    pub indexed: usize,
+    /// Attachments removed because they are no longer in the library
+    pub removed: usize,
+    /// Attachments whose file could not be read, and items whose attachments
+    /// could not be listed, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<String>,
+}
+
+/// Result of `zotero_fulltext_search`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FulltextSearchResult {
+    /// Pages matching the query, best first
+    pub hits: Vec<SearchHit>,
+    /// Number of indexed PDF attachments
+    pub indexed_documents: usize,
+    /// Number of indexed pages
+    pub indexed_pages: usize,
+    /// When the index was last synchronized with the library, in seconds since
+    /// the Unix epoch; absent if it never was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+    /// Changes made to the index before searching, if it was synchronized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update: Option<IndexUpdate>,
+}
+
+/// Search the text of every PDF in the library.
+///
+/// The index is only synchronized with the library, first, when `refresh`
+/// is set: a synchronization lists every item of the library and holds the
+/// index, so other searches wait for it. `zotero-mcp index` builds the index
+/// ahead of time.
+pub async fn fulltext_search(
+    client: &ZoteroClient,
+    state: &FulltextState,
+    query: &str,
+    limit: usize,
+    refresh: bool,
+) -> Result<FulltextSearchResult, ToolError> {
+    let mut guard = state.index.lock().await;
+    let modified = index_modified(&state.path);
+    let loaded = match guard.take() {
+        Some(loaded) if loaded.modified == modified => loaded,
+        _ => LoadedIndex {
+            index: FulltextIndex::load(&state.path)?,
+            modified,
+        },
+    };
+    let LoadedIndex { index, modified } = guard.insert(loaded);
+
+    let update = if refresh {
+        // The file holds this index now, or an earlier state of it if the
+        // sync failed part way, so it need not be reloaded
+        let update = update_index(client, index, &state.path).await;
+        *modified = index_modified(&state.path);
+        Some(update?)
+    } else {
+        None
+    };
+
+    Ok(FulltextSearchResult {
+        hits: index.search(query, limit),
+        indexed_documents: index.len(),
+        indexed_pages: index.page_count(),
+        updated_at: index.updated_at(),
+        update,
+    })
+}
+
+/// Bring the index up to date with the PDF attachments of the library.
+///
+/// Only attachments whose file modification time changed since they were
+/// indexed are extracted again. Attachments no longer in the library are
+/// removed, unless the library listing was cut off at its limit. Items whose
+/// attachments cannot be listed are reported in `failed` and skipped.
+///
+/// The index is saved to `index_path` every [`SAVE_INTERVAL`] indexed
+/// attachments and at the end.
+pub async fn update_index(
+    client: &ZoteroClient,
+    index: &mut FulltextIndex,
+    index_path: &Path,
+) -> Result<IndexUpdate, ToolError> {
+    let mut update = IndexUpdate::default();
+    let mut seen = HashSet::new();
+
+    let items = client.list_items(LIBRARY_LIMIT).await?;
+    let mut complete = items.len() < LIBRARY_LIMIT as usize;
+
+    for item in items {
+        let pdfs = match client.get_pdf_attachments(&item.key).await {
+            Ok(pdfs) => pdfs,
+            Err(e) => {
+                // Its attachments are unknown, so none can be removed
+                update.failed.push(format!("{}: {}", item.key, e));
+                complete = false;
+                continue;
+            }
+        };
+        for pdf in pdfs {
+            seen.insert(pdf.key.clone());
+            let Some(path) = pdf.path else {
+                continue;
+            };
+            let modified = match file_modified(&path) {
+                Ok(modified) => modified,
+                Err(e) => {
+                    update.failed.push(format!("{}: {}", pdf.key, e));
+                    continue;
+                }
+            };
+            if index.is_current(&pdf.key, modified) {
+                continue;
+            }
+
+            let pages = tokio::task::spawn_blocking(move || extract_pages(path))
+                .await
+                .map_err(|e| ToolError::PdfError(e.to_string()))?
+                .unwrap_or_else(|e| {
+                    // Index the attachment without text so it is only retried
+                    // once its file changes.
+                    update.failed.push(format!("{}: {}", pdf.key, e));
+                    Vec::new()
+                });
+
+            index.insert(
+                DocumentInfo {
+                    attachment_key: pdf.key,
+                    item_key: item.key.clone(),
+                    citekey: item.citation_key().map(str::to_string),
+                    title: item.title.clone(),
+                    modified,
+                },
+                pages,
+            );
+            update.indexed += 1;
+            if update.indexed % SAVE_INTERVAL == 0 {
+                index.save(index_path)?;
+            }
+        }
+    }
+
+    // Items past the limit are still in the library, so nothing can be
+    // removed unless every item and its attachments were listed.
+    if complete {
+        update.removed = index.retain(|key| seen.contains(key));
+    }
+    index.mark_updated(now());
+    index.save(index_path)?;
+    Ok(update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_of(attachment_key: &str, text: &str) -> FulltextIndex {
+        let mut index = FulltextIndex::new();
+        index.insert(
+            DocumentInfo {
+                attachment_key: attachment_key.to_string(),
+                item_key: "ITEM1234".to_string(),
+                citekey: None,
+                title: None,
+                modified: 1,
+            },
+            vec![text.to_string()],
+        );
+        index
+    }
+
+    #[tokio::test]
+    async fn test_search_reloads_index_written_by_another_process() {
+        let dir = std::env::temp_dir().join(format!("zotero-mcp-fulltext-{}", std::process::id()));
+        let path = dir.join("fulltext.json");
+        let client = ZoteroClient::new();
+        let state = FulltextState::new(&path);
+
+        index_of("PDF00001", "graph neural networks")
+            .save(&path)
+            .unwrap();
+        let result = fulltext_search(&client, &state, "graph", 10, false)
+            .await
+            .unwrap();
+        assert_eq!(result.hits[0].attachment_key, "PDF00001");
+
+        // As written by `zotero-mcp index` while the server runs
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        index_of("PDF00002", "graph transformers")
+            .save(&path)
+            .unwrap();
+        let result = fulltext_search(&client, &state, "graph", 10, false)
+            .await
+            .unwrap();
+        assert_eq!(result.indexed_documents, 1);
+        assert_eq!(result.hits[0].attachment_key, "PDF00002");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,15 +1,18 @@
 //! Image extraction tool implementations for the Zotero MCP server.
 
+use std::sync::Arc;
+
 use rmcp::{schemars, schemars::JsonSchema};
 use serde::{Deserialize, Serialize};
 use zotero_client::{
     image::{
-        detect_figures, render_page_to_file, render_region_to_file, FigureRegion, ImageFormat,
+        detect_figures, render_page_to_file, render_region_to_file, FigureIndex, FigureIndexCache,
+        FigureRegion, ImageFile, ImageFormat, IndexedFigure, RenderLimits,
     },
     ZoteroClient,
 };
 
-use super::tools::ToolError;
+use super::tools::{find_pdf_path, ToolError};
 
 /// Output format for rendered images.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default)]
@@ -38,6 +41,24 @@ pub struct ImageResult {
     pub file_path: String,
     /// MIME type (e.g., "image/png")
     pub mime_type: String,
+    /// Image width in pixels
+    pub width: u32,
+    /// Image height in pixels
+    pub height: u32,
+    /// Resolution the image was rendered at, lowered to fit the size limits
+    pub dpi: u32,
+}
+
+impl ImageResult {
+    fn new(file: ImageFile, format: ImageFormat) -> Self {
+        Self {
+            file_path: file.path,
+            mime_type: format.mime_type().to_string(),
+            width: file.width,
+            height: file.height,
+            dpi: file.dpi,
+        }
+    }
 }
 
 /// A detected figure on a PDF page.
@@ -55,6 +76,12 @@ pub struct FigureInfo {
     pub width: f64,
     /// Height of the figure region
     pub height: f64,
+    /// Caption label, e.g. "Figure 3" or "Table 2"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Full caption text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
 }
 
 impl From<FigureRegion> for FigureInfo {
@@ -66,36 +93,17 @@ impl From<FigureRegion> for FigureInfo {
             confidence: region.confidence,
             width: region.width(),
             height: region.height(),
+            label: region.label(),
+            caption: region.caption,
         }
     }
 }
 
-/// Find the PDF file path for an attachment key.
-async fn find_pdf_path(client: &ZoteroClient, attachment_key: &str) -> Result<String, ToolError> {
-    let items = client.list_items(500).await?;
-
-    for parent_item in &items {
-        let pdfs = client.get_pdf_attachments(&parent_item.key).await?;
-        for pdf in pdfs {
-            if pdf.key == attachment_key {
-                if let Some(path) = pdf.path {
-                    if std::path::Path::new(&path).exists() {
-                        return Ok(path);
-                    } else {
-                        return Err(ToolError::FileNotFound(path));
-                    }
-                }
-            }
-        }
-    }
-
-    Err(ToolError::PdfNotFound(attachment_key.to_string()))
-}
-
 /// Extract a PDF page as an image, saving it to a temporary file.
 ///
 /// Returns the file path instead of base64 data, allowing the MCP client
 /// to access the image directly without embedding massive data in responses.
+/// The DPI, or the JPEG quality, is lowered as needed to fit `limits`.
 pub async fn extract_page_image(
     client: &ZoteroClient,
     attachment_key: &str,
@@ -103,6 +111,7 @@ pub async fn extract_page_image(
     rect: Option<[f64; 4]>,
     dpi: Option<u32>,
     format: ImageFormatParam,
+    limits: RenderLimits,
 ) -> Result<ImageResult, ToolError> {
     let pdf_path = find_pdf_path(client, attachment_key).await?;
     let dpi = dpi.unwrap_or(150); // Can use higher DPI now since we're not base64 encoding
@@ -125,23 +134,21 @@ pub async fn extract_page_image(
         extension
     ));
 
-    let file_path = match rect {
-        Some(r) => render_region_to_file(&pdf_path, page_index, r, dpi, img_format, &temp_file)
-            .map_err(|e| ToolError::PdfError(e.to_string()))?,
-        None => render_page_to_file(&pdf_path, page_index, dpi, img_format, &temp_file)
-            .map_err(|e| ToolError::PdfError(e.to_string()))?,
-    };
+    let file = match rect {
+        Some(r) => render_region_to_file(
+            &pdf_path, page_index, r, dpi, img_format, limits, &temp_file,
+        ),
+        None => render_page_to_file(&pdf_path, page_index, dpi, img_format, limits, &temp_file),
+    }
+    .map_err(|e| ToolError::PdfError(e.to_string()))?;
 
-    Ok(ImageResult {
-        file_path,
-        mime_type: img_format.mime_type().to_string(),
-    })
+    Ok(ImageResult::new(file, img_format))
 }
 
 /// List detected figures on a PDF page.
 ///
-/// Uses heuristics to detect figure regions (images, charts, diagrams)
-/// on the specified page.
+/// Detects figure regions (images, charts, diagrams, tables) and their
+/// captions on the specified page.
 pub async fn list_figures(
     client: &ZoteroClient,
     attachment_key: &str,
@@ -156,37 +163,176 @@ pub async fn list_figures(
     Ok(figures.into_iter().map(FigureInfo::from).collect())
 }
 
+/// A figure reported by `zotero_list_all_figures`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentFigure {
+    /// 1-based page number
+    pub page: u32,
+    #[serde(flatten)]
+    pub figure: FigureInfo,
+}
+
+impl From<IndexedFigure> for DocumentFigure {
+    fn from(entry: IndexedFigure) -> Self {
+        Self {
+            page: entry.page + 1,
+            figure: entry.figure.into(),
+        }
+    }
+}
+
+/// Result of `zotero_list_all_figures`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FigureList {
+    /// Number of figures listed
+    pub total_figures: usize,
+    /// The figures, page by page and top to bottom on each page
+    pub figures: Vec<DocumentFigure>,
+}
+
+/// List the figures of every page of a PDF attachment.
+///
+/// The index is built once per file and kept in `cache` until the file changes.
+pub async fn list_all_figures(
+    client: &ZoteroClient,
+    cache: &FigureIndexCache,
+    attachment_key: &str,
+    captioned_only: bool,
+) -> Result<FigureList, ToolError> {
+    let path = find_pdf_path(client, attachment_key).await?;
+    let index = figure_index(cache, &path).await?;
+
+    let figures: Vec<DocumentFigure> = index
+        .figures
+        .iter()
+        .filter(|f| !captioned_only || f.figure.caption.is_some())
+        .cloned()
+        .map(DocumentFigure::from)
+        .collect();
+
+    Ok(FigureList {
+        total_figures: figures.len(),
+        figures,
+    })
+}
+
+/// The figure index of a PDF from `cache`.
+///
+/// Building an index runs figure detection over every page, so it happens on
+/// a blocking thread rather than holding up the other sessions of the server.
+async fn figure_index(
+    cache: &FigureIndexCache,
+    pdf_path: &str,
+) -> Result<Arc<FigureIndex>, ToolError> {
+    let cache = cache.clone();
+    let pdf_path = pdf_path.to_string();
+    tokio::task::spawn_blocking(move || cache.get(pdf_path))
+        .await
+        .map_err(|e| ToolError::PdfError(e.to_string()))?
+        .map_err(|e| ToolError::PdfError(e.to_string()))
+}
+
+/// How `zotero_get_figure` picks a figure.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FigureSelector {
+    /// The figure with an index from `zotero_list_figures` on a 1-based page
+    Index { page: u32, index: usize },
+    /// The figure or table with a caption label such as "Figure 3", on a
+    /// 1-based page or, without one, anywhere in the PDF (from the figure index)
+    Label { page: Option<u32>, label: String },
+}
+
+impl FigureSelector {
+    /// Pick a figure by label, optionally on a page, or by page and index.
+    pub fn new(
+        page: Option<u32>,
+        figure_index: Option<usize>,
+        label: Option<String>,
+    ) -> Result<Self, ToolError> {
+        match (label, page, figure_index) {
+            (Some(_), _, Some(_)) => Err(ToolError::InvalidParameter(
+                "pass either a figure label or figure_index, not both".to_string(),
+            )),
+            (Some(label), page, None) => Ok(Self::Label { page, label }),
+            (None, Some(page), Some(index)) => Ok(Self::Index { page, index }),
+            (None, None, Some(_)) => Err(ToolError::InvalidParameter(
+                "page is required with figure_index".to_string(),
+            )),
+            (None, _, None) => Err(ToolError::InvalidParameter(
+                "pass a figure label such as \"Figure 3\", or page and figure_index".to_string(),
+            )),
+        }
+    }
+}
+
+/// Result of `zotero_get_figure`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FigureImage {
+    #[serde(flatten)]
+    pub image: ImageResult,
+    /// 1-based page number of the figure
+    pub page: u32,
+    /// The figure that was extracted
+    pub figure: FigureInfo,
+}
+
+/// Find the figure a selector picks, with its 1-based page.
+async fn select_figure(
+    pdf_path: &str,
+    cache: &FigureIndexCache,
+    selector: &FigureSelector,
+) -> Result<(u32, FigureRegion), ToolError> {
+    match selector {
+        FigureSelector::Index { page, index } => {
+            let figures = detect_figures(pdf_path, page.saturating_sub(1) as usize)
+                .map_err(|e| ToolError::PdfError(e.to_string()))?;
+            let count = figures.len();
+            figures
+                .into_iter()
+                .find(|f| f.index == *index)
+                .map(|f| (*page, f))
+                .ok_or_else(|| {
+                    ToolError::FigureNotFound(format!(
+                        "index {} on page {}, which has {} detected figures",
+                        index, page, count
+                    ))
+                })
+        }
+        FigureSelector::Label {
+            page: Some(page),
+            label,
+        } => detect_figures(pdf_path, page.saturating_sub(1) as usize)
+            .map_err(|e| ToolError::PdfError(e.to_string()))?
+            .into_iter()
+            .find(|f| f.matches_label(label))
+            .map(|f| (*page, f))
+            .ok_or_else(|| ToolError::FigureNotFound(format!("{} on page {}", label, page))),
+        FigureSelector::Label { page: None, label } => figure_index(cache, pdf_path)
+            .await?
+            .find(label)
+            .map(|f| (f.page + 1, f.figure.clone()))
+            .ok_or_else(|| ToolError::FigureNotFound(label.clone())),
+    }
+}
+
 /// Extract a specific figure as an image, saving it to a temporary file.
 ///
-/// First detects figures on the page, then renders the specified figure
-/// by index and saves it to a file.
+/// Finds the figure by its caption label or by its index among the figures
+/// detected on a page, then renders its region within `limits` and saves it
+/// to a file.
 pub async fn get_figure(
     client: &ZoteroClient,
+    cache: &FigureIndexCache,
     attachment_key: &str,
-    page: u32,
-    figure_index: usize,
+    selector: FigureSelector,
     format: ImageFormatParam,
     include_context: bool,
-) -> Result<ImageResult, ToolError> {
+    limits: RenderLimits,
+) -> Result<FigureImage, ToolError> {
     let pdf_path = find_pdf_path(client, attachment_key).await?;
+    let (page, figure) = select_figure(&pdf_path, cache, &selector).await?;
     let page_index = page.saturating_sub(1) as usize;
 
-    // Detect figures first
-    let figures = detect_figures(&pdf_path, page_index)
-        .map_err(|e| ToolError::PdfError(e.to_string()))?;
-
-    // Find the requested figure
-    let figure = figures
-        .iter()
-        .find(|f| f.index == figure_index)
-        .ok_or_else(|| {
-            ToolError::PdfError(format!(
-                "Figure {} not found. Page has {} detected figures.",
-                figure_index,
-                figures.len()
-            ))
-        })?;
-
     // Add padding for context if requested
     let rect = if include_context {
         let padding = 10.0;
@@ -211,7 +357,7 @@ pub async fn get_figure(
         "zotero-figure-{}-p{}-f{}-{}.{}",
         attachment_key,
         page,
-        figure_index,
+        figure.index,
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -219,13 +365,16 @@ pub async fn get_figure(
         extension
     ));
 
-    // Render the figure region at 150 DPI (high quality for figures)
-    let file_path = render_region_to_file(&pdf_path, page_index, rect, 150, img_format, &temp_file)
-        .map_err(|e| ToolError::PdfError(e.to_string()))?;
+    // Render the figure region at 150 DPI (high quality for figures), or lower to fit the limits
+    let file = render_region_to_file(
+        &pdf_path, page_index, rect, 150, img_format, limits, &temp_file,
+    )
+    .map_err(|e| ToolError::PdfError(e.to_string()))?;
 
-    Ok(ImageResult {
-        file_path,
-        mime_type: img_format.mime_type().to_string(),
+    Ok(FigureImage {
+        image: ImageResult::new(file, img_format),
+        page,
+        figure: figure.into(),
     })
 }
 
@@ -246,4 +395,51 @@ mod tests {
     fn test_image_format_param_default() {
         assert!(matches!(ImageFormatParam::default(), ImageFormatParam::Png));
     }
+
+    #[test]
+    fn test_document_figure_has_one_based_page_and_label() {
+        let entry = IndexedFigure {
+            page: 4,
+            figure: FigureRegion {
+                index: 0,
+                rect: [72.0, 400.0, 540.0, 700.0],
+                figure_type: zotero_client::image::FigureType::Table,
+                confidence: 0.9,
+                caption: Some("Table 2: Ablations.".to_string()),
+                number: Some("2".to_string()),
+            },
+        };
+        let json = serde_json::to_value(DocumentFigure::from(entry)).unwrap();
+        assert_eq!(json["page"], 5);
+        assert_eq!(json["label"], "Table 2");
+        assert_eq!(json["figure_type"], "table");
+        assert_eq!(json["caption"], "Table 2: Ablations.");
+    }
+
+    #[test]
+    fn test_figure_selector() {
+        assert_eq!(
+            FigureSelector::new(Some(4), None, Some("Figure 3".to_string())).unwrap(),
+            FigureSelector::Label {
+                page: Some(4),
+                label: "Figure 3".to_string()
+            }
+        );
+        assert_eq!(
+            FigureSelector::new(Some(4), Some(1), None).unwrap(),
+            FigureSelector::Index { page: 4, index: 1 }
+        );
+        assert!(matches!(
+            FigureSelector::new(Some(4), Some(0), Some("Figure 3".to_string())),
+            Err(ToolError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            FigureSelector::new(None, Some(1), None),
+            Err(ToolError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            FigureSelector::new(Some(1), None, None),
+            Err(ToolError::InvalidParameter(_))
+        ));
+    }
 }
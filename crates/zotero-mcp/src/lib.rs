@@ -10,10 +10,30 @@
 //! - `zotero_get_pdf_outline` - Get PDF table of contents/bookmarks
 //! - `zotero_read_pdf_pages` - Extract text from PDF pages
 //! - `zotero_create_highlight` - Create a text highlight annotation
+//! - `zotero_create_underline` - Create a text underline annotation
 //! - `zotero_create_area_annotation` - Create an area/image annotation
+//! - `zotero_create_note_annotation` - Pin a sticky note next to a quoted passage
+//! - `zotero_create_ink_annotation` - Draw a freehand ink annotation
+//! - `zotero_list_annotations` - List existing annotations, filtered by type, color or page
+//! - `zotero_update_annotation` - Change the comment, color or text of an annotation
+//! - `zotero_delete_annotation` - Delete an annotation
+//! - `zotero_create_note` - Create a Zotero note from Markdown
+//! - `zotero_export_annotations` - Export annotations as a Markdown note for Obsidian
+//! - `zotero_fulltext_search` - Search the text of every PDF in the library
+//! - `zotero_search_pdf` - Find the pages, snippets and positions of a term in one PDF
+//! - `zotero_get_references` - Parse the reference list of a PDF into authors, title, year, venue and DOI
+//! - `zotero_resolve_citation` - Check whether a cited paper is in the library and get its citekey
 //! - `zotero_extract_page_image` - Render a PDF page or region as an image
-//! - `zotero_list_figures` - Detect figure regions on a PDF page
-//! - `zotero_get_figure` - Extract a specific figure as an image
+//! - `zotero_list_figures` - Detect figures and tables with their captions on a PDF page
+//! - `zotero_list_all_figures` - List the figures and tables of a whole PDF with their captions
+//! - `zotero_get_figure` - Extract a figure as an image, by caption label ("Figure 3") or index
+//! - `zotero_extract_table` - Extract a table as Markdown or CSV
+//!
+//! ## Prompts
+//!
+//! - `readassist-read` - Read and annotate an item with a reading strategy
+//! - `readassist-summarize` - Summarize an item from its annotations
+//! - `readassist-synthesize` - Synthesize several items from their annotations
 //!
 //! ## Example
 //!
@@ -31,13 +51,36 @@
 //! }
 //! ```
 
+mod annotation_tools;
+mod export_tools;
+mod fulltext_tools;
 mod image_tools;
+mod markdown;
+mod note_tools;
+mod prompts;
+mod reference_tools;
+mod resources;
+mod search_tools;
 mod server;
+mod table_tools;
 mod tools;
 
+pub use export_tools::export_annotations;
+pub use fulltext_tools::{update_index, IndexUpdate};
 pub use image_tools::ImageFormatParam;
 pub use server::ZoteroMcpServer;
-pub use tools::HighlightColorParam;
+pub use table_tools::TableFormatParam;
+pub use tools::{HighlightColorParam, ToolError};
 
 // Re-export rmcp types for convenience
-pub use rmcp::{transport::io::stdio, ServiceExt};
+pub use rmcp::{
+    model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
+    transport::{
+        common::server_side_http::{session_id, SessionId},
+        io::stdio,
+        streamable_http_server::{
+            session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+        },
+    },
+    ServiceExt,
+};
@@ -0,0 +1,470 @@
+//! Conversion of Markdown to Zotero note HTML.
+//!
+//! Zotero notes are HTML documents edited by Zotero's note editor, which
+//! understands a fixed set of elements: headings, paragraphs, lists,
+//! blockquotes, preformatted code, horizontal rules, links and the inline
+//! marks `strong`, `em`, `code` and `s`. This converter covers the Markdown
+//! that maps onto those elements; anything else is kept as literal text.
+
+/// Schema version of Zotero's note editor, declared on the wrapping `div`.
+const NOTE_SCHEMA_VERSION: u32 = 9;
+
+/// Convert Markdown to the HTML of a Zotero note.
+///
+/// Zotero takes the note's title from its first line, so a note that should
+/// be easy to find starts with a heading.
+pub fn markdown_to_note_html(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    format!(
+        "<div data-schema-version=\"{}\">{}</div>",
+        NOTE_SCHEMA_VERSION,
+        blocks(&lines)
+    )
+}
+
+/// Escape text for use in HTML content or a quoted attribute.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Number of leading spaces of a line, counting a tab as four.
+fn indent(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Remove up to `width` columns of indentation.
+fn dedent(line: &str, width: usize) -> &str {
+    let mut removed = 0;
+    for (i, c) in line.char_indices() {
+        if removed >= width || !c.is_whitespace() {
+            return &line[i..];
+        }
+        removed += if c == '\t' { 4 } else { 1 };
+    }
+    ""
+}
+
+/// A list item marker: whether the list is ordered, and the item text.
+fn list_marker(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        return Some((false, rest));
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let rest = &trimmed[digits..];
+    if digits > 0 && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        return Some((true, &rest[2..]));
+    }
+    None
+}
+
+/// A heading: its level and text.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+        Some((level, rest.trim().trim_end_matches('#').trim_end()))
+    } else {
+        None
+    }
+}
+
+/// Whether a line is a horizontal rule (`---`, `***` or `___`).
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&m| chars.iter().all(|&c| c == m))
+}
+
+/// Whether a line starts a block other than a paragraph.
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```")
+        || trimmed.starts_with('>')
+        || heading(trimmed).is_some()
+        || is_rule(trimmed)
+        || list_marker(line).is_some()
+}
+
+/// Convert a sequence of lines to block-level HTML.
+fn blocks(lines: &[&str]) -> String {
+    let mut html = String::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(fence) = trimmed.strip_prefix("```") {
+            let language = fence.trim();
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1; // closing fence
+            let class = if language.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape(language))
+            };
+            html.push_str(&format!(
+                "<pre><code{}>{}</code></pre>",
+                class,
+                escape(&code.join("\n"))
+            ));
+        } else if let Some((level, text)) = heading(trimmed) {
+            html.push_str(&format!("<h{0}>{1}</h{0}>", level, inline(text)));
+            i += 1;
+        } else if is_rule(trimmed) {
+            html.push_str("<hr>");
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let rest = &lines[i].trim_start()[1..];
+                quoted.push(rest.strip_prefix(' ').unwrap_or(rest));
+                i += 1;
+            }
+            html.push_str(&format!("<blockquote>{}</blockquote>", blocks(&quoted)));
+        } else if list_marker(line).is_some() {
+            i = list(lines, i, &mut html);
+        } else {
+            let mut paragraph = vec![trimmed.trim_end()];
+            i += 1;
+            while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines[i]) {
+                paragraph.push(lines[i].trim());
+                i += 1;
+            }
+            html.push_str(&format!("<p>{}</p>", inline(&paragraph.join(" "))));
+        }
+    }
+
+    html
+}
+
+/// Convert the list starting at `start`, returning the index after it.
+///
+/// Items are the lines at the list's indentation; more deeply indented lines
+/// (nested lists or continuation text) belong to the item above them.
+fn list(lines: &[&str], start: usize, html: &mut String) -> usize {
+    let base = indent(lines[start]);
+    let ordered = list_marker(lines[start]).is_some_and(|(ordered, _)| ordered);
+    let tag = if ordered { "ol" } else { "ul" };
+
+    html.push_str(&format!("<{}>", tag));
+    let mut i = start;
+
+    while i < lines.len() {
+        let Some((item_ordered, text)) = list_marker(lines[i]) else {
+            break;
+        };
+        if indent(lines[i]) != base || item_ordered != ordered {
+            break;
+        }
+        i += 1;
+
+        let mut text = vec![text.trim()];
+        let mut nested = Vec::new();
+        while i < lines.len() {
+            let line = lines[i];
+            if line.trim().is_empty() {
+                // A blank line ends the item unless indented content follows
+                if lines.get(i + 1).is_some_and(|next| indent(next) > base) {
+                    nested.push("");
+                    i += 1;
+                    continue;
+                }
+                break;
+            }
+            if indent(line) <= base {
+                if list_marker(line).is_some() || starts_block(line) {
+                    break;
+                }
+                // Lazy continuation of the item text
+                text.push(line.trim());
+            } else if nested.is_empty() && list_marker(line).is_none() {
+                text.push(line.trim());
+            } else {
+                nested.push(dedent(line, base + 2));
+            }
+            i += 1;
+        }
+
+        html.push_str(&format!(
+            "<li>{}{}</li>",
+            inline(&text.join(" ")),
+            blocks(&nested)
+        ));
+
+        while i < lines.len() && lines[i].trim().is_empty() {
+            if lines
+                .get(i + 1)
+                .is_some_and(|next| list_marker(next).is_some() && indent(next) == base)
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    html.push_str(&format!("</{}>", tag));
+    i
+}
+
+/// Find the closing delimiter of an inline span, which must directly follow
+/// non-whitespace text.
+fn closing(chars: &[char], from: usize, delimiter: &[char]) -> Option<usize> {
+    (from + 1..=chars.len().checked_sub(delimiter.len())?)
+        .find(|&i| chars[i..i + delimiter.len()] == *delimiter && !chars[i - 1].is_whitespace())
+}
+
+/// Convert inline Markdown (emphasis, code, links) to HTML.
+fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut html = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let prev_is_word = i > 0 && chars[i - 1].is_alphanumeric();
+
+        // Backslash escapes
+        if c == '\\' && next.is_some_and(|n| n.is_ascii_punctuation()) {
+            html.push_str(&escape(&next.unwrap_or_default().to_string()));
+            i += 2;
+            continue;
+        }
+
+        // Inline code
+        if c == '`' {
+            if let Some(end) = chars[i + 1..].iter().position(|&c| c == '`') {
+                let code: String = chars[i + 1..i + 1 + end].iter().collect();
+                html.push_str(&format!("<code>{}</code>", escape(&code)));
+                i += end + 2;
+                continue;
+            }
+        }
+
+        // Strong, strikethrough and emphasis
+        let spans: [(&[char], &str); 5] = [
+            (&['*', '*'], "strong"),
+            (&['_', '_'], "strong"),
+            (&['~', '~'], "s"),
+            (&['*'], "em"),
+            (&['_'], "em"),
+        ];
+        let span = spans.iter().find_map(|&(delimiter, tag)| {
+            let len = delimiter.len();
+            let opens = chars[i..].starts_with(delimiter)
+                && chars.get(i + len).is_some_and(|c| !c.is_whitespace())
+                && !(delimiter[0] == '_' && prev_is_word);
+            if !opens {
+                return None;
+            }
+            let end = closing(&chars, i + len, delimiter)?;
+            let closes_word =
+                delimiter[0] == '_' && chars.get(end + len).is_some_and(|c| c.is_alphanumeric());
+            (!closes_word).then_some((len, end, tag))
+        });
+        if let Some((len, end, tag)) = span {
+            let content: String = chars[i + len..end].iter().collect();
+            html.push_str(&format!("<{0}>{1}</{0}>", tag, inline(&content)));
+            i = end + len;
+            continue;
+        }
+
+        // Links: [text](url)
+        if c == '[' {
+            if let Some(link) = link(&chars, i) {
+                let (label, url, end) = link;
+                html.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(&url),
+                    inline(&label)
+                ));
+                i = end;
+                continue;
+            }
+        }
+
+        html.push_str(&escape(&c.to_string()));
+        i += 1;
+    }
+
+    html
+}
+
+/// Parse a link starting at `[`: its label, URL and the index after it.
+fn link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let close = (start..chars.len()).find(|&i| {
+        match chars[i] {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?;
+
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let url_end = close + 2 + chars[close + 2..].iter().position(|&c| c == ')')?;
+
+    let label: String = chars[start + 1..close].iter().collect();
+    let url: String = chars[close + 2..url_end].iter().collect();
+    let url = url.trim();
+    if label.is_empty() || url.is_empty() || url.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((label, url.to_string(), url_end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert and strip the wrapping note `div`.
+    fn convert(markdown: &str) -> String {
+        let html = markdown_to_note_html(markdown);
+        html.strip_prefix("<div data-schema-version=\"9\">")
+            .and_then(|h| h.strip_suffix("</div>"))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_wraps_in_note_div() {
+        assert_eq!(
+            markdown_to_note_html("Hello"),
+            "<div data-schema-version=\"9\"><p>Hello</p></div>"
+        );
+    }
+
+    #[test]
+    fn test_headings_and_paragraphs() {
+        assert_eq!(
+            convert("# Summary\n\nFirst line\nsame paragraph.\n\n## Methods ##\nText"),
+            "<h1>Summary</h1><p>First line same paragraph.</p><h2>Methods</h2><p>Text</p>"
+        );
+        assert_eq!(convert("#hashtag"), "<p>#hashtag</p>");
+    }
+
+    #[test]
+    fn test_inline_marks() {
+        assert_eq!(
+            convert("**bold**, *italic*, _also_, __strong__, ~~gone~~ and `a < b`"),
+            "<p><strong>bold</strong>, <em>italic</em>, <em>also</em>, \
+             <strong>strong</strong>, <s>gone</s> and <code>a &lt; b</code></p>"
+        );
+    }
+
+    #[test]
+    fn test_nested_inline_marks() {
+        assert_eq!(
+            convert("**bold with *italic* inside**"),
+            "<p><strong>bold with <em>italic</em> inside</strong></p>"
+        );
+    }
+
+    #[test]
+    fn test_underscores_inside_words_are_literal() {
+        assert_eq!(convert("snake_case_name"), "<p>snake_case_name</p>");
+        assert_eq!(convert("2 * 3 * 4"), "<p>2 * 3 * 4</p>");
+    }
+
+    #[test]
+    fn test_escapes_html() {
+        assert_eq!(
+            convert("<script> & \"quotes\""),
+            "<p>&lt;script&gt; &amp; &quot;quotes&quot;</p>"
+        );
+        assert_eq!(convert(r"\*not italic\*"), "<p>*not italic*</p>");
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(
+            convert("See [the *paper*](https://doi.org/10.1/x?a=1&b=2)."),
+            "<p>See <a href=\"https://doi.org/10.1/x?a=1&amp;b=2\">the <em>paper</em></a>.</p>"
+        );
+        // Wikilinks are kept as text
+        assert_eq!(
+            convert("[[smith2023#p. 4|p. 4]]"),
+            "<p>[[smith2023#p. 4|p. 4]]</p>"
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            convert("- one\n- two\n\n1. first\n2. second"),
+            "<ul><li>one</li><li>two</li></ul><ol><li>first</li><li>second</li></ol>"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        assert_eq!(
+            convert("- parent\n  continued\n  - child\n  - child 2\n- sibling"),
+            "<ul><li>parent continued<ul><li>child</li><li>child 2</li></ul></li>\
+             <li>sibling</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_loose_list_stays_one_list() {
+        assert_eq!(
+            convert("- one\n\n- two\n\nAfter"),
+            "<ul><li>one</li><li>two</li></ul><p>After</p>"
+        );
+    }
+
+    #[test]
+    fn test_blockquote_and_rule() {
+        assert_eq!(
+            convert("> quoted **text**\n> more\n\n---\n\nEnd"),
+            "<blockquote><p>quoted <strong>text</strong> more</p></blockquote><hr><p>End</p>"
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        assert_eq!(
+            convert("```python\nif a < b:\n    print(\"hi\")\n```\nAfter"),
+            "<pre><code class=\"language-python\">if a &lt; b:\n    print(&quot;hi&quot;)</code></pre>\
+             <p>After</p>"
+        );
+    }
+
+    #[test]
+    fn test_unclosed_markers_are_literal() {
+        assert_eq!(convert("a **b and `c"), "<p>a **b and `c</p>");
+    }
+}
@@ -0,0 +1,41 @@
+//! Note tool implementations for the Zotero MCP server.
+
+use zotero_client::ZoteroClient;
+
+use super::markdown::markdown_to_note_html;
+use super::tools::ToolError;
+
+/// Create a Zotero note from Markdown.
+///
+/// With `parent_key` the note is attached to that item; otherwise it is a
+/// standalone note.
+pub async fn create_note(
+    client: &ZoteroClient,
+    parent_key: Option<&str>,
+    markdown: &str,
+) -> Result<String, ToolError> {
+    let html = markdown_to_note_html(markdown);
+    let response = client.create_note(parent_key, &html).await?;
+
+    if !response.success {
+        return Err(ToolError::NoteFailed(
+            response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string()),
+        ));
+    }
+
+    let note = response.note.unwrap_or_default();
+
+    Ok(format!(
+        "Created note: {}\n\
+         Parent: {}\n\
+         Title: {}",
+        note.key.as_deref().unwrap_or("(unknown)"),
+        note.parent_item_key
+            .as_deref()
+            .or(parent_key)
+            .unwrap_or("(standalone)"),
+        note.title.as_deref().unwrap_or("(untitled)")
+    ))
+}
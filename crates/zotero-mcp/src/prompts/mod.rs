@@ -0,0 +1,392 @@
+//! Prompt construction for the critical-reading workflows.
+//!
+//! The prompts mirror the `/readassist-*` slash commands, so every MCP client
+//! gets the workflows. Instead of leaving the first steps to the model, each
+//! prompt embeds the context those steps would fetch: item metadata, the PDF
+//! outline and page text for reading, and exported annotations for summaries
+//! and syntheses.
+
+use zotero_client::{
+    pdf::{extract_text, get_page_count, get_pdf_outline},
+    types::{OutlineItem, PdfOutline, ZoteroItem},
+    ZoteroClient,
+};
+
+use super::tools::{find_pdf_path, parse_page_range, ToolError};
+
+mod strategies;
+
+pub use strategies::ReadingStrategy;
+
+/// Maximum number of characters of page text embedded in a reading prompt.
+const MAX_TEXT_CHARS: usize = 60_000;
+
+/// Number of leading pages embedded when skimming (the last page is added).
+const SKIM_PAGES: usize = 2;
+
+/// The color scheme, with the comment prefixes used across strategies.
+const COLOR_SCHEME: &str = "\
+## Color Scheme
+
+| Color | Use for | Comment prefixes |
+|-------|---------|------------------|
+| `section1` (blue) | Section headings (H2 in Obsidian) | *(text becomes the heading)* |
+| `section2` (purple) | Subsection headings (H3) | *(text becomes the heading)* |
+| `section3` (magenta) | Sub-subsection headings (H4) | *(text becomes the heading)* |
+| `positive` (green) | Claims, evidence, findings, answers | `CLAIM:`, `EVIDENCE:`, `FINDING:`, `A:` |
+| `negative` (red) | Weaknesses, limitations, concerns | `WEAKNESS:`, `LIMITATION:`, `CONCERN:` |
+| `question` (yellow) | Questions, gaps, unclear points | `Q:`, `GAP:`, `UNCLEAR:` |
+| `detail` (grey) | Definitions, methods, context | `TERM:`, `METHOD:`, `ASSUMPTION:` |
+| `code` (orange) | Code, statistics, formulas, data | `CODE:`, `STAT:`, `DATA:` |
+
+Start a comment with `IDEA:` (before any other prefix) to mark an atomic idea worth a \
+permanent note.";
+
+/// A document prepared for a reading prompt.
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// Citation key the document was looked up by.
+    pub citekey: String,
+    /// The Zotero item.
+    pub item: ZoteroItem,
+    /// Key of the PDF attachment that was read, if the item has one.
+    pub attachment_key: Option<String>,
+    /// Outline of the PDF, if it has one.
+    pub outline: Option<PdfOutline>,
+    /// Total number of pages of the PDF.
+    pub total_pages: usize,
+    /// Embedded page text, each page preceded by a `--- Page N ---` marker.
+    pub text: String,
+    /// 1-based number of the last page included in full, if the text was cut.
+    pub truncated_after: Option<usize>,
+}
+
+/// Look up an item and gather the context of a reading prompt.
+///
+/// `pages` selects the pages to embed; by default the document is embedded
+/// from the start (or, when skimming, its first pages and last page) until
+/// the text budget is used up.
+pub async fn load_document(
+    client: &ZoteroClient,
+    citekey: &str,
+    pages: Option<&str>,
+    strategy: ReadingStrategy,
+) -> Result<Document, ToolError> {
+    let item = client
+        .find_by_citation_key(citekey, 1000)
+        .await?
+        .ok_or_else(|| ToolError::ItemNotFound(citekey.to_string()))?;
+
+    let mut document = Document {
+        citekey: citekey.to_string(),
+        item,
+        attachment_key: None,
+        outline: None,
+        total_pages: 0,
+        text: String::new(),
+        truncated_after: None,
+    };
+
+    let pdfs = client.get_pdf_attachments(&document.item.key).await?;
+    let Some(pdf) = pdfs.into_iter().next() else {
+        return Ok(document);
+    };
+    let path = find_pdf_path(client, &pdf.key).await?;
+    document.attachment_key = Some(pdf.key);
+
+    let pdf_error = |e: zotero_client::ZoteroClientError| ToolError::PdfError(e.to_string());
+    document.total_pages = get_page_count(&path).map_err(pdf_error)?;
+    document.outline = get_pdf_outline(&path)
+        .ok()
+        .filter(|outline| outline.has_outline);
+
+    let page_indices = match pages {
+        Some(pages) => parse_page_range(pages, document.total_pages)?,
+        None if strategy.is_partial() => skim_pages(document.total_pages),
+        None => (0..document.total_pages).collect(),
+    };
+
+    let mut last_page = None;
+    for page in page_indices {
+        let text = extract_text(&path, page).map_err(pdf_error)?;
+        if last_page.is_some() && document.text.len() + text.len() > MAX_TEXT_CHARS {
+            document.truncated_after = last_page;
+            break;
+        }
+        document
+            .text
+            .push_str(&format!("--- Page {} ---\n\n{}\n\n", page + 1, text.trim()));
+        last_page = Some(page + 1);
+    }
+
+    Ok(document)
+}
+
+/// Zero-based indices of the pages embedded when skimming.
+fn skim_pages(total_pages: usize) -> Vec<usize> {
+    let mut pages: Vec<usize> = (0..total_pages.min(SKIM_PAGES)).collect();
+    if total_pages > SKIM_PAGES {
+        pages.push(total_pages - 1);
+    }
+    pages
+}
+
+/// The user message of the `readassist-read` prompt.
+pub fn read_prompt(
+    document: &Document,
+    strategy: ReadingStrategy,
+    purpose: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "Start an AI-assisted reading session for [@{}] using the **{}** strategy.\n\n",
+        document.citekey,
+        strategy.name()
+    );
+    if let Some(purpose) = purpose.filter(|p| !p.trim().is_empty()) {
+        prompt.push_str(&format!("Reading purpose: {}\n\n", purpose.trim()));
+    }
+
+    prompt.push_str(&item_header(document));
+
+    if let Some(outline) = &document.outline {
+        prompt.push_str("\n## Outline\n\n");
+        prompt.push_str(&outline_list(&outline.items, 0));
+    }
+
+    if document.attachment_key.is_none() {
+        prompt.push_str(
+            "\nThis item has no PDF attachment. Tell the user, and work only from the metadata.\n",
+        );
+    } else if !document.text.is_empty() {
+        prompt.push_str("\n## Text\n\n");
+        prompt.push_str(&document.text);
+        if let Some(page) = document.truncated_after {
+            prompt.push_str(&format!(
+                "*Text embedded up to page {}. Read further pages with zotero_read_pdf_pages.*\n",
+                page
+            ));
+        }
+    }
+
+    prompt.push('\n');
+    prompt.push_str(strategy.instructions());
+    prompt.push_str("\n\n");
+    prompt.push_str(COLOR_SCHEME);
+    prompt.push_str(
+        "\n\nQuote highlighted text exactly as it appears in the PDF, and use 1-based page \
+         numbers with the annotation tools.\n",
+    );
+    prompt
+}
+
+/// The user message of the `readassist-summarize` prompt.
+///
+/// `annotations` is the item's annotation export, as produced by
+/// `zotero_export_annotations`.
+pub fn summarize_prompt(citekey: &str, annotations: &str) -> String {
+    format!(
+        "Write a summary note for [@{citekey}] from its annotations below.\n\n\
+         ## Instructions\n\n\
+         1. First extract permanent notes: for every annotation whose comment starts with \
+         `IDEA:`, write one atomic note in your own words, with frontmatter `type: permanent`, \
+         `source: \"[@{citekey} p. X]\"` and `created`, for the `Permanent/` folder of the vault.\n\
+         2. Then write the summary with frontmatter `type: summary`, `source: \"[@{citekey}]\"` \
+         and `created: <today>`.\n\
+         3. Use the outline (section colors) for structure, and group content by semantic \
+         color: positive points, negative points, open questions, details, code.\n\
+         4. Cite pages in pandoc format, `[@{citekey} p. X]` or `[@{citekey} pp. X-Y]`.\n\
+         5. Add a \"Permanent Notes\" section linking each note as `[[note-filename]]`; omit it \
+         if there are no `IDEA:` annotations.\n\
+         6. Offer to save the summary in Zotero with zotero_create_note (parent_key = the item \
+         key).\n\n\
+         ## Annotations\n\n{annotations}"
+    )
+}
+
+/// The user message of the `readassist-synthesize` prompt.
+///
+/// `sources` pairs each citation key with its annotation export.
+pub fn synthesize_prompt(sources: &[(String, String)], theme: Option<&str>) -> String {
+    let citekeys: Vec<String> = sources
+        .iter()
+        .map(|(key, _)| format!("[@{}]", key))
+        .collect();
+    let mut prompt = format!("Write a synthesis note across {}.\n\n", citekeys.join(", "));
+    if let Some(theme) = theme.filter(|t| !t.trim().is_empty()) {
+        prompt.push_str(&format!(
+            "Focus on the theme \"{}\": use the annotations related to it and leave out the rest.\n\n",
+            theme.trim()
+        ));
+    }
+
+    prompt.push_str(
+        "## Instructions\n\n\
+         1. First extract permanent notes from the `IDEA:` annotations of every source, one \
+         atomic note per idea with frontmatter `type: permanent` and `source: \"[@citekey p. X]\"`.\n\
+         2. Write the synthesis with frontmatter `type: synthesis`, `sources: [...]` listing \
+         every `[@citekey]`, and `created: <today>`.\n\
+         3. Organize by themes across sources, not by source: where the sources agree (positive), \
+         where they disagree or are criticized (negative), and open questions.\n\
+         4. Attribute every finding inline in pandoc format, `[@citekey p. X]`.\n\
+         5. Add a \"Permanent Notes\" section grouped by source; omit it if no source has `IDEA:` \
+         annotations.\n",
+    );
+
+    for (citekey, annotations) in sources {
+        prompt.push_str(&format!("\n## Source: {}\n\n{}", citekey, annotations));
+    }
+    prompt
+}
+
+/// Item metadata and the attachment to use with the tools.
+fn item_header(document: &Document) -> String {
+    let item = &document.item;
+    let authors: Vec<String> = item
+        .creators
+        .iter()
+        .filter_map(|c| match (&c.first_name, &c.last_name) {
+            (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
+            (_, Some(last)) => Some(last.clone()),
+            _ => c.name.clone(),
+        })
+        .collect();
+
+    let mut header = format!(
+        "## Item\n\n\
+         - Title: {}\n\
+         - Authors: {}\n\
+         - Date: {}\n\
+         - Item key: {}\n",
+        item.title.as_deref().unwrap_or("(no title)"),
+        if authors.is_empty() {
+            "(unknown)".to_string()
+        } else {
+            authors.join(", ")
+        },
+        item.date.as_deref().unwrap_or("(no date)"),
+        item.key
+    );
+    if let Some(key) = &document.attachment_key {
+        header.push_str(&format!(
+            "- PDF attachment key: {} ({} pages)\n",
+            key, document.total_pages
+        ));
+    }
+    if let Some(abstract_note) = item.abstract_note.as_deref().filter(|a| !a.is_empty()) {
+        header.push_str(&format!("\n### Abstract\n\n{}\n", abstract_note.trim()));
+    }
+    header
+}
+
+/// Outline items as a nested list with 1-based page numbers.
+fn outline_list(items: &[OutlineItem], depth: usize) -> String {
+    let mut list = String::new();
+    for item in items {
+        list.push_str(&"  ".repeat(depth));
+        list.push_str(&format!("- {}", item.title));
+        if let Some(page) = item.page {
+            list.push_str(&format!(" (p. {})", page + 1));
+        }
+        list.push('\n');
+        list.push_str(&outline_list(&item.children, depth + 1));
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Document {
+        let item: ZoteroItem = serde_json::from_str(
+            r#"{"key": "ITEM0001", "itemType": "journalArticle", "title": "A Study",
+                "creators": [{"firstName": "Jane", "lastName": "Doe"}],
+                "abstract": "We study things."}"#,
+        )
+        .unwrap();
+
+        Document {
+            citekey: "doe2024".to_string(),
+            item,
+            attachment_key: Some("PDF00001".to_string()),
+            outline: Some(PdfOutline {
+                has_outline: true,
+                total_pages: 12,
+                items: vec![OutlineItem {
+                    title: "Methods".to_string(),
+                    page: Some(2),
+                    top: None,
+                    children: vec![OutlineItem {
+                        title: "Data".to_string(),
+                        page: Some(3),
+                        top: None,
+                        children: Vec::new(),
+                    }],
+                }],
+                inferred: false,
+            }),
+            total_pages: 12,
+            text: "--- Page 1 ---\n\nIntroduction text\n\n".to_string(),
+            truncated_after: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_read_prompt_embeds_context() {
+        let prompt = read_prompt(&document(), ReadingStrategy::Sq3r, Some("learn the method"));
+
+        assert!(prompt.contains("[@doe2024] using the **sq3r** strategy"));
+        assert!(prompt.contains("Reading purpose: learn the method"));
+        assert!(prompt.contains("- Authors: Jane Doe"));
+        assert!(prompt.contains("- PDF attachment key: PDF00001 (12 pages)"));
+        assert!(prompt.contains("We study things."));
+        assert!(prompt.contains("- Methods (p. 3)\n  - Data (p. 4)\n"));
+        assert!(prompt.contains("Introduction text"));
+        assert!(prompt.contains("Text embedded up to page 1"));
+        assert!(prompt.contains("## Strategy: SQ3R"));
+        assert!(prompt.contains("## Color Scheme"));
+    }
+
+    #[test]
+    fn test_read_prompt_without_pdf() {
+        let mut document = document();
+        document.attachment_key = None;
+        document.outline = None;
+        document.text.clear();
+        let prompt = read_prompt(&document, ReadingStrategy::Critical, None);
+
+        assert!(prompt.contains("no PDF attachment"));
+        assert!(!prompt.contains("## Outline"));
+        assert!(!prompt.contains("Reading purpose"));
+    }
+
+    #[test]
+    fn test_skim_pages() {
+        assert_eq!(skim_pages(1), vec![0]);
+        assert_eq!(skim_pages(2), vec![0, 1]);
+        assert_eq!(skim_pages(10), vec![0, 1, 9]);
+    }
+
+    #[test]
+    fn test_summarize_prompt() {
+        let prompt = summarize_prompt("doe2024", "## Outline\n\n- Methods\n");
+
+        assert!(prompt.contains("`source: \"[@doe2024]\"`"));
+        assert!(prompt.contains("`[@doe2024 pp. X-Y]`"));
+        assert!(prompt.ends_with("## Annotations\n\n## Outline\n\n- Methods\n"));
+    }
+
+    #[test]
+    fn test_synthesize_prompt() {
+        let sources = vec![
+            ("doe2024".to_string(), "Doe's annotations\n".to_string()),
+            ("roe2023".to_string(), "Roe's annotations\n".to_string()),
+        ];
+        let prompt = synthesize_prompt(&sources, Some("transfer learning"));
+
+        assert!(prompt.starts_with("Write a synthesis note across [@doe2024], [@roe2023]."));
+        assert!(prompt.contains("Focus on the theme \"transfer learning\""));
+        assert!(prompt.contains("## Source: doe2024\n\nDoe's annotations\n"));
+        assert!(prompt.contains("## Source: roe2023\n\nRoe's annotations\n"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Reading strategies offered by the `readassist-read` prompt.
+
+use rmcp::{schemars, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+
+/// A reading methodology.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadingStrategy {
+    /// General critical reading and analysis (default)
+    #[default]
+    Critical,
+    /// Survey, Question, Read, Recite, Review - for textbook learning
+    Sq3r,
+    /// Evidence extraction for a literature review
+    Review,
+    /// Deep analysis of arguments: thesis, premises, conclusions
+    Analyze,
+    /// Quick relevance assessment
+    Skim,
+}
+
+impl ReadingStrategy {
+    /// Name of the strategy as used in prompt arguments.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Sq3r => "sq3r",
+            Self::Review => "review",
+            Self::Analyze => "analyze",
+            Self::Skim => "skim",
+        }
+    }
+
+    /// Whether the strategy only needs the beginning and end of a document.
+    pub fn is_partial(self) -> bool {
+        self == Self::Skim
+    }
+
+    /// Step-by-step instructions, including how the strategy uses colors.
+    pub fn instructions(self) -> &'static str {
+        match self {
+            Self::Critical => CRITICAL,
+            Self::Sq3r => SQ3R,
+            Self::Review => REVIEW,
+            Self::Analyze => ANALYZE,
+            Self::Skim => SKIM,
+        }
+    }
+}
+
+const CRITICAL: &str = "\
+## Strategy: Critical Reading
+
+1. Use the outline to plan the reading; read section by section with zotero_read_pdf_pages \
+(by `section` when the outline has it, otherwise by `pages`).
+2. For pages with figures, call zotero_list_figures and zotero_get_figure, analyze the images \
+and record insights with zotero_create_area_annotation.
+3. Identify key claims and arguments, evidence, agreement and disagreement, technical terms \
+and definitions. Cross-reference text and figures.
+4. Annotate as you go with zotero_create_highlight, quoting the PDF text exactly:
+   - section1/section2/section3 for section headings (they become H2/H3/H4 in Obsidian)
+   - positive for claims and evidence (`CLAIM:`, `EVIDENCE:`), negative for weaknesses \
+(`WEAKNESS:`, `CONCERN:`), question for open questions (`Q:`, `GAP:`), detail for \
+definitions and methods (`TERM:`, `METHOD:`), code for code, statistics and formulas (`STAT:`, `CODE:`)
+5. Finish with a short summary of the main argument, its strengths and weaknesses.";
+
+const SQ3R: &str = "\
+## Strategy: SQ3R
+
+1. **Survey**: go through headings, figures and structure. Highlight headings with section1 \
+(chapters), section2 (subsections) and section3 (key topics).
+2. **Question**: turn headings into questions. Add them as question-colored annotations \
+with a `Q:` comment (zotero_create_note_annotation or a highlight on the heading).
+3. **Read**: read each section looking for the answers. Highlight answers in positive \
+(`A:` comment), confusing passages in negative (`UNCLEAR:`), definitions in detail (`TERM:`).
+4. **Recite**: after each section, summarize it in your own words in the conversation.
+5. **Review**: write a review of all sections, answering the questions, and save it with \
+zotero_create_note attached to the item.";
+
+const REVIEW: &str = "\
+## Strategy: Literature Review
+
+1. Extract the research question, claims, methods and findings.
+2. Highlight supported claims and findings in positive (`CLAIM:`, `FINDING:`), limitations \
+and threats to validity in negative (`LIMITATION:`), methodology in detail (`METHOD:`), \
+statistics and data in code (`STAT:`, `DATA:`), gaps and future work in question (`GAP:`).
+3. Assess quality: sample size, design, controls, potential biases, conflicts of interest. \
+Record the assessment in annotation comments.
+4. Categorize findings by theme with `THEME [name]:` comments on detail-colored highlights.
+5. Finish with an evidence table: claim, evidence, strength, page.";
+
+const ANALYZE: &str = "\
+## Strategy: Analytical Reading
+
+1. Identify the thesis, the premises and the conclusions. Highlight them in positive with \
+`THESIS:`, `PREMISE:` and `CONCLUSION:` comments. Keep section colors for document headings only.
+2. Note unstated assumptions in detail (`ASSUMPTION:`) and connections between arguments \
+(`CONNECTION:`).
+3. Critique the argument: check validity and soundness, and mark logical gaps, fallacies and \
+unsupported leaps in negative (`WEAKNESS:`).
+4. Mark open questions in question (`Q:`).
+5. Finish with an argument map: thesis, premises, inferences and an assessment of each.";
+
+const SKIM: &str = "\
+## Strategy: Skim
+
+Read only the title, abstract, headings, figures and conclusion; do not read the full text. \
+Use a minimal set of colors: positive for key findings and the core contribution \
+(`FINDING:`, `CORE:`), question to flag relevance (`RELEVANT:`), negative for obvious \
+limitations, and section1 for headings if marking structure helps.
+
+Finish with a brief relevance assessment: what the paper contributes, whether it is worth a \
+full reading, and which sections to read first.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_names_match_serde() {
+        for strategy in [
+            ReadingStrategy::Critical,
+            ReadingStrategy::Sq3r,
+            ReadingStrategy::Review,
+            ReadingStrategy::Analyze,
+            ReadingStrategy::Skim,
+        ] {
+            let json = serde_json::to_value(strategy).unwrap();
+            assert_eq!(json, strategy.name());
+            assert!(strategy.instructions().starts_with("## Strategy"));
+        }
+    }
+
+    #[test]
+    fn test_unknown_strategy_lists_alternatives() {
+        let error = serde_json::from_str::<ReadingStrategy>("\"speed\"").unwrap_err();
+        assert!(error.to_string().contains("sq3r"));
+    }
+}
@@ -0,0 +1,349 @@
+//! Reference list and citation tool implementations for the Zotero MCP server.
+
+use serde::Serialize;
+use zotero_client::pdf::{extract_references, parse_reference, CitationMarker, Reference};
+use zotero_client::types::ZoteroItem;
+use zotero_client::ZoteroClient;
+
+use super::tools::{find_pdf_path, ToolError};
+
+/// Least title similarity for a library item to match a reference.
+const MIN_TITLE_SIMILARITY: f64 = 0.85;
+
+/// Number of library items compared with a reference's title.
+const TITLE_CANDIDATES: u32 = 25;
+
+/// Number of title words the library is searched for.
+const TITLE_SEARCH_WORDS: usize = 4;
+
+/// An entry reported by `zotero_get_references`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceEntry {
+    /// Label of a numbered entry ("12" for "[12]")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Author names as written in the entry
+    pub authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    /// Journal, proceedings or other container of the work
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    /// 1-based page where the entry starts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// The full text of the entry
+    pub text: String,
+}
+
+impl From<Reference> for ReferenceEntry {
+    fn from(r: Reference) -> Self {
+        Self {
+            label: r.label,
+            authors: r.authors,
+            title: r.title,
+            year: r.year,
+            venue: r.venue,
+            doi: r.doi,
+            page: r.page.map(|p| p + 1),
+            text: r.text,
+        }
+    }
+}
+
+/// Result of `zotero_get_references`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceList {
+    /// Number of entries in the reference list
+    pub total_references: usize,
+    /// The entries, in the order of the reference list
+    pub references: Vec<ReferenceEntry>,
+}
+
+/// Extract and parse the reference list of a PDF attachment.
+pub async fn get_references(
+    client: &ZoteroClient,
+    attachment_key: &str,
+) -> Result<ReferenceList, ToolError> {
+    let path = find_pdf_path(client, attachment_key).await?;
+    let references = extract_references(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    Ok(ReferenceList {
+        total_references: references.len(),
+        references: references.into_iter().map(ReferenceEntry::from).collect(),
+    })
+}
+
+/// How a reference was matched to a library item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchedBy {
+    Doi,
+    Title,
+}
+
+/// A reference looked up in the library by `zotero_resolve_citation`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedReference {
+    /// The cited reference, as parsed
+    pub reference: ReferenceEntry,
+    /// Whether the library has the cited work
+    pub in_library: bool,
+    /// BetterBibTeX citation key of the library item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citekey: Option<String>,
+    /// Key of the library item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_key: Option<String>,
+    /// Title of the library item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_by: Option<MatchedBy>,
+    /// Similarity of the reference title and the item title, from 0.0 to 1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_similarity: Option<f64>,
+}
+
+/// Result of `zotero_resolve_citation`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CitationResolution {
+    /// The citation as given
+    pub citation: String,
+    /// One result per cited reference
+    pub references: Vec<ResolvedReference>,
+}
+
+/// Lowercase words of letters and digits.
+fn title_words(title: &str) -> Vec<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Similarity of two titles, from 0.0 to 1.0: the Dice coefficient of
+/// their words, ignoring case and punctuation.
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = title_words(a);
+    let mut b = title_words(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut common = 0;
+    for word in &a {
+        if let Some(i) = b.iter().position(|w| w == word) {
+            b.swap_remove(i);
+            common += 1;
+        }
+    }
+    2.0 * common as f64 / total as f64
+}
+
+/// The words of a title to search the library for: the longest ones
+/// without hyphens or other punctuation, which the library may spell differently.
+fn title_query(title: &str) -> String {
+    let mut words: Vec<&str> = title
+        .split_whitespace()
+        .filter(|word| word.chars().all(char::is_alphanumeric))
+        .collect();
+    words.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+    words.truncate(TITLE_SEARCH_WORDS);
+    words.join(" ")
+}
+
+/// Lowercase DOI without a resolver prefix.
+fn normalize_doi(doi: &str) -> String {
+    let doi = doi.trim().to_lowercase();
+    [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| doi.strip_prefix(prefix))
+    .unwrap_or(&doi)
+    .trim()
+    .to_string()
+}
+
+/// The library item with the most similar title, if it is similar enough.
+fn best_title_match<'a>(title: &str, items: &'a [ZoteroItem]) -> Option<(&'a ZoteroItem, f64)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let score = title_similarity(title, item.title.as_deref()?);
+            (score >= MIN_TITLE_SIMILARITY).then_some((item, score))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Look a reference up in the library, by DOI, then by title.
+async fn find_in_library(
+    client: &ZoteroClient,
+    reference: Reference,
+) -> Result<ResolvedReference, ToolError> {
+    let mut found = None;
+
+    if let Some(doi) = &reference.doi {
+        let doi = normalize_doi(doi);
+        let items = client.search_items(&doi, TITLE_CANDIDATES).await?;
+        found = items
+            .into_iter()
+            .find(|item| item.doi.as_deref().map(normalize_doi).as_ref() == Some(&doi))
+            .map(|item| (item, MatchedBy::Doi, None));
+    }
+
+    if found.is_none() {
+        if let Some(title) = &reference.title {
+            let query = title_query(title);
+            if !query.is_empty() {
+                let items = client.search_items(&query, TITLE_CANDIDATES).await?;
+                found = best_title_match(title, &items)
+                    .map(|(item, score)| (item.clone(), MatchedBy::Title, Some(score)));
+            }
+        }
+    }
+
+    let reference = ReferenceEntry::from(reference);
+    Ok(match found {
+        Some((item, matched_by, title_similarity)) => ResolvedReference {
+            reference,
+            in_library: true,
+            citekey: item.citation_key().map(str::to_string),
+            item_key: Some(item.key.clone()),
+            item_title: item.title.clone(),
+            matched_by: Some(matched_by),
+            title_similarity,
+        },
+        None => ResolvedReference {
+            reference,
+            in_library: false,
+            citekey: None,
+            item_key: None,
+            item_title: None,
+            matched_by: None,
+            title_similarity: None,
+        },
+    })
+}
+
+/// Check whether the works a citation refers to are in the library.
+///
+/// `citation` is either an in-text citation marker ("[12]", "Smith et al.,
+/// 2020"), looked up in the reference list of the PDF `attachment_key`, or
+/// the text of a reference entry.
+pub async fn resolve_citation(
+    client: &ZoteroClient,
+    citation: &str,
+    attachment_key: Option<&str>,
+) -> Result<CitationResolution, ToolError> {
+    let references = match CitationMarker::parse(citation) {
+        Some(marker) => {
+            let Some(attachment_key) = attachment_key else {
+                return Err(ToolError::InvalidCitation(format!(
+                    "'{}' is a citation marker; pass the attachment_key of the citing PDF",
+                    citation
+                )));
+            };
+            let path = find_pdf_path(client, attachment_key).await?;
+            let references =
+                extract_references(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+            let cited: Vec<Reference> = marker.find(&references).into_iter().cloned().collect();
+            if cited.is_empty() {
+                return Err(ToolError::CitationNotFound(citation.to_string()));
+            }
+            cited
+        }
+        None => {
+            let reference = parse_reference(citation);
+            if reference.doi.is_none() && reference.title.is_none() {
+                return Err(ToolError::InvalidCitation(format!(
+                    "no DOI or title found in '{}'",
+                    citation
+                )));
+            }
+            vec![reference]
+        }
+    };
+
+    let mut resolved = Vec::new();
+    for reference in references {
+        resolved.push(find_in_library(client, reference).await?);
+    }
+
+    Ok(CitationResolution {
+        citation: citation.to_string(),
+        references: resolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(key: &str, title: &str) -> ZoteroItem {
+        serde_json::from_value(serde_json::json!({
+            "key": key,
+            "itemType": "journalArticle",
+            "title": title,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_title_similarity_ignores_case_and_punctuation() {
+        assert_eq!(
+            title_similarity("Attention Is All You Need", "Attention is all you need."),
+            1.0
+        );
+        assert!(title_similarity("Deep learning", "Deep reinforcement learning") > 0.7);
+        assert_eq!(title_similarity("", "Deep learning"), 0.0);
+    }
+
+    #[test]
+    fn test_best_title_match_requires_similar_title() {
+        let items = vec![
+            item("AAA", "Deep learning for graphs"),
+            item(
+                "BBB",
+                "BERT: Pre-training of Deep Bidirectional Transformers for Language Understanding",
+            ),
+        ];
+        let (found, score) = best_title_match(
+            "BERT: pre-training of deep bidirectional transformers for language understanding",
+            &items,
+        )
+        .unwrap();
+        assert_eq!(found.key, "BBB");
+        assert_eq!(score, 1.0);
+        assert!(best_title_match("Deep learning", &items).is_none());
+    }
+
+    #[test]
+    fn test_title_query_uses_longest_plain_words() {
+        assert_eq!(
+            title_query(
+                "BERT: Pre-training of deep bidirectional transformers for language understanding"
+            ),
+            "bidirectional understanding transformers language"
+        );
+    }
+
+    #[test]
+    fn test_normalize_doi() {
+        assert_eq!(
+            normalize_doi("https://doi.org/10.1038/Nature14539"),
+            "10.1038/nature14539"
+        );
+        assert_eq!(normalize_doi("10.1038/nature14539"), "10.1038/nature14539");
+    }
+}
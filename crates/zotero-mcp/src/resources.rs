@@ -0,0 +1,315 @@
+//! MCP resources for the Zotero MCP server.
+//!
+//! Resources let a client attach an item, a PDF page or a PDF outline to the
+//! conversation context by URI, without a tool call:
+//!
+//! - `zotero://item/{key}` - item metadata and its PDF attachments (JSON)
+//! - `zotero://attachment/{key}/page/{n}` - text of a 1-based PDF page
+//! - `zotero://attachment/{key}/outline` - table of contents of a PDF (JSON)
+
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+    ResourceTemplate,
+};
+use serde::Serialize;
+use zotero_client::{
+    pdf::{extract_text, get_page_count, OutlineCache},
+    types::ZoteroItem,
+    ZoteroClient, ZoteroClientError,
+};
+
+use super::tools::{find_pdf_path, get_outline, ToolError};
+
+/// URI scheme shared by all resources.
+const SCHEME: &str = "zotero://";
+
+/// Maximum number of library items returned by `resources/list`.
+const LIST_LIMIT: u32 = 100;
+
+/// A parsed resource URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// `zotero://item/{key}`
+    Item(String),
+    /// `zotero://attachment/{key}/page/{n}`, with a 1-based page number
+    Page { attachment_key: String, page: u32 },
+    /// `zotero://attachment/{key}/outline`
+    Outline(String),
+}
+
+impl ResourceUri {
+    /// Parse a resource URI, returning `None` if it does not name a resource.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let parts: Vec<&str> = uri.strip_prefix(SCHEME)?.split('/').collect();
+        let valid_key =
+            |key: &str| !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric());
+
+        match parts.as_slice() {
+            ["item", key] if valid_key(key) => Some(Self::Item(key.to_string())),
+            ["attachment", key, "page", page] if valid_key(key) => Some(Self::Page {
+                attachment_key: key.to_string(),
+                page: page.parse().ok().filter(|&p| p > 0)?,
+            }),
+            ["attachment", key, "outline"] if valid_key(key) => {
+                Some(Self::Outline(key.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The URI string of this resource.
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Item(key) => format!("{}item/{}", SCHEME, key),
+            Self::Page {
+                attachment_key,
+                page,
+            } => format!("{}attachment/{}/page/{}", SCHEME, attachment_key, page),
+            Self::Outline(key) => format!("{}attachment/{}/outline", SCHEME, key),
+        }
+    }
+}
+
+/// Contents of an item resource.
+#[derive(Debug, Serialize)]
+struct ItemResource {
+    item: ZoteroItem,
+    attachments: Vec<AttachmentResource>,
+}
+
+/// A PDF attachment listed in an item resource, with the URIs to read it.
+#[derive(Debug, Serialize)]
+struct AttachmentResource {
+    key: String,
+    title: Option<String>,
+    outline: String,
+    first_page: String,
+}
+
+/// The resource templates offered by the server.
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    let template = |uri: &str, name: &str, description: &str, mime_type: &str| {
+        RawResourceTemplate {
+            uri_template: format!("{}{}", SCHEME, uri),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(mime_type.to_string()),
+        }
+        .no_annotation()
+    };
+
+    vec![
+        template(
+            "item/{key}",
+            "Zotero item",
+            "Metadata of a Zotero item (title, creators, date, DOI, tags, abstract) and its PDF attachments",
+            "application/json",
+        ),
+        template(
+            "attachment/{key}/page/{n}",
+            "PDF page",
+            "Text of page n (1-based) of a PDF attachment",
+            "text/plain",
+        ),
+        template(
+            "attachment/{key}/outline",
+            "PDF outline",
+            "Table of contents of a PDF attachment, with section titles and page numbers",
+            "application/json",
+        ),
+    ]
+}
+
+/// List library items as resources.
+pub async fn list_item_resources(client: &ZoteroClient) -> Result<Vec<Resource>, ToolError> {
+    let items = client.list_items(LIST_LIMIT).await?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            let mut resource = RawResource::new(
+                ResourceUri::Item(item.key.clone()).uri(),
+                item.title.clone().unwrap_or_else(|| item.key.clone()),
+            );
+            resource.description = Some(describe_item(&item));
+            resource.mime_type = Some("application/json".to_string());
+            resource.no_annotation()
+        })
+        .collect())
+}
+
+/// Read a resource by URI.
+pub async fn read_resource(
+    client: &ZoteroClient,
+    outlines: &OutlineCache,
+    uri: &str,
+) -> Result<ReadResourceResult, ToolError> {
+    let resource =
+        ResourceUri::parse(uri).ok_or_else(|| ToolError::InvalidResourceUri(uri.to_string()))?;
+
+    let (mime_type, text) = match &resource {
+        ResourceUri::Item(key) => ("application/json", read_item(client, key).await?),
+        ResourceUri::Page {
+            attachment_key,
+            page,
+        } => (
+            "text/plain",
+            read_page(client, attachment_key, *page).await?,
+        ),
+        ResourceUri::Outline(key) => {
+            let outline = get_outline(client, outlines, key).await?;
+            ("application/json", to_json(&outline))
+        }
+    };
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            text,
+            meta: None,
+        }],
+    })
+}
+
+/// The item's metadata and its PDF attachments, as JSON.
+async fn read_item(client: &ZoteroClient, key: &str) -> Result<String, ToolError> {
+    let item = client.get_item(key).await.map_err(|e| match e {
+        ZoteroClientError::NotFound { key } => ToolError::ItemNotFound(key),
+        other => ToolError::ClientError(other),
+    })?;
+
+    let attachments = client
+        .get_pdf_attachments(key)
+        .await?
+        .into_iter()
+        .map(|pdf| AttachmentResource {
+            outline: ResourceUri::Outline(pdf.key.clone()).uri(),
+            first_page: ResourceUri::Page {
+                attachment_key: pdf.key.clone(),
+                page: 1,
+            }
+            .uri(),
+            key: pdf.key,
+            title: pdf.title,
+        })
+        .collect();
+
+    Ok(to_json(&ItemResource { item, attachments }))
+}
+
+/// The text of a 1-based page of a PDF attachment.
+async fn read_page(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    page: u32,
+) -> Result<String, ToolError> {
+    let path = find_pdf_path(client, attachment_key).await?;
+    let total_pages = get_page_count(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+    if page as usize > total_pages {
+        return Err(ToolError::PageOutOfRange(page, total_pages));
+    }
+
+    extract_text(&path, page as usize - 1).map_err(|e| ToolError::PdfError(e.to_string()))
+}
+
+/// A one-line description of an item: creators, date and type.
+fn describe_item(item: &ZoteroItem) -> String {
+    let creators: Vec<&str> = item
+        .creators
+        .iter()
+        .filter_map(|c| c.last_name.as_deref().or(c.name.as_deref()))
+        .collect();
+    let creators = match creators.as_slice() {
+        [] => String::new(),
+        [one] => format!("{} ", one),
+        [first, second] => format!("{} & {} ", first, second),
+        [first, ..] => format!("{} et al. ", first),
+    };
+    let date = item
+        .date
+        .as_deref()
+        .map(|d| format!("({}) ", d))
+        .unwrap_or_default();
+
+    format!("{}{}- {}", creators, date, item.item_type)
+}
+
+/// Pretty-printed JSON of a resource.
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value)
+        .unwrap_or_else(|e| format!("Failed to serialize resource: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_uri() {
+        assert_eq!(
+            ResourceUri::parse("zotero://item/ABCD1234"),
+            Some(ResourceUri::Item("ABCD1234".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_page_uri() {
+        assert_eq!(
+            ResourceUri::parse("zotero://attachment/PDF12345/page/3"),
+            Some(ResourceUri::Page {
+                attachment_key: "PDF12345".to_string(),
+                page: 3
+            })
+        );
+        assert_eq!(
+            ResourceUri::parse("zotero://attachment/PDF12345/page/0"),
+            None
+        );
+        assert_eq!(
+            ResourceUri::parse("zotero://attachment/PDF12345/page/x"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_outline_uri() {
+        assert_eq!(
+            ResourceUri::parse("zotero://attachment/PDF12345/outline"),
+            Some(ResourceUri::Outline("PDF12345".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_uris() {
+        assert_eq!(ResourceUri::parse("file:///tmp/a.pdf"), None);
+        assert_eq!(ResourceUri::parse("zotero://item/"), None);
+        assert_eq!(ResourceUri::parse("zotero://item/AB/CD"), None);
+        assert_eq!(ResourceUri::parse("zotero://attachment/PDF12345"), None);
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        for uri in [
+            "zotero://item/ABCD1234",
+            "zotero://attachment/PDF12345/page/12",
+            "zotero://attachment/PDF12345/outline",
+        ] {
+            assert_eq!(ResourceUri::parse(uri).unwrap().uri(), uri);
+        }
+    }
+
+    #[test]
+    fn test_templates_match_parser() {
+        let templates = resource_templates();
+        assert_eq!(templates.len(), 3);
+        for template in templates {
+            let uri = template
+                .uri_template
+                .replace("{key}", "ABCD1234")
+                .replace("{n}", "1");
+            assert!(ResourceUri::parse(&uri).is_some(), "{}", uri);
+        }
+    }
+}
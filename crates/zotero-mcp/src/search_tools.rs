@@ -0,0 +1,81 @@
+//! Within-document search tool implementation for the Zotero MCP server.
+
+use serde::Serialize;
+use zotero_client::pdf::{get_page_count, search_pages, SearchOptions, TextMatch, TextMatcher};
+use zotero_client::ZoteroClient;
+
+use super::tools::{find_pdf_path, parse_page_range, ToolError};
+
+/// A match reported by `zotero_search_pdf`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PdfMatch {
+    /// 1-based page number
+    pub page: u32,
+    /// The matched text
+    pub text: String,
+    /// The matched text with surrounding context
+    pub snippet: String,
+    /// One rectangle [x1, y1, x2, y2] per matched line, in PDF coordinates
+    pub rects: Vec<[f64; 4]>,
+}
+
+impl From<TextMatch> for PdfMatch {
+    fn from(m: TextMatch) -> Self {
+        Self {
+            page: m.page + 1,
+            text: m.text,
+            snippet: m.snippet,
+            rects: m.rects,
+        }
+    }
+}
+
+/// Result of `zotero_search_pdf`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PdfSearchResult {
+    /// Number of matches returned
+    pub total_matches: usize,
+    /// 1-based pages with at least one match, in the order searched
+    pub pages: Vec<u32>,
+    /// Whether the search stopped at the maximum number of matches
+    pub truncated: bool,
+    /// The matches, in the order searched
+    pub matches: Vec<PdfMatch>,
+}
+
+/// Search the text of a PDF attachment.
+///
+/// `pages` restricts the search to a page range (see `zotero_read_pdf_pages`);
+/// all pages are searched by default.
+pub async fn search_pdf(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    query: &str,
+    options: SearchOptions,
+    pages: Option<&str>,
+    max_matches: usize,
+) -> Result<PdfSearchResult, ToolError> {
+    let matcher = TextMatcher::new(query, options)?;
+    let path = find_pdf_path(client, attachment_key).await?;
+    let total_pages = get_page_count(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+    let pages = match pages {
+        Some(pages) => parse_page_range(pages, total_pages)?,
+        None => (0..total_pages).collect(),
+    };
+
+    // Ask for one more match than needed to tell whether there are more
+    let mut matches = search_pages(&path, &pages, &matcher, max_matches + 1)
+        .map_err(|e| ToolError::PdfError(e.to_string()))?;
+    let truncated = matches.len() > max_matches;
+    matches.truncate(max_matches);
+
+    let mut hit_pages: Vec<u32> = matches.iter().map(|m| m.page + 1).collect();
+    hit_pages.dedup();
+
+    Ok(PdfSearchResult {
+        total_matches: matches.len(),
+        pages: hit_pages,
+        truncated,
+        matches: matches.into_iter().map(PdfMatch::from).collect(),
+    })
+}
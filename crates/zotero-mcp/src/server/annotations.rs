@@ -0,0 +1,374 @@
+//! Annotation tools: creating, listing, updating and deleting annotations.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, model::*, schemars, schemars::JsonSchema, tool,
+    tool_router, ErrorData as McpError,
+};
+use serde::{Deserialize, Serialize};
+
+use super::ZoteroMcpServer;
+use crate::annotation_tools::{
+    create_area_annotation, create_highlight, create_ink_annotation, create_note_annotation,
+    create_underline, delete_annotation, list_annotations, update_annotation, NewPosition,
+};
+use crate::tools::HighlightColorParam;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateHighlightParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Text to highlight, as quoted from the PDF. Whitespace and hyphenated line
+    /// ends are ignored, and the passage may continue onto following pages.
+    pub text: String,
+    /// 1-based page number where the passage starts
+    pub page: u32,
+    /// Semantic color for the highlight
+    pub color: HighlightColorParam,
+    /// Optional comment
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Opt into approximate matching: if the text is not found exactly, highlight the
+    /// most similar passage when its similarity score is at least this value, which
+    /// must be above 0.0 and at most 1.0.
+    /// Tolerates ligatures, smart quotes, missing accents, OCR errors. 0.85 is a good start.
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateAreaAnnotationParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+    /// Bounding box [x1, y1, x2, y2] in PDF coordinates
+    pub rect: [f64; 4],
+    /// Semantic color for the annotation
+    pub color: HighlightColorParam,
+    /// Optional comment
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateUnderlineParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Text to underline, as quoted from the PDF. Whitespace and hyphenated line
+    /// ends are ignored, and the passage may continue onto following pages.
+    pub text: String,
+    /// 1-based page number where the passage starts
+    pub page: u32,
+    /// Semantic color for the underline
+    pub color: HighlightColorParam,
+    /// Optional comment
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Opt into approximate matching with this minimum similarity score (0.0-1.0),
+    /// as in zotero_create_highlight
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateNoteAnnotationParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Text to pin the note next to, as quoted from the PDF. The text itself is not marked.
+    pub text: String,
+    /// 1-based page number where the text starts
+    pub page: u32,
+    /// Content of the note
+    pub comment: String,
+    /// Semantic color for the note
+    pub color: HighlightColorParam,
+    /// Opt into approximate matching with this minimum similarity score (0.0-1.0),
+    /// as in zotero_create_highlight
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateInkAnnotationParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+    /// Strokes, each a flat list of points [x1, y1, x2, y2, ...] in PDF coordinates
+    pub paths: Vec<Vec<f64>>,
+    /// Stroke width in points (default: 2)
+    #[serde(default)]
+    pub width: Option<f64>,
+    /// Semantic color for the drawing
+    pub color: HighlightColorParam,
+    /// Optional comment
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListAnnotationsParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Only include annotations of this type (e.g., "highlight", "image", "note")
+    #[serde(default)]
+    pub annotation_type: Option<String>,
+    /// Only include annotations with this semantic color
+    #[serde(default)]
+    pub color: Option<HighlightColorParam>,
+    /// Only include annotations on this 1-based page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateAnnotationParams {
+    /// Annotation key (from zotero_list_annotations or a create tool)
+    pub annotation_key: String,
+    /// New comment. An empty string clears the comment.
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// New semantic color
+    #[serde(default)]
+    pub color: Option<HighlightColorParam>,
+    /// New annotation text
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Zotero attachment key for the PDF; required with page and rects
+    #[serde(default)]
+    pub attachment_key: Option<String>,
+    /// New 1-based page number; given together with rects
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// New rectangles [x1, y1, x2, y2] in PDF coordinates, in reading order
+    #[serde(default)]
+    pub rects: Option<Vec<[f64; 4]>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteAnnotationParams {
+    /// Annotation key (from zotero_list_annotations or a create tool)
+    pub annotation_key: String,
+}
+
+#[tool_router(router = annotation_router, vis = "pub(super)")]
+impl ZoteroMcpServer {
+    /// Create a text highlight annotation on a PDF.
+    ///
+    /// The text is matched ignoring whitespace and hyphenated line ends; passages
+    /// crossing a page break get one annotation per page. Uses semantic colors:
+    /// - section1 (blue), section2 (purple), section3 (magenta) for organization
+    /// - positive (green), detail (grey), negative (red) for assessment
+    /// - code (orange) for technical content
+    #[tool(
+        name = "zotero_create_highlight",
+        description = "Create a text highlight annotation. Quote the text as read from the PDF starting on the given page; line breaks, hyphenation and page breaks are handled (one annotation per page). Set min_score (e.g. 0.85) to allow approximate matches; the matched text is reported. Colors: section1/2/3, positive, detail, negative, code."
+    )]
+    async fn zotero_create_highlight(
+        &self,
+        Parameters(params): Parameters<CreateHighlightParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_highlight(
+            &self.client,
+            &params.attachment_key,
+            &params.text,
+            params.page,
+            params.color,
+            params.comment.as_deref(),
+            params.min_score,
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Create a text underline annotation on a PDF.
+    ///
+    /// The text is matched like a highlight; use underlines to mark passages
+    /// without covering them.
+    #[tool(
+        name = "zotero_create_underline",
+        description = "Create a text underline annotation. Quote the text as read from the PDF starting on the given page; matching works as in zotero_create_highlight (min_score allows approximate matches). Colors: section1/2/3, positive, detail, negative, code."
+    )]
+    async fn zotero_create_underline(
+        &self,
+        Parameters(params): Parameters<CreateUnderlineParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_underline(
+            &self.client,
+            &params.attachment_key,
+            &params.text,
+            params.page,
+            params.color,
+            params.comment.as_deref(),
+            params.min_score,
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Create an area/image annotation on a PDF for figures, diagrams, etc.
+    ///
+    /// Specify the rectangular region using PDF coordinates [x1, y1, x2, y2].
+    #[tool(
+        name = "zotero_create_area_annotation",
+        description = "Create an area annotation for figures/diagrams. Specify rect as [x1, y1, x2, y2] in PDF coordinates."
+    )]
+    async fn zotero_create_area_annotation(
+        &self,
+        Parameters(params): Parameters<CreateAreaAnnotationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_area_annotation(
+            &self.client,
+            &params.attachment_key,
+            params.page,
+            params.rect,
+            params.color,
+            params.comment.as_deref(),
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Pin a note (sticky note) annotation next to a quoted passage.
+    ///
+    /// The note is placed beside the first line of the passage, which is left
+    /// unmarked.
+    #[tool(
+        name = "zotero_create_note_annotation",
+        description = "Pin a sticky note with a comment next to quoted text on a PDF page, without marking the text. Use for remarks that refer to a passage. Set min_score to allow approximate matches."
+    )]
+    async fn zotero_create_note_annotation(
+        &self,
+        Parameters(params): Parameters<CreateNoteAnnotationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_note_annotation(
+            &self.client,
+            &params.attachment_key,
+            &params.text,
+            params.page,
+            &params.comment,
+            params.color,
+            params.min_score,
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Create an ink (freehand drawing) annotation on a PDF.
+    #[tool(
+        name = "zotero_create_ink_annotation",
+        description = "Draw freehand strokes on a PDF page, e.g. to circle or connect elements. Each path is a flat list of points [x1, y1, x2, y2, ...] in PDF coordinates."
+    )]
+    async fn zotero_create_ink_annotation(
+        &self,
+        Parameters(params): Parameters<CreateInkAnnotationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_ink_annotation(
+            &self.client,
+            &params.attachment_key,
+            params.page,
+            params.paths,
+            params.width,
+            params.color,
+            params.comment.as_deref(),
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// List existing annotations on a PDF attachment.
+    ///
+    /// Annotations are returned in reading order and can be filtered by type,
+    /// semantic color and page.
+    #[tool(
+        name = "zotero_list_annotations",
+        description = "List existing annotations on a PDF in reading order. Optionally filter by annotation_type (highlight/image/note), semantic color, or 1-based page."
+    )]
+    async fn zotero_list_annotations(
+        &self,
+        Parameters(params): Parameters<ListAnnotationsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match list_annotations(
+            &self.client,
+            &params.attachment_key,
+            params.annotation_type.as_deref(),
+            params.color,
+            params.page,
+        )
+        .await
+        {
+            Ok(annotations) => {
+                if annotations.is_empty() {
+                    Ok(CallToolResult::success(vec![Content::text(
+                        "No annotations found.",
+                    )]))
+                } else {
+                    let json = serde_json::to_string_pretty(&annotations)
+                        .unwrap_or_else(|_| "Failed to serialize annotations".to_string());
+                    Ok(CallToolResult::success(vec![Content::text(json)]))
+                }
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Update the comment, color or text of an existing annotation.
+    ///
+    /// Only the provided fields are changed.
+    #[tool(
+        name = "zotero_update_annotation",
+        description = "Update an existing annotation by key. Only provided fields change: comment (empty string clears it), semantic color, text. To move it, pass attachment_key, page and rects together; the page label and reading order are updated to match."
+    )]
+    async fn zotero_update_annotation(
+        &self,
+        Parameters(params): Parameters<UpdateAnnotationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let position = match NewPosition::new(params.attachment_key, params.page, params.rects) {
+            Ok(position) => position,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match update_annotation(
+            &self.client,
+            &params.annotation_key,
+            params.comment.as_deref(),
+            params.color,
+            params.text.as_deref(),
+            position.as_ref(),
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Permanently delete an annotation.
+    #[tool(
+        name = "zotero_delete_annotation",
+        description = "Permanently delete an annotation by key. Use to remove incorrect highlights or area annotations."
+    )]
+    async fn zotero_delete_annotation(
+        &self,
+        Parameters(params): Parameters<DeleteAnnotationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match delete_annotation(&self.client, &params.annotation_key).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+}
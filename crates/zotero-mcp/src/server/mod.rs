@@ -0,0 +1,573 @@
+//! MCP Server implementation for Zotero.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rmcp::{
+    handler::server::{router::prompt::PromptRouter, tool::ToolRouter, wrapper::Parameters},
+    model::*,
+    prompt_handler, schemars,
+    schemars::JsonSchema,
+    service::RequestContext,
+    tool, tool_handler, tool_router, ErrorData as McpError, RoleServer, ServerHandler,
+};
+use serde::{Deserialize, Serialize};
+use zotero_client::{
+    image::{FigureIndexCache, RenderLimits},
+    pdf::OutlineCache,
+    ZoteroClient, ZoteroClientError,
+};
+
+use crate::export_tools::export_annotations;
+use crate::fulltext_tools::FulltextState;
+use crate::image_tools::{
+    extract_page_image, get_figure, list_all_figures, list_figures, FigureSelector,
+    ImageFormatParam,
+};
+use crate::note_tools::create_note;
+use crate::resources::{list_item_resources, read_resource, resource_templates};
+use crate::table_tools::{extract_table, TableFormatParam};
+use crate::tools::{get_outline, lookup_item, read_pdf_pages, ExtractionMode, ToolError};
+
+mod annotations;
+mod prompts;
+mod references;
+mod search;
+
+/// MCP Server for Zotero integration.
+#[derive(Clone)]
+pub struct ZoteroMcpServer {
+    client: Arc<ZoteroClient>,
+    fulltext: Arc<FulltextState>,
+    figures: FigureIndexCache,
+    outlines: OutlineCache,
+    tool_router: ToolRouter<Self>,
+    prompt_router: PromptRouter<Self>,
+}
+
+// Tool parameter schemas
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LookupParams {
+    /// BetterBibTeX citation key (e.g., "smithMachineLearning2023")
+    pub citekey: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetPdfOutlineParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReadPdfPagesParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Page range (e.g., "1-5", "1,3,5", "all"). Either pages or section is required.
+    #[serde(default)]
+    pub pages: Option<String>,
+    /// Section name(s) from PDF outline (e.g., "Introduction", "Introduction,Methods").
+    /// Without bookmarks, sections are found from the headings. Either pages or section is required.
+    #[serde(default)]
+    pub section: Option<String>,
+    /// Text order: "native" (as stored in the PDF), "reading_order" (columns of
+    /// multi-column papers read one after the other) or "section" (only the requested
+    /// sections, from their heading to the next heading; requires section) (default: native)
+    #[serde(default)]
+    pub mode: Option<ExtractionMode>,
+    /// Leave out running headers, footers and page numbers. Only applies to
+    /// reading_order and section modes (default: false)
+    #[serde(default)]
+    pub strip_headers_footers: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateNoteParams {
+    /// Key of the Zotero item to attach the note to (the item key from zotero_lookup,
+    /// not a PDF attachment key). Omit to create a standalone note.
+    #[serde(default)]
+    pub parent_key: Option<String>,
+    /// Note content in Markdown: headings, paragraphs, lists, blockquotes, code blocks,
+    /// links, bold/italic/strikethrough and inline code. The first line becomes the title.
+    pub markdown: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportAnnotationsParams {
+    /// BetterBibTeX citation key of the item whose annotations to export
+    pub citekey: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractPageImageParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+    /// Optional region [x1, y1, x2, y2] in PDF coordinates. If omitted, renders full page.
+    #[serde(default)]
+    pub rect: Option<[f64; 4]>,
+    /// Resolution in DPI (default: 150). Higher DPI = larger file size and better quality.
+    #[serde(default)]
+    pub dpi: Option<u32>,
+    /// Output format: "png" (default) or "jpeg". JPEG is ~80% smaller.
+    #[serde(default)]
+    pub format: Option<ImageFormatParam>,
+    /// Maximum image width in pixels. The DPI is lowered to fit.
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    /// Maximum image height in pixels. The DPI is lowered to fit.
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    /// Maximum image file size in bytes. The JPEG quality, then the DPI, is lowered to fit.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListFiguresParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListAllFiguresParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Only list figures and tables that have a caption (default: false)
+    #[serde(default)]
+    pub captioned_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetFigureParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Caption label of the figure or table, e.g. "Figure 3", "Fig. 2b" or "Table 1".
+    /// Without page, it is looked up in the figure index of the whole PDF.
+    #[serde(default)]
+    pub figure: Option<String>,
+    /// 1-based page number. Required with figure_index.
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Figure index from zotero_list_figures; not combined with figure
+    #[serde(default)]
+    pub figure_index: Option<usize>,
+    /// Output format: "png" (default) or "jpeg"
+    #[serde(default)]
+    pub format: Option<ImageFormatParam>,
+    /// Include padding around the figure for context
+    #[serde(default)]
+    pub include_context: Option<bool>,
+    /// Maximum image width in pixels. The DPI is lowered to fit.
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    /// Maximum image height in pixels. The DPI is lowered to fit.
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    /// Maximum image file size in bytes. The JPEG quality, then the DPI, is lowered to fit.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractTableParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// 1-based page number
+    pub page: u32,
+    /// Optional table region [x1, y1, x2, y2] in PDF coordinates. If omitted, the
+    /// table drawn with the most horizontal rules on the page is read.
+    #[serde(default)]
+    pub rect: Option<[f64; 4]>,
+    /// Output format: "markdown" (default) or "csv"
+    #[serde(default)]
+    pub format: Option<TableFormatParam>,
+}
+
+#[tool_router]
+impl ZoteroMcpServer {
+    /// Create a new Zotero MCP server.
+    pub fn new(zotero_url: &str) -> Self {
+        Self {
+            client: Arc::new(ZoteroClient::with_base_url(zotero_url)),
+            fulltext: Arc::new(FulltextState::default()),
+            figures: FigureIndexCache::new(),
+            outlines: OutlineCache::new(),
+            tool_router: Self::tool_router()
+                + Self::annotation_router()
+                + Self::search_router()
+                + Self::reference_router(),
+            prompt_router: Self::prompt_router(),
+        }
+    }
+
+    /// Store the full-text search index at `path` instead of the user's cache directory.
+    pub fn with_index_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.fulltext = Arc::new(FulltextState::new(path));
+        self
+    }
+
+    /// Find a Zotero item by its BetterBibTeX citation key.
+    ///
+    /// Returns the item metadata including key, title, and PDF attachment keys.
+    #[tool(
+        name = "zotero_lookup",
+        description = "Find a Zotero item by its BetterBibTeX citation key. Returns item metadata including PDF attachment keys."
+    )]
+    async fn zotero_lookup(
+        &self,
+        Parameters(params): Parameters<LookupParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match lookup_item(&self.client, &params.citekey).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Get the PDF outline (table of contents/bookmarks).
+    ///
+    /// Returns the document's outline structure with section titles and page numbers.
+    /// Use this to discover available sections before reading by section name.
+    #[tool(
+        name = "zotero_get_pdf_outline",
+        description = "Get PDF outline (table of contents/bookmarks). Returns section titles and page numbers. If the PDF has no bookmarks, the outline is inferred from its section headings and flagged with inferred: true. Use to discover sections before reading."
+    )]
+    async fn zotero_get_pdf_outline(
+        &self,
+        Parameters(params): Parameters<GetPdfOutlineParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match get_outline(&self.client, &self.outlines, &params.attachment_key).await {
+            Ok(outline) => {
+                // Format the outline for display
+                let json = serde_json::to_string_pretty(&outline)
+                    .unwrap_or_else(|_| "Failed to serialize outline".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Extract text from specific pages of a PDF attachment.
+    ///
+    /// Supports page ranges (e.g., "1-5"), comma-separated pages (e.g., "1,3,5"),
+    /// "all" for the entire document, or section names from the PDF outline.
+    #[tool(
+        name = "zotero_read_pdf_pages",
+        description = "Extract text from specific pages of a PDF attachment. Use page ranges like '1-5', '1,3,5', or 'all'. Alternatively, use 'section' parameter with section names from the PDF outline. For two-column papers, set mode to 'reading_order' to read columns in order, optionally with strip_headers_footers. Set mode to 'section' to get exactly the requested sections, cut at their headings, instead of whole pages."
+    )]
+    async fn zotero_read_pdf_pages(
+        &self,
+        Parameters(params): Parameters<ReadPdfPagesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match read_pdf_pages(
+            &self.client,
+            &self.outlines,
+            &params.attachment_key,
+            params.pages.as_deref(),
+            params.section.as_deref(),
+            params.mode.unwrap_or_default(),
+            params.strip_headers_footers.unwrap_or(false),
+        )
+        .await
+        {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Create a Zotero note from Markdown.
+    ///
+    /// The note is attached to an item, or standalone if no parent is given.
+    #[tool(
+        name = "zotero_create_note",
+        description = "Create a Zotero note from Markdown, attached to an item (parent_key = item key from zotero_lookup) or standalone. Use to store summaries and syntheses next to the item in Zotero. Start with a heading: the first line becomes the note title."
+    )]
+    async fn zotero_create_note(
+        &self,
+        Parameters(params): Parameters<CreateNoteParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match create_note(&self.client, params.parent_key.as_deref(), &params.markdown).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Export an item's annotations as a Markdown note for Obsidian.
+    ///
+    /// Annotations are grouped by color meaning and link back to their pages.
+    #[tool(
+        name = "zotero_export_annotations",
+        description = "Export all annotations of an item's PDFs as a Markdown note for Obsidian: Dataview frontmatter (type, citekey, source, authors, year, tags, created), an outline from section colors, and sections per semantic color (positive, negative, question, detail, code). Each annotation links to its page as [[citekey#p. X|p. X]]. Use as input for summaries and syntheses."
+    )]
+    async fn zotero_export_annotations(
+        &self,
+        Parameters(params): Parameters<ExportAnnotationsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match export_annotations(&self.client, &params.citekey).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(result)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Render a PDF page or region as an image for vision AI analysis.
+    ///
+    /// Returns a base64-encoded image (PNG or JPEG) that can be analyzed by vision models.
+    #[tool(
+        name = "zotero_extract_page_image",
+        description = "Render a PDF page or region as an image file for vision analysis. Returns file path to saved image with its pixel size and effective DPI. Use rect parameter to render specific regions only. Set max_width, max_height or max_bytes to fit an image budget; the DPI (or JPEG quality) is lowered automatically."
+    )]
+    async fn zotero_extract_page_image(
+        &self,
+        Parameters(params): Parameters<ExtractPageImageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match extract_page_image(
+            &self.client,
+            &params.attachment_key,
+            params.page,
+            params.rect,
+            params.dpi,
+            params.format.unwrap_or_default(),
+            RenderLimits {
+                max_width: params.max_width,
+                max_height: params.max_height,
+                max_bytes: params.max_bytes,
+            },
+        )
+        .await
+        {
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize result".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Detect and list figure regions on a PDF page.
+    ///
+    /// Groups the page's drawings and images into figures and matches
+    /// "Figure N" / "Table N" captions to them. Returns bounding boxes,
+    /// estimated types and captions for each figure.
+    #[tool(
+        name = "zotero_list_figures",
+        description = "Detect and list figures and tables on a PDF page from its drawings, images and captions. Returns bounding boxes, estimated types (image/chart/diagram/table) and, for captioned figures, the label (\"Figure 3\") and caption text."
+    )]
+    async fn zotero_list_figures(
+        &self,
+        Parameters(params): Parameters<ListFiguresParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match list_figures(&self.client, &params.attachment_key, params.page).await {
+            Ok(figures) => {
+                if figures.is_empty() {
+                    Ok(CallToolResult::success(vec![Content::text(
+                        "No figures detected on this page.",
+                    )]))
+                } else {
+                    let json = serde_json::to_string_pretty(&figures)
+                        .unwrap_or_else(|_| "Failed to serialize figures".to_string());
+                    Ok(CallToolResult::success(vec![Content::text(json)]))
+                }
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// List the figures and tables of a whole PDF.
+    ///
+    /// Runs figure detection over every page once per file and keeps the
+    /// index until the file changes, so later lookups by label are cheap.
+    #[tool(
+        name = "zotero_list_all_figures",
+        description = "List the figures and tables of every page of a PDF attachment: 1-based page, rect, type, label (\"Figure 3\") and caption. Use to find a figure without knowing its page; zotero_get_figure takes the label directly. The index is cached per file."
+    )]
+    async fn zotero_list_all_figures(
+        &self,
+        Parameters(params): Parameters<ListAllFiguresParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match list_all_figures(
+            &self.client,
+            &self.figures,
+            &params.attachment_key,
+            params.captioned_only.unwrap_or(false),
+        )
+        .await
+        {
+            Ok(result) if result.figures.is_empty() => {
+                Ok(CallToolResult::success(vec![Content::text(
+                    "No figures detected in this PDF.",
+                )]))
+            }
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize figures".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Extract a specific figure as an image file.
+    ///
+    /// A figure is picked by its caption label ("Figure 3"), or by its index
+    /// among the figures `zotero_list_figures` finds on a page. Returns file path.
+    #[tool(
+        name = "zotero_get_figure",
+        description = "Extract a figure or table as an image file. Pass figure as a caption label (\"Figure 3\", \"Table 2\"), with or without page, or pass page and a figure_index from zotero_list_figures, not both. Returns the file path, pixel size and effective DPI, the 1-based page and the figure's rect, label and caption. max_width, max_height and max_bytes lower the DPI (or JPEG quality) to fit."
+    )]
+    async fn zotero_get_figure(
+        &self,
+        Parameters(params): Parameters<GetFigureParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let selector = match FigureSelector::new(params.page, params.figure_index, params.figure) {
+            Ok(selector) => selector,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match get_figure(
+            &self.client,
+            &self.figures,
+            &params.attachment_key,
+            selector,
+            params.format.unwrap_or_default(),
+            params.include_context.unwrap_or(false),
+            RenderLimits {
+                max_width: params.max_width,
+                max_height: params.max_height,
+                max_bytes: params.max_bytes,
+            },
+        )
+        .await
+        {
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize result".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Extract the rows and columns of a table on a PDF page.
+    ///
+    /// Rows and columns come from the text positions and the table's ruling
+    /// lines, so the cells can be read without a vision model.
+    #[tool(
+        name = "zotero_extract_table",
+        description = "Extract a table from a PDF page as Markdown (default) or CSV. Rows and columns are read from text positions and ruling lines. Without rect, the table drawn with horizontal rules (booktabs or grid) is found automatically; pass rect [x1, y1, x2, y2] in PDF coordinates (e.g. from zotero_list_figures) for tables without rules or when a page has several tables."
+    )]
+    async fn zotero_extract_table(
+        &self,
+        Parameters(params): Parameters<ExtractTableParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match extract_table(
+            &self.client,
+            &params.attachment_key,
+            params.page,
+            params.rect,
+            params.format.unwrap_or_default(),
+        )
+        .await
+        {
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize table".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+}
+
+#[tool_handler]
+#[prompt_handler]
+impl ServerHandler for ZoteroMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(
+                "Zotero MCP Server for AI-assisted critical reading with vision support. \
+                Use zotero_lookup to find items by citation key, \
+                zotero_get_pdf_outline to discover document sections, \
+                zotero_read_pdf_pages to extract text (by page or section), \
+                zotero_create_highlight/zotero_create_underline/zotero_create_area_annotation \
+                to mark passages and figures, \
+                zotero_create_note_annotation to pin remarks next to a passage, \
+                zotero_create_ink_annotation to draw, \
+                zotero_create_note to save Markdown summaries as Zotero notes, \
+                zotero_export_annotations to export annotations as Markdown for Obsidian, \
+                zotero_fulltext_search to find which papers in the library mention a topic, \
+                zotero_search_pdf to find the pages of a PDF that mention a term, \
+                zotero_list_annotations to review existing annotations, \
+                zotero_update_annotation/zotero_delete_annotation to correct them, \
+                zotero_extract_page_image to render pages for vision analysis, \
+                zotero_list_figures/zotero_list_all_figures/zotero_get_figure to detect and \
+                extract figures, and \
+                zotero_extract_table to read tables as Markdown or CSV. \
+                Items, PDF pages and outlines are also available as resources: \
+                zotero://item/{key}, zotero://attachment/{key}/page/{n} and \
+                zotero://attachment/{key}/outline. \
+                The readassist-read, readassist-summarize and readassist-synthesize prompts \
+                start the reading, summary and synthesis workflows."
+                    .to_string(),
+            ),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let resources = list_item_resources(&self.client).await.map_err(mcp_error)?;
+        Ok(ListResourcesResult {
+            resources,
+            ..Default::default()
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: resource_templates(),
+            ..Default::default()
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        read_resource(&self.client, &self.outlines, &request.uri)
+            .await
+            .map_err(mcp_error)
+    }
+}
+
+/// Convert a failure outside of a tool call to the matching MCP error.
+fn mcp_error(error: ToolError) -> McpError {
+    let message = error.to_string();
+    match error {
+        ToolError::ItemNotFound(_)
+        | ToolError::PdfNotFound(_)
+        | ToolError::FileNotFound(_)
+        | ToolError::ClientError(ZoteroClientError::NotFound { .. }) => {
+            McpError::resource_not_found(message, None)
+        }
+        ToolError::InvalidResourceUri(_) | ToolError::PageOutOfRange(..) => {
+            McpError::invalid_params(message, None)
+        }
+        _ => McpError::internal_error(message, None),
+    }
+}
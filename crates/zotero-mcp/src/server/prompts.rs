@@ -0,0 +1,136 @@
+//! Prompts for the critical-reading workflows: reading, summarizing and
+//! synthesizing.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, model::*, prompt, prompt_router, schemars,
+    schemars::JsonSchema, ErrorData as McpError,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{mcp_error, ZoteroMcpServer};
+use crate::export_tools::export_annotations;
+use crate::prompts::{
+    load_document, read_prompt, summarize_prompt, synthesize_prompt, ReadingStrategy,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReadPromptArgs {
+    /// BetterBibTeX citation key of the item to read
+    pub citekey: String,
+    /// Reading strategy: critical (default), sq3r, review, analyze or skim
+    #[serde(default)]
+    pub strategy: Option<ReadingStrategy>,
+    /// What the reading should focus on (e.g., "understand the methodology")
+    #[serde(default)]
+    pub purpose: Option<String>,
+    /// Pages to embed (e.g., "1-10", "1,3,5"). Defaults to the whole document,
+    /// or the first and last pages when skimming, within a size budget.
+    #[serde(default)]
+    pub pages: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SummarizePromptArgs {
+    /// BetterBibTeX citation key of the annotated item
+    pub citekey: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SynthesizePromptArgs {
+    /// BetterBibTeX citation keys of the annotated items, separated by spaces or commas
+    pub citekeys: String,
+    /// Theme to focus the synthesis on
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+#[prompt_router(vis = "pub(super)")]
+impl ZoteroMcpServer {
+    /// Start a reading session with the item's metadata, outline and text.
+    #[prompt(
+        name = "readassist-read",
+        description = "Read a Zotero item with a reading strategy (critical, sq3r, review, analyze, skim) and annotate it with semantic colors. Embeds the item metadata, PDF outline and page text."
+    )]
+    async fn read_prompt(
+        &self,
+        Parameters(args): Parameters<ReadPromptArgs>,
+    ) -> Result<GetPromptResult, McpError> {
+        let strategy = args.strategy.unwrap_or_default();
+        let document = load_document(&self.client, &args.citekey, args.pages.as_deref(), strategy)
+            .await
+            .map_err(mcp_error)?;
+
+        Ok(GetPromptResult {
+            description: Some(format!(
+                "{} reading of {}",
+                strategy.name(),
+                document.item.title.as_deref().unwrap_or(&args.citekey)
+            )),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                read_prompt(&document, strategy, args.purpose.as_deref()),
+            )],
+        })
+    }
+
+    /// Summarize an item from its annotations.
+    #[prompt(
+        name = "readassist-summarize",
+        description = "Write a summary note of an annotated Zotero item, grouped by semantic color, with pandoc citations and permanent notes from IDEA: annotations. Embeds the item's annotations."
+    )]
+    async fn summarize_prompt(
+        &self,
+        Parameters(args): Parameters<SummarizePromptArgs>,
+    ) -> Result<GetPromptResult, McpError> {
+        let annotations = export_annotations(&self.client, &args.citekey)
+            .await
+            .map_err(mcp_error)?;
+
+        Ok(GetPromptResult {
+            description: Some(format!("Summary of {}", args.citekey)),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                summarize_prompt(&args.citekey, &annotations),
+            )],
+        })
+    }
+
+    /// Synthesize several items from their annotations.
+    #[prompt(
+        name = "readassist-synthesize",
+        description = "Write a synthesis note across several annotated Zotero items, organized by theme with source attribution, optionally focused on one theme. Embeds the annotations of every item."
+    )]
+    async fn synthesize_prompt(
+        &self,
+        Parameters(args): Parameters<SynthesizePromptArgs>,
+    ) -> Result<GetPromptResult, McpError> {
+        let citekeys: Vec<&str> = args
+            .citekeys
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(|key| key.trim_start_matches('@'))
+            .filter(|key| !key.is_empty())
+            .collect();
+        if citekeys.is_empty() {
+            return Err(McpError::invalid_params(
+                "At least one citation key is required",
+                None,
+            ));
+        }
+
+        let mut sources = Vec::new();
+        for citekey in &citekeys {
+            let annotations = export_annotations(&self.client, citekey)
+                .await
+                .map_err(mcp_error)?;
+            sources.push((citekey.to_string(), annotations));
+        }
+
+        Ok(GetPromptResult {
+            description: Some(format!("Synthesis of {}", citekeys.join(", "))),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                synthesize_prompt(&sources, args.theme.as_deref()),
+            )],
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! Reference tools: the reference list of a PDF and the library items it cites.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, model::*, schemars, schemars::JsonSchema, tool,
+    tool_router, ErrorData as McpError,
+};
+use serde::{Deserialize, Serialize};
+
+use super::ZoteroMcpServer;
+use crate::reference_tools::{get_references, resolve_citation};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetReferencesParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResolveCitationParams {
+    /// An in-text citation marker ("[12]", "[3, 5-7]", "Smith et al., 2020") or the
+    /// text of a reference entry
+    pub citation: String,
+    /// Zotero attachment key of the citing PDF. Required for citation markers, which
+    /// are looked up in its reference list.
+    #[serde(default)]
+    pub attachment_key: Option<String>,
+}
+
+#[tool_router(router = reference_router, vis = "pub(super)")]
+impl ZoteroMcpServer {
+    /// Get the reference list of a PDF attachment.
+    ///
+    /// Finds the References or Bibliography section, splits it into entries
+    /// and parses each entry into authors, title, year, venue and DOI.
+    #[tool(
+        name = "zotero_get_references",
+        description = "Get the reference list (References/Bibliography section) of a PDF attachment as JSON. Each entry has its label (\"12\" for \"[12]\"), authors, title, year, venue, DOI, 1-based page and full text. Fields that could not be parsed are omitted."
+    )]
+    async fn zotero_get_references(
+        &self,
+        Parameters(params): Parameters<GetReferencesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match get_references(&self.client, &params.attachment_key).await {
+            Ok(result) if result.references.is_empty() => {
+                Ok(CallToolResult::success(vec![Content::text(
+                    "The reference section has no entries that could be parsed",
+                )]))
+            }
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize references".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Check whether the works a citation refers to are in the library.
+    ///
+    /// Markers are looked up in the reference list of the citing PDF; each
+    /// cited reference is matched by DOI, then by title.
+    #[tool(
+        name = "zotero_resolve_citation",
+        description = "Check whether a cited paper is already in the Zotero library. Pass an in-text citation marker (\"[12]\", \"Smith et al., 2020\") with the attachment_key of the citing PDF, or the text of a reference entry. Each cited reference is matched by DOI, then by a fuzzy title match; returns in_library and, if found, the citekey and item key."
+    )]
+    async fn zotero_resolve_citation(
+        &self,
+        Parameters(params): Parameters<ResolveCitationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match resolve_citation(
+            &self.client,
+            &params.citation,
+            params.attachment_key.as_deref(),
+        )
+        .await
+        {
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize citation".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Search tools: finding text across the library and within a PDF.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, model::*, schemars, schemars::JsonSchema, tool,
+    tool_router, ErrorData as McpError,
+};
+use serde::{Deserialize, Serialize};
+use zotero_client::pdf::SearchOptions;
+
+use super::ZoteroMcpServer;
+use crate::fulltext_tools::fulltext_search;
+use crate::search_tools::search_pdf;
+
+/// Default number of full-text search hits.
+const DEFAULT_LIMIT: usize = 10;
+
+/// Maximum number of full-text search hits.
+const MAX_LIMIT: usize = 100;
+
+/// Default number of matches returned by a PDF search.
+const DEFAULT_MAX_MATCHES: usize = 50;
+
+/// Maximum number of matches returned by a PDF search.
+const MAX_MATCHES: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FulltextSearchParams {
+    /// Words to search for in the text of every PDF in the library
+    pub query: String,
+    /// Maximum number of pages to return (default: 10, maximum: 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Synchronize the index with the library before searching, to include PDFs
+    /// added or changed since it was last synchronized (default: false). Slow on
+    /// large libraries.
+    #[serde(default)]
+    pub refresh: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchPdfParams {
+    /// Zotero attachment key for the PDF
+    pub attachment_key: String,
+    /// Text to find. Whitespace matches line breaks, and words hyphenated at a line end
+    /// are found whole.
+    pub query: String,
+    /// Match letter case exactly (default: false)
+    #[serde(default)]
+    pub case_sensitive: Option<bool>,
+    /// Only match whole words (default: false)
+    #[serde(default)]
+    pub whole_word: Option<bool>,
+    /// Treat the query as a regular expression, e.g. "p\s*<\s*0\.05" (default: false)
+    #[serde(default)]
+    pub regex: Option<bool>,
+    /// Pages to search (e.g., "1-5", "1,3,5"). Searches all pages if omitted.
+    #[serde(default)]
+    pub pages: Option<String>,
+    /// Maximum number of matches to return (default: 50, maximum: 500)
+    #[serde(default)]
+    pub max_results: Option<usize>,
+}
+
+#[tool_router(router = search_router, vis = "pub(super)")]
+impl ZoteroMcpServer {
+    /// Search the text of every PDF in the library.
+    ///
+    /// Pages are ranked with BM25 over a local index that is updated
+    /// incrementally: only PDFs whose file changed are extracted again.
+    #[tool(
+        name = "zotero_fulltext_search",
+        description = "Search the text of every PDF in the Zotero library (\"which of my papers mention X\"). Returns the best matching pages with citekey, attachment key, 1-based page and a snippet. Searches a local index that is only synchronized with the library when refresh is set (or by running `zotero-mcp index`): set refresh on the first search and to include PDFs added or changed since updated_at. Synchronizing can take a while for large libraries."
+    )]
+    async fn zotero_fulltext_search(
+        &self,
+        Parameters(params): Parameters<FulltextSearchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        match fulltext_search(
+            &self.client,
+            &self.fulltext,
+            &params.query,
+            limit,
+            params.refresh.unwrap_or(false),
+        )
+        .await
+        {
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize search results".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    /// Search the text of one PDF attachment.
+    ///
+    /// Returns every match with its page, a snippet and the rectangles of the
+    /// matched text, so the right pages can be read or annotated directly.
+    #[tool(
+        name = "zotero_search_pdf",
+        description = "Search inside one PDF attachment. Returns the 1-based page, a snippet and the rectangles [x1, y1, x2, y2] of every match, so you can jump to the right pages before calling zotero_read_pdf_pages. Case-insensitive by default; supports whole_word, case_sensitive and regex options and a page range."
+    )]
+    async fn zotero_search_pdf(
+        &self,
+        Parameters(params): Parameters<SearchPdfParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let options = SearchOptions {
+            case_sensitive: params.case_sensitive.unwrap_or(false),
+            whole_word: params.whole_word.unwrap_or(false),
+            regex: params.regex.unwrap_or(false),
+        };
+        let max_matches = params
+            .max_results
+            .unwrap_or(DEFAULT_MAX_MATCHES)
+            .clamp(1, MAX_MATCHES);
+
+        match search_pdf(
+            &self.client,
+            &params.attachment_key,
+            &params.query,
+            options,
+            params.pages.as_deref(),
+            max_matches,
+        )
+        .await
+        {
+            Ok(result) if result.matches.is_empty() => {
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "No matches found for: {}",
+                    params.query
+                ))]))
+            }
+            Ok(result) => {
+                let json = serde_json::to_string_pretty(&result)
+                    .unwrap_or_else(|_| "Failed to serialize matches".to_string());
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Table extraction tool implementation for the Zotero MCP server.
+
+use rmcp::{schemars, schemars::JsonSchema};
+use serde::{Deserialize, Serialize};
+use zotero_client::pdf::{extract_table as extract_pdf_table, get_page_count, Table};
+use zotero_client::ZoteroClient;
+
+use super::tools::{find_pdf_path, ToolError};
+
+/// Output format for extracted tables.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormatParam {
+    /// Markdown pipe table with the first row as header - default
+    #[default]
+    Markdown,
+    /// Comma-separated values
+    Csv,
+}
+
+/// Result of `zotero_extract_table`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableResult {
+    /// 1-based page number
+    pub page: u32,
+    /// Area of the table [x1, y1, x2, y2] in PDF coordinates
+    pub rect: [f64; 4],
+    /// Number of rows, including the header
+    pub rows: usize,
+    /// Number of columns
+    pub columns: usize,
+    /// The table in the requested format
+    pub table: String,
+}
+
+impl TableResult {
+    fn new(table: Table, format: TableFormatParam) -> Self {
+        let text = match format {
+            TableFormatParam::Markdown => table.to_markdown(),
+            TableFormatParam::Csv => table.to_csv(),
+        };
+        Self {
+            page: table.page + 1,
+            rect: table.rect,
+            rows: table.rows.len(),
+            columns: table.columns(),
+            table: text,
+        }
+    }
+}
+
+/// Extract the rows and columns of a table on a 1-based page of a PDF attachment.
+///
+/// Without `rect`, the table drawn by the most horizontal rules on the page is read.
+pub async fn extract_table(
+    client: &ZoteroClient,
+    attachment_key: &str,
+    page: u32,
+    rect: Option<[f64; 4]>,
+    format: TableFormatParam,
+) -> Result<TableResult, ToolError> {
+    let path = find_pdf_path(client, attachment_key).await?;
+    let total_pages = get_page_count(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+    if page == 0 || page as usize > total_pages {
+        return Err(ToolError::PageOutOfRange(page, total_pages));
+    }
+
+    let table = extract_pdf_table(&path, page as usize - 1, rect)
+        .map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+    Ok(TableResult::new(table, format))
+}
@@ -1,13 +1,16 @@
 //! Tool implementations for the Zotero MCP server.
 
+use std::sync::Arc;
+
 use rmcp::{schemars, schemars::JsonSchema};
 use serde::{Deserialize, Serialize};
 use zotero_client::{
     pdf::{
-        extract_text, get_page_count, get_pdf_outline, resolve_sections_to_pages, search_for_rects,
+        extract_section_text, extract_text, extract_text_in_reading_order, get_page_count,
+        resolve_section_to_pages, resolve_sections_to_pages, LayoutOptions, OutlineCache,
     },
-    types::{CreateAnnotationRequest, CreateAreaAnnotationRequest, HighlightColor, PdfOutline},
-    ZoteroClient,
+    types::{HighlightColor, PdfOutline},
+    ZoteroClient, ZoteroClientError,
 };
 
 /// Semantic color parameter for MCP tools.
@@ -47,6 +50,19 @@ impl From<HighlightColorParam> for HighlightColor {
     }
 }
 
+/// How `zotero_read_pdf_pages` extracts text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionMode {
+    /// Whole pages in MuPDF's block order, as stored in the PDF
+    #[default]
+    Native,
+    /// Whole pages with columns read one after the other, for multi-column papers
+    ReadingOrder,
+    /// Only the requested sections, from their heading to the next heading, in reading order
+    Section,
+}
+
 /// Error type for tool operations.
 #[derive(Debug, thiserror::Error)]
 pub enum ToolError {
@@ -68,12 +84,47 @@ pub enum ToolError {
     #[error("Text not found on page {0}: {1}")]
     TextNotFound(u32, String),
 
+    #[error(
+        "Best match on page {0} scored {1:.2}, below the minimum of {2:.2}. Matched text: {3}"
+    )]
+    MatchBelowThreshold(u32, f64, f64, String),
+
     #[error("Section not found: {0}")]
     SectionNotFound(String),
 
-    #[error("PDF has no outline. Please use page numbers instead.")]
+    #[error("PDF has no outline and no section headings were recognized. Please use page numbers instead.")]
     NoOutline,
 
+    #[error("Invalid citation: {0}")]
+    InvalidCitation(String),
+
+    #[error("No entry of the reference list matches the citation: {0}")]
+    CitationNotFound(String),
+
+    #[error("Figure not found: {0}")]
+    FigureNotFound(String),
+
+    #[error("Annotation not found for key: {0}")]
+    AnnotationNotFound(String),
+
+    #[error("Annotation operation failed: {0}")]
+    AnnotationFailed(String),
+
+    #[error("Invalid ink path: {0}")]
+    InvalidInkPath(String),
+
+    #[error("Invalid annotation position: {0}")]
+    InvalidPosition(String),
+
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    #[error("Note operation failed: {0}")]
+    NoteFailed(String),
+
+    #[error("Unknown resource URI: {0}")]
+    InvalidResourceUri(String),
+
     #[error("Zotero client error: {0}")]
     ClientError(#[from] zotero_client::ZoteroClientError),
 
@@ -130,7 +181,7 @@ pub async fn lookup_item(client: &ZoteroClient, citekey: &str) -> Result<String,
 }
 
 /// Parse a page range string into a list of page numbers.
-fn parse_page_range(pages: &str, total_pages: usize) -> Result<Vec<usize>, ToolError> {
+pub(crate) fn parse_page_range(pages: &str, total_pages: usize) -> Result<Vec<usize>, ToolError> {
     let pages = pages.trim().to_lowercase();
 
     if pages == "all" {
@@ -194,46 +245,77 @@ fn parse_page_range(pages: &str, total_pages: usize) -> Result<Vec<usize>, ToolE
 }
 
 /// Find the PDF file path for an attachment key.
-async fn find_pdf_path(client: &ZoteroClient, attachment_key: &str) -> Result<String, ToolError> {
-    let items = client.list_items(500).await?;
-
-    for parent_item in &items {
-        let pdfs = client.get_pdf_attachments(&parent_item.key).await?;
-        for pdf in pdfs {
-            if pdf.key == attachment_key {
-                if let Some(path) = pdf.path {
-                    if std::path::Path::new(&path).exists() {
-                        return Ok(path);
-                    } else {
-                        return Err(ToolError::FileNotFound(path));
-                    }
-                }
-            }
+///
+/// Resolves the key directly through the client's attachment index, so the
+/// lookup cost does not depend on the size of the library.
+pub(crate) async fn find_pdf_path(
+    client: &ZoteroClient,
+    attachment_key: &str,
+) -> Result<String, ToolError> {
+    match client.resolve_pdf_path(attachment_key).await {
+        Ok(path) => Ok(path.display().to_string()),
+        Err(ZoteroClientError::NotFound { .. }) => {
+            Err(ToolError::PdfNotFound(attachment_key.to_string()))
         }
+        Err(ZoteroClientError::FileNotFound { path }) => Err(ToolError::FileNotFound(path)),
+        Err(e) => Err(e.into()),
     }
-
-    Err(ToolError::PdfNotFound(attachment_key.to_string()))
 }
 
 /// Get the PDF outline (table of contents/bookmarks).
+///
+/// Falls back to an outline inferred from section headings if the PDF has
+/// no bookmarks. The outline is kept in `cache` until the file changes.
 pub async fn get_outline(
     client: &ZoteroClient,
+    cache: &OutlineCache,
     attachment_key: &str,
 ) -> Result<PdfOutline, ToolError> {
     let path = find_pdf_path(client, attachment_key).await?;
-    get_pdf_outline(&path).map_err(|e| ToolError::PdfError(e.to_string()))
+    let outline = pdf_outline(cache, &path).await?;
+    Ok(PdfOutline::clone(&outline))
+}
+
+/// The outline of a PDF from `cache`.
+///
+/// Inferring an outline reads the text of every page, so it happens on a
+/// blocking thread rather than holding up the other sessions of the server.
+async fn pdf_outline(cache: &OutlineCache, path: &str) -> Result<Arc<PdfOutline>, ToolError> {
+    let cache = cache.clone();
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || cache.get(path))
+        .await
+        .map_err(|e| ToolError::PdfError(e.to_string()))?
+        .map_err(|e| ToolError::PdfError(e.to_string()))
 }
 
 /// Read text from specific pages of a PDF attachment.
-/// Supports either page ranges or section names (if PDF has outline).
+/// Supports either page ranges or section names (from the PDF outline, or from
+/// its section headings if it has none).
+///
+/// [`ExtractionMode::Section`] requires `section`. `strip_headers_footers`
+/// does not apply to [`ExtractionMode::Native`].
 pub async fn read_pdf_pages(
     client: &ZoteroClient,
+    cache: &OutlineCache,
     attachment_key: &str,
     pages: Option<&str>,
     section: Option<&str>,
+    mode: ExtractionMode,
+    strip_headers_footers: bool,
 ) -> Result<String, ToolError> {
     let path = find_pdf_path(client, attachment_key).await?;
 
+    if mode == ExtractionMode::Section {
+        let Some(sections) = section else {
+            return Err(ToolError::InvalidPageRange(
+                "The 'section' parameter is required in section mode".to_string(),
+            ));
+        };
+        let outline = pdf_outline(cache, &path).await?;
+        return read_sections(&outline, &path, sections, strip_headers_footers);
+    }
+
     // Get total page count
     let total_pages = get_page_count(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
 
@@ -245,7 +327,7 @@ pub async fn read_pdf_pages(
         }
         (None, Some(s)) => {
             // Use section names
-            let outline = get_pdf_outline(&path).map_err(|e| ToolError::PdfError(e.to_string()))?;
+            let outline = pdf_outline(cache, &path).await?;
             if !outline.has_outline {
                 return Err(ToolError::NoOutline);
             }
@@ -272,150 +354,56 @@ pub async fn read_pdf_pages(
         }
         result.push_str(&format!("--- Page {} ---\n\n", page_num + 1));
 
-        let text = extract_text(&path, page_num).map_err(|e| ToolError::PdfError(e.to_string()))?;
-        result.push_str(&text);
-    }
-
-    Ok(result)
-}
-
-/// Create a text highlight annotation.
-pub async fn create_highlight(
-    client: &ZoteroClient,
-    attachment_key: &str,
-    text: &str,
-    page: u32,
-    color: HighlightColorParam,
-    comment: Option<&str>,
-) -> Result<String, ToolError> {
-    // Find the PDF file path
-    let items = client.list_items(500).await?;
-    let mut pdf_path: Option<String> = None;
-
-    for parent_item in &items {
-        let pdfs = client.get_pdf_attachments(&parent_item.key).await?;
-        for pdf in pdfs {
-            if pdf.key == attachment_key {
-                pdf_path = pdf.path;
-                break;
+        let text = match mode {
+            ExtractionMode::Native => extract_text(&path, page_num),
+            ExtractionMode::ReadingOrder | ExtractionMode::Section => {
+                extract_text_in_reading_order(
+                    &path,
+                    page_num,
+                    LayoutOptions {
+                        strip_headers_footers,
+                    },
+                )
             }
         }
-        if pdf_path.is_some() {
-            break;
-        }
-    }
-
-    let path = pdf_path.ok_or_else(|| ToolError::PdfNotFound(attachment_key.to_string()))?;
-
-    // Verify the file exists
-    if !std::path::Path::new(&path).exists() {
-        return Err(ToolError::FileNotFound(path));
-    }
-
-    // Convert 1-based page to 0-based index
-    let page_index = page.saturating_sub(1) as usize;
-
-    // Search for the text to get coordinates
-    let rects = search_for_rects(&path, page_index, text)
         .map_err(|e| ToolError::PdfError(e.to_string()))?;
-
-    if rects.is_empty() {
-        return Err(ToolError::TextNotFound(page, text.to_string()));
-    }
-
-    // Convert to the format expected by Zotero
-    let rects_vec: Vec<Vec<f64>> = rects.iter().map(|r| r.to_vec()).collect();
-
-    // Create the annotation request
-    let semantic_color: HighlightColor = color.into();
-    let mut request =
-        CreateAnnotationRequest::highlight(attachment_key, text, page_index as u32, rects_vec)
-            .with_semantic_color(semantic_color);
-
-    if let Some(c) = comment {
-        request = request.with_comment(c);
+        result.push_str(&text);
     }
 
-    // Create the annotation
-    let response = client.create_annotation(request).await?;
-
-    if response.success {
-        let ann_key = response
-            .annotation
-            .and_then(|a| a.key)
-            .unwrap_or_else(|| "(unknown)".to_string());
-        Ok(format!(
-            "Created highlight annotation: {}\n\
-             Text: {}\n\
-             Page: {}\n\
-             Color: {} ({})",
-            ann_key,
-            text,
-            page,
-            semantic_color.to_hex(),
-            semantic_color.description()
-        ))
-    } else {
-        Err(ToolError::PdfError(
-            response
-                .error
-                .unwrap_or_else(|| "Unknown error".to_string()),
-        ))
-    }
+    Ok(result)
 }
 
-/// Create an area/image annotation.
-pub async fn create_area_annotation(
-    client: &ZoteroClient,
-    attachment_key: &str,
-    page: u32,
-    rect: [f64; 4],
-    color: HighlightColorParam,
-    comment: Option<&str>,
+/// Read comma-separated sections from their heading to the next heading.
+fn read_sections(
+    outline: &PdfOutline,
+    path: &str,
+    sections: &str,
+    strip_headers_footers: bool,
 ) -> Result<String, ToolError> {
-    // Convert 1-based page to 0-based index
-    let page_index = page.saturating_sub(1);
-
-    // Create the annotation request
-    let semantic_color: HighlightColor = color.into();
-    let mut request = CreateAreaAnnotationRequest::new(attachment_key, page_index, rect)
-        .with_semantic_color(semantic_color);
-
-    if let Some(c) = comment {
-        request = request.with_comment(c);
+    if !outline.has_outline {
+        return Err(ToolError::NoOutline);
     }
+    let options = LayoutOptions {
+        strip_headers_footers,
+    };
 
-    // Create the annotation
-    let response = client.create_area_annotation(request).await?;
-
-    if response.success {
-        let ann_key = response
-            .annotation
-            .and_then(|a| a.key)
-            .unwrap_or_else(|| "(unknown)".to_string());
-        Ok(format!(
-            "Created area annotation: {}\n\
-             Page: {}\n\
-             Rect: [{:.1}, {:.1}, {:.1}, {:.1}]\n\
-             Color: {} ({})\n\
-             Comment: {}",
-            ann_key,
-            page,
-            rect[0],
-            rect[1],
-            rect[2],
-            rect[3],
-            semantic_color.to_hex(),
-            semantic_color.description(),
-            comment.unwrap_or("(none)")
-        ))
-    } else {
-        Err(ToolError::PdfError(
-            response
-                .error
-                .unwrap_or_else(|| "Unknown error".to_string()),
-        ))
+    let mut result = String::new();
+    for section in sections.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        resolve_section_to_pages(outline, section)
+            .map_err(|e| ToolError::SectionNotFound(e.to_string()))?;
+        let pages = extract_section_text(path, outline, section, options)
+            .map_err(|e| ToolError::PdfError(e.to_string()))?;
+
+        for page in pages {
+            if !result.is_empty() {
+                result.push_str("\n\n");
+            }
+            result.push_str(&format!("--- Page {} ---\n\n", page.page + 1));
+            result.push_str(&page.text);
+        }
     }
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -455,6 +443,15 @@ mod tests {
         assert!(parse_page_range("0", 10).is_err());
     }
 
+    #[test]
+    fn test_extraction_mode_deserialization() {
+        let mode: ExtractionMode = serde_json::from_str("\"reading_order\"").unwrap();
+        assert_eq!(mode, ExtractionMode::ReadingOrder);
+        let mode: ExtractionMode = serde_json::from_str("\"section\"").unwrap();
+        assert_eq!(mode, ExtractionMode::Section);
+        assert_eq!(ExtractionMode::default(), ExtractionMode::Native);
+    }
+
     #[test]
     fn test_highlight_color_param_conversion() {
         let param = HighlightColorParam::Positive;
@@ -11,7 +11,7 @@
 
 use std::env;
 use std::fs;
-use zotero_client::image::{detect_figures, render_page, render_region, ImageFormat};
+use zotero_client::image::{detect_figures, render_page, render_region, ImageFormat, RenderLimits};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
@@ -31,7 +31,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 1: Render full page
     println!("=== Test 1: Render Full Page ===");
-    match render_page(pdf_path, page_index, 150, ImageFormat::Png) {
+    match render_page(
+        pdf_path,
+        page_index,
+        150,
+        ImageFormat::Png,
+        RenderLimits::default(),
+    ) {
         Ok(output) => {
             println!("  Success!");
             println!("  Size: {}x{} pixels", output.width, output.height);
@@ -75,8 +81,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // Test 3: Render first figure
                 if let Some(first_fig) = figures.first() {
                     println!("\n=== Test 3: Render First Figure ===");
-                    match render_region(pdf_path, page_index, first_fig.rect, 150, ImageFormat::Png)
-                    {
+                    match render_region(
+                        pdf_path,
+                        page_index,
+                        first_fig.rect,
+                        150,
+                        ImageFormat::Png,
+                        RenderLimits::default(),
+                    ) {
                         Ok(output) => {
                             println!("  Success!");
                             println!("  Size: {}x{} pixels", output.width, output.height);
@@ -6,7 +6,8 @@
  * 
  * Endpoints:
  *   GET  /mcp/ping              - Check if the plugin is active
- *   POST /mcp/annotations       - Create a new annotation
+ *   POST /mcp/annotations       - Create, update or delete an annotation
+ *   POST /mcp/notes             - Create or update a note
  *   POST /mcp/items             - Get item details by key
  *   POST /mcp/search            - Search for items
  *   POST /mcp/children          - Get child items
@@ -80,7 +81,7 @@ function registerEndpoints() {
         }
     });
     
-    // Create annotation endpoint
+    // Annotation endpoint - create (default), update or delete via "action"
     registerEndpoint("/mcp/annotations", {
         supportedMethods: ["POST"],
         supportedDataTypes: ["application/json", "text/plain"],
@@ -107,6 +108,22 @@ function registerEndpoints() {
                     data = {};
                 }
                 
+                let action = data.action || "create";
+                if (action === "update") {
+                    await updateAnnotation(data, sendResponseCallback);
+                    return;
+                }
+                if (action === "delete") {
+                    await deleteAnnotation(data, sendResponseCallback);
+                    return;
+                }
+                if (action !== "create") {
+                    sendResponseCallback(400, "application/json", JSON.stringify({
+                        error: "Unknown action: " + action
+                    }));
+                    return;
+                }
+                
                 // Validate required fields
                 if (!data.parentItemKey) {
                     sendResponseCallback(400, "application/json", JSON.stringify({
@@ -203,7 +220,7 @@ function registerEndpoints() {
                 }));
                 
             } catch (e) {
-                log("Error creating annotation: " + e);
+                log("Error handling annotation request: " + e);
                 sendResponseCallback(500, "application/json", JSON.stringify({
                     error: "Internal error",
                     message: e.message
@@ -687,9 +704,267 @@ function registerEndpoints() {
         }
     });
     
+    // Note endpoint - create (default) or update via "action"
+    registerEndpoint("/mcp/notes", {
+        supportedMethods: ["POST"],
+        supportedDataTypes: ["application/json", "text/plain"],
+        init: async function(requestData, sendResponseCallback) {
+            try {
+                let data;
+                if (typeof requestData === 'object' && requestData !== null) {
+                    data = requestData;
+                } else if (typeof requestData === 'string') {
+                    try {
+                        data = JSON.parse(requestData);
+                    } catch (e) {
+                        sendResponseCallback(400, "application/json", JSON.stringify({
+                            error: "Invalid JSON",
+                            message: e.message
+                        }));
+                        return;
+                    }
+                } else {
+                    data = {};
+                }
+                
+                let action = data.action || "create";
+                if (action === "create") {
+                    await createNote(data, sendResponseCallback);
+                } else if (action === "update") {
+                    await updateNote(data, sendResponseCallback);
+                } else {
+                    sendResponseCallback(400, "application/json", JSON.stringify({
+                        error: "Unknown action: " + action
+                    }));
+                }
+                
+            } catch (e) {
+                log("Error handling note request: " + e);
+                sendResponseCallback(500, "application/json", JSON.stringify({
+                    error: "Internal error",
+                    message: e.message
+                }));
+            }
+        }
+    });
+    
     log("Registered " + Object.keys(MCP_Zotero.endpoints).length + " MCP endpoints");
 }
 
+/**
+ * Look up an annotation item by key, returning null if the key does not
+ * exist or does not refer to an annotation.
+ */
+async function getAnnotationByKey(key) {
+    let item = await Zotero.Items.getByLibraryAndKeyAsync(
+        Zotero.Libraries.userLibraryID,
+        key
+    );
+    if (!item || !item.isAnnotation()) {
+        return null;
+    }
+    return item;
+}
+
+/**
+ * Update fields of an existing annotation. Only fields present in the
+ * request are changed; an empty comment clears the comment.
+ */
+async function updateAnnotation(data, sendResponseCallback) {
+    if (!data.key) {
+        sendResponseCallback(400, "application/json", JSON.stringify({
+            error: "Missing required field: key"
+        }));
+        return;
+    }
+    
+    let annotation = await getAnnotationByKey(data.key);
+    if (!annotation) {
+        sendResponseCallback(404, "application/json", JSON.stringify({
+            error: "Annotation not found",
+            key: data.key
+        }));
+        return;
+    }
+    
+    if (data.text !== undefined) {
+        annotation.annotationText = data.text;
+    }
+    
+    if (data.comment !== undefined) {
+        annotation.annotationComment = data.comment;
+    }
+    
+    if (data.color !== undefined) {
+        annotation.annotationColor = data.color;
+    }
+    
+    if (data.pageLabel !== undefined) {
+        annotation.annotationPageLabel = String(data.pageLabel);
+    }
+    
+    if (data.position !== undefined) {
+        if (typeof data.position === 'object') {
+            annotation.annotationPosition = JSON.stringify(data.position);
+        } else {
+            annotation.annotationPosition = data.position;
+        }
+    }
+    
+    if (data.sortIndex !== undefined) {
+        annotation.annotationSortIndex = data.sortIndex;
+    } else if (data.position && typeof data.position === 'object' && data.position.pageIndex !== undefined) {
+        // Keep the annotation ordered with its new page
+        annotation.annotationSortIndex = String(data.position.pageIndex).padStart(5, '0') + "|000000|00000";
+    }
+    
+    await annotation.saveTx();
+    
+    log("Updated annotation: " + annotation.key);
+    
+    sendResponseCallback(200, "application/json", JSON.stringify({
+        success: true,
+        annotation: {
+            id: annotation.id,
+            key: annotation.key,
+            parentItemKey: annotation.parentItem ? annotation.parentItem.key : null,
+            type: annotation.annotationType,
+            text: annotation.annotationText,
+            comment: annotation.annotationComment,
+            color: annotation.annotationColor,
+            pageLabel: annotation.annotationPageLabel,
+            sortIndex: annotation.annotationSortIndex,
+            position: annotation.annotationPosition
+        }
+    }));
+}
+
+/**
+ * Permanently delete an annotation.
+ */
+async function deleteAnnotation(data, sendResponseCallback) {
+    if (!data.key) {
+        sendResponseCallback(400, "application/json", JSON.stringify({
+            error: "Missing required field: key"
+        }));
+        return;
+    }
+    
+    let annotation = await getAnnotationByKey(data.key);
+    if (!annotation) {
+        sendResponseCallback(404, "application/json", JSON.stringify({
+            error: "Annotation not found",
+            key: data.key
+        }));
+        return;
+    }
+    
+    await annotation.eraseTx();
+    
+    log("Deleted annotation: " + data.key);
+    
+    sendResponseCallback(200, "application/json", JSON.stringify({
+        success: true,
+        key: data.key
+    }));
+}
+
+/**
+ * Serialize a note item for responses.
+ */
+function noteJSON(note) {
+    return {
+        id: note.id,
+        key: note.key,
+        parentItemKey: note.parentItem ? note.parentItem.key : null,
+        title: note.getNoteTitle(),
+        note: note.getNote()
+    };
+}
+
+/**
+ * Create a note, as a child of a regular item when parentItemKey is given
+ * or as a standalone note otherwise.
+ */
+async function createNote(data, sendResponseCallback) {
+    if (typeof data.note !== 'string') {
+        sendResponseCallback(400, "application/json", JSON.stringify({
+            error: "Missing required field: note"
+        }));
+        return;
+    }
+    
+    let note = new Zotero.Item('note');
+    note.libraryID = Zotero.Libraries.userLibraryID;
+    
+    if (data.parentItemKey) {
+        let parentItem = await Zotero.Items.getByLibraryAndKeyAsync(
+            Zotero.Libraries.userLibraryID,
+            data.parentItemKey
+        );
+        if (!parentItem) {
+            sendResponseCallback(404, "application/json", JSON.stringify({
+                error: "Parent item not found",
+                key: data.parentItemKey
+            }));
+            return;
+        }
+        if (!parentItem.isRegularItem()) {
+            sendResponseCallback(400, "application/json", JSON.stringify({
+                error: "Notes can only be added to regular items",
+                key: data.parentItemKey
+            }));
+            return;
+        }
+        note.libraryID = parentItem.libraryID;
+        note.parentID = parentItem.id;
+    }
+    
+    note.setNote(data.note);
+    await note.saveTx();
+    
+    log("Created note: " + note.key + (data.parentItemKey ? " on item " + data.parentItemKey : ""));
+    
+    sendResponseCallback(201, "application/json", JSON.stringify({
+        success: true,
+        note: noteJSON(note)
+    }));
+}
+
+/**
+ * Replace the content of an existing note.
+ */
+async function updateNote(data, sendResponseCallback) {
+    if (!data.key || typeof data.note !== 'string') {
+        sendResponseCallback(400, "application/json", JSON.stringify({
+            error: "Missing required fields: key, note"
+        }));
+        return;
+    }
+    
+    let note = await Zotero.Items.getByLibraryAndKeyAsync(
+        Zotero.Libraries.userLibraryID,
+        data.key
+    );
+    if (!note || !note.isNote()) {
+        sendResponseCallback(404, "application/json", JSON.stringify({
+            error: "Note not found",
+            key: data.key
+        }));
+        return;
+    }
+    
+    note.setNote(data.note);
+    await note.saveTx();
+    
+    log("Updated note: " + note.key);
+    
+    sendResponseCallback(200, "application/json", JSON.stringify({
+        success: true,
+        note: noteJSON(note)
+    }));
+}
+
 function registerEndpoint(path, handler) {
     Zotero.Server.Endpoints[path] = function() {};
     Zotero.Server.Endpoints[path].prototype = handler;